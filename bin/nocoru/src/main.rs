@@ -8,10 +8,14 @@ mod scene;
 mod systems;
 
 use hell_app::HellGame;
+use hell_common::window::HellWindowExtent;
 use hell_core::prelude::*;
-use hell_input::InputManager;
+use hell_core::logging::{init_logging, target, LogConfig};
+use hell_core::profiling;
+use hell_input::{InputManager, KeyCode};
 use hell_renderer::{render_types::RenderPackage, HellRenderer};
 use hell_winit::Window;
+use tracing::info;
 
 use self::scene::NocoruScene;
 
@@ -19,6 +23,10 @@ use self::scene::NocoruScene;
 
 
 fn main() -> HellResult<()> {
+    let _log = init_logging(&LogConfig::default())?;
+    // every profiled scope locks the global profiler, so release builds only profile on request
+    profiling::set_enabled_from_env(cfg!(debug_assertions));
+
     let win = Window::new("hell-app", 800, 600).expect("failed to create window");
 
     let game = Box::new(NocoruGame::new());
@@ -37,14 +45,18 @@ fn main() -> HellResult<()> {
 
 struct NocoruGame {
     scene_1: NocoruScene,
+    was_trace_down: bool,
 }
 
 impl NocoruGame {
+    pub const TRACE_FILE_PATH: &'static str = "nocoru_trace.json";
+
     pub fn new() -> Self {
         let scene_1 = NocoruScene::new();
 
         Self {
             scene_1,
+            was_trace_down: false,
         }
     }
 }
@@ -58,7 +70,19 @@ impl HellGame for NocoruGame {
     }
 
     fn update_game(&mut self, delta_time: f32, input: &InputManager) -> HellResult<()> {
+        let is_trace_down = input.key_state(KeyCode::F12).is_down();
+        if is_trace_down && !self.was_trace_down && profiling::is_enabled() {
+            profiling::write_chrome_trace(Self::TRACE_FILE_PATH)?;
+            info!(target: target::GAME, "wrote profiler trace to '{}'", Self::TRACE_FILE_PATH);
+        }
+        self.was_trace_down = is_trace_down;
+
         self.scene_1.update_scene(delta_time, input)
     }
+
+    fn handle_window_changed(&mut self, window_extent: HellWindowExtent) -> HellResult<()> {
+        self.scene_1.handle_window_changed(window_extent);
+        Ok(())
+    }
 }
 
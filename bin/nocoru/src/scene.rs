@@ -1,12 +1,22 @@
+use std::sync::Arc;
+
 use hell_core::prelude::*;
+use hell_core::logging::target;
 use hell_common::transform::Transform;
+use hell_common::window::HellWindowExtent;
 use hell_gui::text::{HellFont, TextMesh};
+use hell_gui::ui::{Align, Anchor, FlexStyle, FlexTree, Justify, Length, Ui, UiInput, UiScaleMode, UiScreen, UiTheme};
 use hell_input::{KeyCode, InputManager};
+use hell_math::random::Rng;
+use hell_physics::character::{CharacterConfig, CharacterController, CharacterInput};
 use hell_physics::collision::AABB2D;
-use hell_renderer::{render_types::RenderPackage, HellRenderer};
+use hell_physics::world::{PhysicsWorld, RigidBody};
+use hell_renderer::{render_types::{RenderPackage, SdfStyle}, resources::ResourceHandle, HellRenderer};
 use hell_resources::fonts::FntFile;
+use hell_resources::locale::Localization;
+use tracing::{info, warn};
 
-use crate::systems::{MovementSystem, MovementData, EnemySpawnSystem, EnemyKillSystem, EneymCollisionSystem, EnvironmentCollisionSystem, JumpSystem, GravitySystem};
+use crate::systems::{MovementSystem, MovementData, EnemySpawnSystem, EnemyKillSystem, EneymCollisionSystem};
 
 
 
@@ -15,20 +25,33 @@ pub struct NocoruScene {
     pub movement_data: Vec<MovementData>,
     pub colliders: Vec<AABB2D>,
     pub is_alive: Vec<bool>,
-    is_grounded: Vec<bool>,
 
-    gravity_system: GravitySystem,
+    physics: PhysicsWorld,
+    player: CharacterController,
+
     movement_system: MovementSystem,
-    jump_system: JumpSystem,
-    environment_collision_system: EnvironmentCollisionSystem,
     enemy_collision_system: EneymCollisionSystem,
     enemy_spawn_system: EnemySpawnSystem,
     enemy_kill_system: EnemyKillSystem,
 
     ground_distance: f32,
     enemy_distance: f32,
+    next_enemy_distance: f32,
+    rng: Rng,
+    /// distance the world scrolled since the last reset, the score counts whole units of it
+    travelled: f32,
+    score: u32,
 
     score_txt: TextMesh,
+    localization: Localization,
+    ui: Ui,
+    ui_solid_mat: ResourceHandle,
+    ui_screen: UiScreen,
+    ui_layout: FlexTree,
+    pause_panel: usize,
+    is_paused: bool,
+    was_pause_down: bool,
+    time_scale: f32,
 }
 
 impl NocoruScene {
@@ -61,8 +84,28 @@ impl NocoruScene {
     pub const ENEMY_T1_MAT:  &'static str = "assets/characters/enemy_t1_mat.yaml";
     pub const PLAYER_MAT:    &'static str = "assets/characters/player_mat.yaml";
     pub const FONT_MAT:      &'static str = "assets/fonts/font_bm_fira_code_mat.yaml";
+    pub const SDF_FONT_MAT:  &'static str = "assets/fonts/font_sdf_dejavu_sans_mat.yaml";
+
+    pub const UI_SOLID_MAT:  &'static str = "assets/ui/ui_solid_mat.yaml";
 
     pub const FONT_FILE_PATH: &str = "assets/fonts/font_bm_fira_code.fnt";
+    /// baked with `sdf_font assets/fonts/font_sdf_dejavu_sans.ttf assets/fonts/font_sdf_dejavu_sans.fnt --size 48`, umlauts included
+    pub const SDF_FONT_FILE_PATH: &str = "assets/fonts/font_sdf_dejavu_sans.fnt";
+
+    pub const LOCALE_DIR: &str = "assets/locales";
+    pub const LANGUAGE:   &str = "en";
+
+    /// the ui is scaled to fit this size into the window, so the hud looks the same at every resolution
+    pub const UI_SIZE: glam::Vec2 = glam::Vec2::new(800.0, 600.0);
+    pub const SCORE_TEXT_SIZE: f32 = 32.0;
+    pub const SCORE_TEXT_OFFSET: glam::Vec2 = glam::Vec2::new(16.0, 16.0);
+    /// keeps the score readable on top of the bright sprites
+    pub const SCORE_TEXT_STYLE: SdfStyle = SdfStyle {
+        outline_width: 0.1,
+        outline_color: glam::Vec4::new(0.0, 0.0, 0.0, 1.0),
+        shadow_offset: glam::Vec2::new(2.0, 2.0),
+        shadow_color: glam::Vec4::new(0.0, 0.0, 0.0, 0.5),
+    };
 
     pub const GROUND_SPAWN_Y:     f32 = Self::FLOOR_Y - Self::GROUND_SIZE;
     pub const GROUND_SPAWN_POS:   glam::Vec3 = glam::Vec3::new(5.0, Self::GROUND_SPAWN_Y, 0.0);
@@ -75,10 +118,10 @@ impl NocoruScene {
     pub const WORLD_SCROLL_SPEED: f32 = 5.0;
 
     pub const GROUND_SPAWN_INTERVAL: f32 = Self::GROUND_SIZE;
-    pub const ENEMY_SPAWN_INTERVAL: f32 = 8.0;
+    pub const ENEMY_SPAWN_INTERVAL: std::ops::Range<f32> = 5.0..11.0;
+    pub const SPAWN_SEED: u64 = 0x6e6f636f7275;
 
-    pub const JUMP_FORCE: f32 = 10.0;
-    pub const FALL_FORCE: f32 = -20.0;
+    pub const PLAYER_CONTROLLER: &'static str = "assets/characters/player_controller.yaml";
 }
 
 impl NocoruScene {
@@ -89,41 +132,71 @@ impl NocoruScene {
         let movement_data = vec![MovementData::default(); Self::ENTITY_COUNT];
         let colliders = vec![AABB2D::default(); Self::ENTITY_COUNT];
         let is_alive = vec![false; Self::ENTITY_COUNT];
-        let is_grounded = vec![false; Self::ENTITY_COUNT];
 
-        let gravity_system = GravitySystem::default();
-        let movement_system = MovementSystem::default();
-        let jump_system = JumpSystem::new(Self::JUMP_FORCE, Self::FALL_FORCE);
-        let environment_collision_system = EnvironmentCollisionSystem::new(Self::FLOOR_Y, Self::CEILING_Y);
-        let enemy_collision_system = EneymCollisionSystem::default();
+        // the player's position is kept between the floor and the ceiling
+        let player_collider = colliders[Self::PLAYER_IDX];
+        let mut physics = PhysicsWorld::default();
+        physics.add_body(RigidBody::fixed(AABB2D::new(
+            glam::vec2(-100.0, Self::FLOOR_Y + player_collider.min.y - 10.0),
+            glam::vec2(100.0, Self::FLOOR_Y + player_collider.min.y),
+        )));
+        physics.add_body(RigidBody::fixed(AABB2D::new(
+            glam::vec2(-100.0, Self::CEILING_Y + player_collider.max.y),
+            glam::vec2(100.0, Self::CEILING_Y + player_collider.max.y + 10.0),
+        )));
+        let player = CharacterController::new(CharacterConfig::default(), player_collider, glam::Vec2::ZERO);
+
+        let movement_system = MovementSystem;
+        let enemy_collision_system = EneymCollisionSystem;
 
         let enemy_spawn_system = EnemySpawnSystem::new(glam::vec2(-Self::WORLD_SCROLL_SPEED, 0.0));
         let enemy_kill_system = EnemyKillSystem::new(Self::ENEMY_KILL_POS_X);
 
         let score_txt = TextMesh::new(None);
-
-        let font_path = std::path::Path::new(Self::FONT_FILE_PATH);
-        let _font_file = FntFile::from_file(font_path).unwrap();
+        let ui = Ui::new(UiTheme::default(), None);
+        // updated with the real window extent after the scene was loaded
+        let ui_screen = UiScreen::new(UiScaleMode::Reference(Self::UI_SIZE), HellWindowExtent { width: 800, height: 600, scale_factor: 1.0 });
+
+        // the pause menu stays centered when the window is resized
+        let mut ui_layout = FlexTree::new(ui_screen.size());
+        let ui_root = ui_layout.add(FlexStyle { justify: Justify::Center, align_items: Align::Center, ..FlexStyle::row() });
+        let pause_panel = ui_layout.add_child(ui_root, FlexStyle {
+            width: Length::Px(300.0),
+            height: Length::Px(280.0),
+            ..Default::default()
+        });
 
         Self {
             render_pkg,
             movement_data,
             colliders,
             is_alive,
-            is_grounded,
 
-            gravity_system,
+            physics,
+            player,
+
             movement_system,
-            jump_system,
-            environment_collision_system,
             enemy_collision_system,
             enemy_spawn_system,
             enemy_kill_system,
 
             ground_distance: 0.0,
             enemy_distance: 0.0,
+            next_enemy_distance: Self::ENEMY_SPAWN_INTERVAL.end,
+            rng: Rng::new(Self::SPAWN_SEED),
+            travelled: 0.0,
+            score: 0,
 
             score_txt,
+            localization: Localization::default(),
+            ui,
+            ui_solid_mat: ResourceHandle::default(),
+            ui_screen,
+            ui_layout,
+            pause_panel,
+            is_paused: false,
+            was_pause_down: false,
+            time_scale: 1.0,
         }
     }
 
@@ -131,8 +204,30 @@ impl NocoruScene {
         &self.render_pkg
     }
 
+    /// starts a new run - the score, the spawned enemies and ground tiles and the player are put back to where `load_scene` left them
     pub fn reset_scene(&mut self) {
-        println!("reset scene");
+        info!(target: target::GAME, "reset scene with score {}", self.score);
+
+        let render_data = &mut self.render_pkg.world;
+        self.enemy_spawn_system.prepare(&Self::GROUND_SPAWN_POS, render_data.transforms.locals_mut(Self::GROUND_START_IDX..=Self::GROUND_END_IDX), &mut self.movement_data[Self::GROUND_START_IDX..=Self::GROUND_END_IDX]);
+        self.enemy_spawn_system.prepare(&Self::ENEMY_SPAWN_POS, render_data.transforms.locals_mut(Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX), &mut self.movement_data[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX]);
+        self.is_alive.fill(false);
+
+        self.ground_distance = 0.0;
+        self.enemy_distance = 0.0;
+        self.next_enemy_distance = Self::ENEMY_SPAWN_INTERVAL.end;
+        self.rng = Rng::new(Self::SPAWN_SEED);
+
+        self.player.teleport(glam::Vec2::ZERO);
+        self.movement_data[Self::PLAYER_IDX].velocity = glam::Vec2::ZERO;
+        let player_transform = render_data.transforms.local_mut(Self::PLAYER_IDX);
+        player_transform.translation.x = self.player.position.x;
+        player_transform.translation.y = self.player.position.y;
+
+        self.travelled = 0.0;
+        self.score = 0;
+        self.update_score_text();
+        self.render_pkg.update_transforms();
     }
 
     pub fn load_scene(&mut self, renderer: &mut HellRenderer) -> HellResult<()> {
@@ -160,38 +255,64 @@ impl NocoruScene {
 
         // setup gui
         // ---------
+        self.localization = Localization::from_dir(Self::LOCALE_DIR, Self::LANGUAGE)?;
+        for missing in self.localization.missing_keys() {
+            warn!(target: target::GAME, "language '{}' misses the keys {:?} and the plural forms {:?}", missing.language, missing.keys, missing.plural_forms);
+        }
+
+        // the score is scaled up, so it uses the distance field font to stay sharp
+        let sdf_font_mat = renderer.acquire_material(Self::SDF_FONT_MAT)?;
+        let sdf_font_file = FntFile::from_file(std::path::Path::new(Self::SDF_FONT_FILE_PATH))?;
+        self.score_txt.set_font(Some(HellFont::new(Self::QUAD_MESH, sdf_font_mat.idx, Arc::new(sdf_font_file))));
+        self.score_txt.set_sdf_style(Some(Self::SCORE_TEXT_STYLE));
+        self.update_score_text();
+
         let font_mat = renderer.acquire_material(Self::FONT_MAT)?;
-        let font = HellFont::new(Self::QUAD_MESH, font_mat.idx);
-        self.score_txt.set_font(Some(font));
-        self.score_txt.set_text("H");
+        let font_file = FntFile::from_file(std::path::Path::new(Self::FONT_FILE_PATH))?;
+        let font = HellFont::new(Self::QUAD_MESH, font_mat.idx, Arc::new(font_file));
 
-        for t in self.score_txt.char_transforms() {
-            self.render_pkg.ui.add_data(Self::QUAD_MESH, font_mat, t.clone());
-        }
+        self.ui.set_font(Some(font));
+        self.ui_solid_mat = renderer.acquire_material(Self::UI_SOLID_MAT)?;
+        self.update_ui(UiInput::default());
 
         // setup systems
         // -------------
-        self.enemy_spawn_system.prepare(&Self::GROUND_SPAWN_POS, &mut self.render_pkg.world.transforms[Self::GROUND_START_IDX..=Self::GROUND_END_IDX], &mut self.movement_data);
-        self.enemy_spawn_system.prepare(&Self::ENEMY_SPAWN_POS, &mut self.render_pkg.world.transforms[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX], &mut self.movement_data);
+        self.player.config = CharacterConfig::from_file(Self::PLAYER_CONTROLLER)?;
+        self.enemy_spawn_system.prepare(&Self::GROUND_SPAWN_POS, self.render_pkg.world.transforms.locals_mut(Self::GROUND_START_IDX..=Self::GROUND_END_IDX), &mut self.movement_data);
+        self.enemy_spawn_system.prepare(&Self::ENEMY_SPAWN_POS, self.render_pkg.world.transforms.locals_mut(Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX), &mut self.movement_data);
+        self.render_pkg.update_transforms();
 
         Ok(())
     }
 
     pub fn update_scene(&mut self, delta_time: f32, input: &InputManager) -> HellResult<()> {
+        let is_pause_down = input.key_state(KeyCode::P).is_down();
+        if is_pause_down && !self.was_pause_down {
+            self.is_paused = !self.is_paused;
+        }
+        self.was_pause_down = is_pause_down;
+
+        self.update_ui(UiInput::from_input(input, self.ui_screen.scale()));
+        if self.is_paused {
+            self.render_pkg.update_transforms();
+            return Ok(());
+        }
+
+        let delta_time = delta_time * self.time_scale;
         let render_data = &mut self.render_pkg.world;
 
         // kill
         // ----
         self.enemy_kill_system.execute(
             &Self::GROUND_RESET_POS,
-            &mut render_data.transforms[Self::GROUND_START_IDX..=Self::GROUND_END_IDX],
+            render_data.transforms.locals_mut(Self::GROUND_START_IDX..=Self::GROUND_END_IDX),
             &mut self.movement_data[Self::GROUND_START_IDX..=Self::GROUND_END_IDX],
             &mut self.is_alive[Self::GROUND_START_IDX..=Self::GROUND_END_IDX]
         );
 
         self.enemy_kill_system.execute(
             &Self::ENEMY_RESET_POS,
-            &mut render_data.transforms[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX],
+            render_data.transforms.locals_mut(Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX),
             &mut self.movement_data[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX],
             &mut self.is_alive[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX]
         );
@@ -205,54 +326,134 @@ impl NocoruScene {
 
             let _spawned_ground_idx = self.enemy_spawn_system.execute(
                 Self::GROUND_SPAWN_POS + offset,
-                &mut render_data.transforms[Self::GROUND_START_IDX..=Self::GROUND_END_IDX],
+                render_data.transforms.locals_mut(Self::GROUND_START_IDX..=Self::GROUND_END_IDX),
                 &mut self.movement_data[Self::GROUND_START_IDX..=Self::GROUND_END_IDX],
                 &mut self.is_alive[Self::GROUND_START_IDX..=Self::GROUND_END_IDX]
             );
         }
-        if self.enemy_distance >= Self::ENEMY_SPAWN_INTERVAL {
-            let offset = glam::vec3(-(self.enemy_distance % Self::ENEMY_SPAWN_INTERVAL), 0.0, 0.0);
+        if self.enemy_distance >= self.next_enemy_distance {
+            let offset = glam::vec3(-(self.enemy_distance % self.next_enemy_distance), 0.0, 0.0);
 
             self.enemy_distance = 0.0;
+            self.next_enemy_distance = self.rng.range_f32(Self::ENEMY_SPAWN_INTERVAL);
             let _spawned_enemy_idx = self.enemy_spawn_system.execute(
                 Self::ENEMY_SPAWN_POS + offset,
-                &mut render_data.transforms[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX],
+                render_data.transforms.locals_mut(Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX),
                 &mut self.movement_data[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX],
                 &mut self.is_alive[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX]
             );
         }
 
-        self.environment_collision_system.execute(
-            &mut render_data.transforms[Self::PLAYER_IDX..=Self::PLAYER_IDX],
-            &mut self.movement_data[Self::PLAYER_IDX..=Self::PLAYER_IDX],
-            &mut self.is_grounded[Self::PLAYER_IDX..=Self::PLAYER_IDX]
-        );
-
-        self.gravity_system.execute(&mut self.movement_data[Self::PLAYER_IDX..=Self::PLAYER_IDX], delta_time);
+        // player
+        // ------
+        let player_input = CharacterInput {
+            move_x: 0.0,
+            jump: input.key_state(KeyCode::Space).is_down(),
+        };
+        self.player.update(&self.physics, &player_input, delta_time);
+        self.movement_data[Self::PLAYER_IDX].velocity = self.player.velocity;
 
-        let wants_to_jump = input.key_state(KeyCode::Space).is_down();
-        self.jump_system.execute(
-            delta_time,
-            &mut self.movement_data[Self::PLAYER_IDX..=Self::PLAYER_IDX],
-            &mut self.is_grounded[Self::PLAYER_IDX..=Self::PLAYER_IDX],
-            &[wants_to_jump]
-        );
+        let player_transform = render_data.transforms.local_mut(Self::PLAYER_IDX);
+        player_transform.translation.x = self.player.position.x;
+        player_transform.translation.y = self.player.position.y;
 
-        self.movement_system.execute(delta_time, &mut render_data.transforms[Self::GROUND_START_IDX..=Self::PLAYER_IDX], &self.movement_data)?;
+        self.movement_system.execute(delta_time, render_data.transforms.locals_mut(Self::GROUND_START_IDX..=Self::ENEMY_END_IDX), &self.movement_data[Self::GROUND_START_IDX..=Self::ENEMY_END_IDX])?;
         self.ground_distance += Self::WORLD_SCROLL_SPEED * delta_time;
         self.enemy_distance += Self::WORLD_SCROLL_SPEED * delta_time;
+        self.travelled += Self::WORLD_SCROLL_SPEED * delta_time;
 
         let did_collide = self.enemy_collision_system.execute(
+            delta_time,
             &self.colliders[Self::PLAYER_IDX],
             &self.colliders[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX],
-            &render_data.transforms[Self::PLAYER_IDX],
-            &render_data.transforms[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX]
+            render_data.transforms.local(Self::PLAYER_IDX),
+            &render_data.transforms.locals()[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX],
+            &self.movement_data[Self::PLAYER_IDX],
+            &self.movement_data[Self::ENEMY_START_IDX..=Self::ENEMY_END_IDX]
         );
 
         if did_collide {
             self.reset_scene();
+        } else if self.travelled as u32 != self.score {
+            self.score = self.travelled as u32;
+            self.update_score_text();
         }
 
+        self.render_pkg.update_transforms();
+
         Ok(())
     }
+
+    fn update_score_text(&mut self) {
+        self.score_txt.set_localized_text(&self.localization, "hud.score", &[("score", self.score.into())]);
+    }
+
+    pub fn handle_window_changed(&mut self, window_extent: HellWindowExtent) {
+        self.ui_screen.set_window_extent(window_extent);
+        self.ui_layout.set_screen_size(self.ui_screen.size());
+    }
+
+    /// rebuilds the ui render data
+    fn update_ui(&mut self, input: UiInput) {
+        let ui_data = &mut self.render_pkg.ui;
+        ui_data.clear();
+        let root = ui_data.add_node(self.ui_screen.root_transform(), None);
+
+        // the ui goes down along y, the render data up
+        let score_pos = self.ui_screen.anchor_point(Anchor::TopLeft) + Self::SCORE_TEXT_OFFSET;
+        *self.score_txt.transform_mut() = Transform::new(
+            glam::vec3(score_pos.x, -score_pos.y, 0.0),
+            glam::Quat::IDENTITY,
+            glam::vec3(Self::SCORE_TEXT_SIZE, Self::SCORE_TEXT_SIZE, 1.0),
+        );
+
+        // the characters follow the transform of the text
+        let score_node = ui_data.add_node(self.score_txt.transform().clone(), Some(root));
+        // glyphs can come from a fallback font with its own mesh and material
+        let score_font = self.score_txt.font();
+        for g in self.score_txt.glyphs() {
+            let Some(font) = score_font.and_then(|f| f.font(g.font)) else {
+                continue;
+            };
+
+            let draw = ui_data.len();
+            ui_data.add_child_data(font.mesh(), ResourceHandle::new(font.material()), score_node, g.transform.clone());
+            ui_data.set_uv_rect(draw, g.uv_rect);
+            ui_data.set_tint(draw, g.color);
+            ui_data.set_sdf_style(draw, self.score_txt.sdf_style());
+        }
+
+        self.ui_layout.update();
+
+        let mut is_resumed = false;
+        let mut is_restarted = false;
+        let mut is_language_switched = false;
+        self.ui.begin_frame(input, self.ui_screen.size());
+        if self.is_paused {
+            let rect = self.ui_layout.rect(self.pause_panel);
+            // the ids of the widgets must not change with the language
+            let loc = &self.localization;
+            self.ui.panel("pause", rect, |ui| {
+                ui.label(&format!("[size=1.5]{}[/size]", loc.text("menu.paused")));
+                is_resumed = ui.button(&format!("{}##resume", loc.text("menu.resume"))).clicked;
+                is_restarted = ui.button(&format!("{}##restart", loc.text("menu.restart"))).clicked;
+                ui.slider(&format!("{}##speed", loc.text("menu.speed")), &mut self.time_scale, 0.5..=2.0);
+                is_language_switched = ui.button(&format!("{}##language", loc.text("menu.language"))).clicked;
+            });
+        }
+        self.ui.end_frame();
+
+        self.ui.write_render_data(ui_data, Self::QUAD_MESH, self.ui_solid_mat, Some(root));
+
+        if is_language_switched {
+            self.localization.next_language();
+            self.score_txt.localize(&self.localization);
+        }
+        if is_resumed || is_restarted {
+            self.is_paused = false;
+        }
+        if is_restarted {
+            self.reset_scene();
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use glam::{Vec3, Vec2};
 use hell_common::transform::Transform;
+use hell_core::logging::target;
+use tracing::debug;
 use super::MovementData;
 
 
@@ -39,7 +41,7 @@ impl EnemySpawnSystem {
         movement[spawn_idx].velocity = self.initial_velocity;
         is_alive[spawn_idx] = true;
 
-        println!("spawning enemy '{}'", spawn_idx);
+        debug!(target: target::GAME, "spawning enemy '{}'", spawn_idx);
 
         Some(spawn_idx)
     }
@@ -1,7 +1,7 @@
 use hell_core::prelude::*;
 use hell_common::transform::Transform;
 use hell_core::error::{HellErrorKind, HellErrorContent};
-use hell_physics::PhysicsConfig;
+use hell_core::jobs;
 
 
 
@@ -22,6 +22,8 @@ impl Default for MovementData {
 pub struct MovementSystem;
 
 impl MovementSystem {
+    const CHUNK_SIZE: usize = 64;
+
     pub fn execute(&self, delta_time: f32, transforms: &mut [Transform], settings: &[MovementData]) -> HellResult<()> {
         if transforms.len() != settings.len() {
             return Err(
@@ -29,75 +31,11 @@ impl MovementSystem {
             );
         }
 
-        for (t, s) in transforms.iter_mut().zip(settings.iter()) {
-            t.translate_xy(s.velocity * delta_time);
-        }
+        jobs::global().par_for_each_mut(transforms, Self::CHUNK_SIZE, |idx, t| {
+            t.translate_xy(settings[idx].velocity * delta_time);
+        });
 
         Ok(())
     }
 }
 
-
-
-// ----------------------------------------------
-
-
-
-#[derive(Default)]
-pub struct GravitySystem {
-    config: PhysicsConfig
-}
-
-impl GravitySystem {
-    #[allow(dead_code)]
-    pub fn new(config: PhysicsConfig) -> Self {
-        Self {
-            config
-        }
-    }
-
-    pub fn execute(&self, movement_data: &mut [MovementData], delta_time: f32) {
-        let offset = self.config.g_force * delta_time;
-
-        for md in movement_data {
-            md.velocity.y += offset;
-        }
-    }
-}
-
-
-
-// ----------------------------------------------
-
-
-
-#[derive(Clone)]
-pub struct JumpSystem {
-    jump_force: f32,
-    fall_force: f32,
-}
-
-impl JumpSystem {
-    pub fn new(jump_force: f32, fall_force: f32) -> Self {
-        Self { jump_force, fall_force }
-    }
-
-    pub fn execute(&self, delta_time: f32, movement_data: &mut [MovementData], is_grounded: &mut [bool], wants_to_jump: &[bool]) {
-        let offset = self.jump_force;
-        let fall_multi = self.fall_force * delta_time;
-
-        for (idx, md) in movement_data.iter_mut().enumerate() {
-            let ig = &mut is_grounded[idx];
-            let wtj = &wants_to_jump[idx];
-
-            if *ig && *wtj {
-                // jump
-                *ig = false;
-                md.velocity.y += offset;
-            } else if !*ig && !*wtj {
-                // fall
-                md.velocity.y += fall_multi;
-            }
-        }
-    }
-}
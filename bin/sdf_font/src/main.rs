@@ -0,0 +1,68 @@
+// crate-config: start
+#![deny(warnings)]
+// crate-config: end
+
+
+
+use std::path::PathBuf;
+
+use hell_core::error::{HellErrorHelper, HellResult};
+use hell_resources::fonts::{SdfFont, SdfFontConfig};
+
+
+
+const USAGE: &str = "usage: sdf_font <font.ttf|font.otf> <out.fnt> [--size <px>] [--spread <px>] [--supersample <n>] [--width <px>] [--chars <chars>]";
+
+/// bakes a signed distance field atlas and its BMFont metrics, e.g.
+/// `cargo run -p sdf_font -- fira_code.ttf assets/fonts/font_sdf_fira_code.fnt --size 48`
+fn main() -> HellResult<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (font_path, fnt_path, config) = parse_args(&args)?;
+
+    let font = SdfFont::from_file(&font_path, &config)?;
+    font.save(&fnt_path)?;
+
+    println!(
+        "wrote {} glyphs to '{}' ({}x{} atlas '{}')",
+        font.fnt.chars.len(), fnt_path.display(), font.fnt.common.scale_w, font.fnt.common.scale_h, config.page_file,
+    );
+
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> HellResult<(PathBuf, PathBuf, SdfFontConfig)> {
+    let err = |msg: &str| HellErrorHelper::request_msg_err(format!("{msg}\n{USAGE}"));
+    let mut positional = Vec::new();
+    let mut config = SdfFontConfig::default();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with("--") {
+            positional.push(arg);
+            continue;
+        }
+
+        let val = iter.next().ok_or_else(|| err(&format!("missing value for '{arg}'")))?;
+        let num = || val.parse::<u32>().map_err(|_| err(&format!("invalid value '{val}' for '{arg}'")));
+        match arg.as_str() {
+            "--size"        => config.size = num()?,
+            "--spread"      => config.spread = num()?,
+            "--supersample" => config.supersample = num()?,
+            "--width"       => config.atlas_width = num()?,
+            "--chars"       => config.chars = val.chars().collect(),
+            _               => return Err(err(&format!("unknown option '{arg}'"))),
+        }
+    }
+
+    let [font_path, fnt_path] = positional.as_slice() else {
+        return Err(err("expected a font and an output file"));
+    };
+    let (font_path, fnt_path) = (PathBuf::from(font_path), PathBuf::from(fnt_path));
+
+    // the atlas is named after the metrics file
+    let stem = fnt_path.file_stem().and_then(|s| s.to_str()).unwrap_or("font_sdf");
+    config.page_file = format!("{stem}.png");
+    config.face = font_path.file_stem().and_then(|s| s.to_str()).unwrap_or_default().to_owned();
+
+    Ok((font_path, fnt_path, config))
+}
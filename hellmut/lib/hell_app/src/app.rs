@@ -1,5 +1,6 @@
 use hell_common::window::{HellWindow, HellWindowExtent};
 use hell_core::error::HellResult;
+use hell_core::{profile_scope, profiling};
 use hell_input::InputManager;
 use hell_renderer::render_types::RenderPackage;
 use hell_renderer::{HellRenderer, HellRendererInfo, config};
@@ -16,6 +17,11 @@ pub trait HellGame {
 
     fn init_game(&mut self, renderer: &mut HellRenderer) -> HellResult<()>;
     fn update_game(&mut self, delta_time: f32, input: &InputManager) -> HellResult<()>;
+
+    /// called with the initial extent after `init_game` and whenever the window was resized or its scale factor changed
+    fn handle_window_changed(&mut self, _window_extent: HellWindowExtent) -> HellResult<()> {
+        Ok(())
+    }
 }
 
 
@@ -57,6 +63,7 @@ impl HellApp {
 impl HellApp {
     pub fn init_game(&mut self) -> HellResult<()> {
         self.game.init_game(&mut self.renderer)?;
+        self.game.handle_window_changed(self.renderer.window_extent())?;
         self.renderer.prepare_renderer()?;
 
         Ok(())
@@ -64,6 +71,7 @@ impl HellApp {
 
 
     fn update_game(&mut self, delta_time: f32) -> HellResult<()> {
+        profile_scope!("update_game");
         self.game.update_game(delta_time, &self.input)
     }
 }
@@ -71,6 +79,7 @@ impl HellApp {
 impl HellApp {
     pub fn handle_window_changed(&mut self, window_extent: HellWindowExtent) -> HellResult<()> {
         self.wait_idle()?;
+        self.game.handle_window_changed(window_extent)?;
         self.renderer.handle_window_changed(window_extent)
     }
 
@@ -88,8 +97,14 @@ impl HellApp {
         // std::thread::sleep(std::time::Duration::from_millis(250));
         // let delta_time = 0.1;
 
+        profiling::begin_frame();
+
         self.update_game(delta_time)?;
+        self.input.clear_typed_text();
         let render_pkg = self.game.render_package();
-        self.renderer.draw_frame(delta_time, render_pkg)
+        let is_resized = self.renderer.draw_frame(delta_time, render_pkg);
+
+        profiling::end_frame();
+        is_resized
     }
 }
@@ -0,0 +1,318 @@
+use std::ops::{Bound, Range, RangeBounds};
+
+use glam::{Mat4, Vec3};
+use hell_core::error::{HellErrorHelper, HellResult};
+
+use crate::transform::Transform;
+
+
+
+// ----------------------------------------------------------------------------
+// transform hierarchy
+// ----------------------------------------------------------------------------
+
+/// transforms with optional parents - local and world matrices are cached and only recalculated after changes
+///
+/// nodes are addressed by their index and can only be removed all at once. world matrices are brought up to date by `update`.
+#[derive(Debug, Default, Clone)]
+pub struct TransformHierarchy {
+    locals: Vec<Transform>,
+    parents: Vec<Option<usize>>,
+    children: Vec<Vec<usize>>,
+    local_mats: Vec<Mat4>,
+    world_mats: Vec<Mat4>,
+    is_dirty: Vec<bool>,
+    has_dirty_nodes: bool,
+
+    /// every parent comes before its children
+    order: Vec<usize>,
+    is_order_dirty: bool,
+}
+
+impl TransformHierarchy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.locals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// removes all nodes, e.g. to rebuild the hierarchy every frame
+    pub fn clear(&mut self) {
+        self.locals.clear();
+        self.parents.clear();
+        self.children.clear();
+        self.local_mats.clear();
+        self.world_mats.clear();
+        self.is_dirty.clear();
+        self.has_dirty_nodes = false;
+        self.order.clear();
+        self.is_order_dirty = false;
+    }
+
+    pub fn add(&mut self, local: Transform) -> usize {
+        let mat = local.create_model_mat();
+
+        self.locals.push(local);
+        self.parents.push(None);
+        self.children.push(Vec::new());
+        self.local_mats.push(mat);
+        self.world_mats.push(mat);
+        self.is_dirty.push(false);
+        self.is_order_dirty = true;
+
+        self.len() - 1
+    }
+
+    /// `local` is relative to `parent`
+    pub fn add_child(&mut self, parent: usize, local: Transform) -> usize {
+        let idx = self.add(local);
+        self.parents[idx] = Some(parent);
+        self.children[parent].push(idx);
+        self.mark_dirty(idx);
+        idx
+    }
+
+    /// the local transform is kept, so the node jumps to its new place relative to the new parent
+    pub fn set_parent(&mut self, idx: usize, parent: Option<usize>) -> HellResult<()> {
+        if parent.is_some_and(|p| p == idx || self.is_ancestor(idx, p)) {
+            return Err(HellErrorHelper::request_msg_err(format!("node '{idx}' can't be parented to its own descendant")));
+        }
+
+        if let Some(old) = self.parents[idx] {
+            self.children[old].retain(|c| *c != idx);
+        }
+        if let Some(new) = parent {
+            self.children[new].push(idx);
+        }
+
+        self.parents[idx] = parent;
+        self.is_order_dirty = true;
+        self.mark_dirty(idx);
+        Ok(())
+    }
+
+    pub fn parent(&self, idx: usize) -> Option<usize> {
+        self.parents[idx]
+    }
+
+    pub fn children(&self, idx: usize) -> &[usize] {
+        &self.children[idx]
+    }
+
+    /// is `ancestor` somewhere above `idx`
+    pub fn is_ancestor(&self, ancestor: usize, idx: usize) -> bool {
+        let mut curr = self.parents[idx];
+        while let Some(p) = curr {
+            if p == ancestor {
+                return true;
+            }
+            curr = self.parents[p];
+        }
+        false
+    }
+}
+
+// transforms
+// ----------
+impl TransformHierarchy {
+    pub fn local(&self, idx: usize) -> &Transform {
+        &self.locals[idx]
+    }
+
+    pub fn locals(&self) -> &[Transform] {
+        &self.locals
+    }
+
+    /// the node and all of its descendants are updated during the next `update`
+    pub fn local_mut(&mut self, idx: usize) -> &mut Transform {
+        self.mark_dirty(idx);
+        &mut self.locals[idx]
+    }
+
+    /// every node in `range` is treated as changed
+    pub fn locals_mut(&mut self, range: impl RangeBounds<usize>) -> &mut [Transform] {
+        let range = self.to_range(range);
+        self.is_dirty[range.clone()].fill(true);
+        self.has_dirty_nodes |= !range.is_empty();
+        &mut self.locals[range]
+    }
+
+    pub fn local_mat(&self, idx: usize) -> &Mat4 {
+        &self.local_mats[idx]
+    }
+
+    /// as of the last `update`
+    pub fn world_mat(&self, idx: usize) -> &Mat4 {
+        &self.world_mats[idx]
+    }
+
+    /// as of the last `update`
+    pub fn world_mats(&self) -> &[Mat4] {
+        &self.world_mats
+    }
+
+    pub fn world_translation(&self, idx: usize) -> Vec3 {
+        self.world_mats[idx].w_axis.truncate()
+    }
+
+    pub fn is_up_to_date(&self) -> bool {
+        !self.has_dirty_nodes && !self.is_order_dirty
+    }
+
+    fn mark_dirty(&mut self, idx: usize) {
+        self.is_dirty[idx] = true;
+        self.has_dirty_nodes = true;
+    }
+
+    fn to_range(&self, range: impl RangeBounds<usize>) -> Range<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => s + 1,
+            Bound::Unbounded   => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => e + 1,
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded   => self.len(),
+        };
+
+        start..end
+    }
+}
+
+// update
+// ------
+impl TransformHierarchy {
+    /// recalculates the matrices of all changed nodes and their descendants
+    pub fn update(&mut self) {
+        if self.is_order_dirty {
+            self.rebuild_order();
+        }
+        if !self.has_dirty_nodes {
+            return;
+        }
+
+        for i in 0..self.order.len() {
+            let idx = self.order[i];
+            let parent = self.parents[idx];
+
+            // parents are visited first, so their dirty flag is still set
+            let is_parent_dirty = parent.is_some_and(|p| self.is_dirty[p]);
+            if !self.is_dirty[idx] && !is_parent_dirty {
+                continue;
+            }
+
+            self.is_dirty[idx] = true;
+            self.local_mats[idx] = self.locals[idx].create_model_mat();
+            self.world_mats[idx] = match parent {
+                Some(p) => self.world_mats[p] * self.local_mats[idx],
+                None    => self.local_mats[idx],
+            };
+        }
+
+        self.is_dirty.fill(false);
+        self.has_dirty_nodes = false;
+    }
+
+    fn rebuild_order(&mut self) {
+        self.order.clear();
+
+        let mut stack: Vec<usize> = (0..self.len()).rev().filter(|idx| self.parents[*idx].is_none()).collect();
+        while let Some(idx) = stack.pop() {
+            self.order.push(idx);
+            stack.extend(self.children[idx].iter().rev());
+        }
+
+        self.is_order_dirty = false;
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn at(x: f32, y: f32) -> Transform {
+        Transform::new(glam::vec3(x, y, 0.0), Quat::IDENTITY, Vec3::ONE)
+    }
+
+    fn assert_pos(h: &TransformHierarchy, idx: usize, x: f32, y: f32) {
+        let pos = h.world_translation(idx);
+        assert!((pos - glam::vec3(x, y, 0.0)).length() < 1e-5, "node {idx}: {pos:?}");
+    }
+
+    #[test]
+    fn test_children_follow_parent() {
+        let mut h = TransformHierarchy::new();
+        let root = h.add(at(10.0, 0.0));
+        let child = h.add_child(root, at(1.0, 0.0));
+        let grand_child = h.add_child(child, at(0.0, 2.0));
+        h.update();
+        assert!(h.is_up_to_date());
+        assert_pos(&h, grand_child, 11.0, 2.0);
+
+        // rotating the root swings the whole chain around it
+        h.local_mut(root).rotate_around_z(FRAC_PI_2);
+        assert!(!h.is_up_to_date());
+        h.update();
+        assert_pos(&h, child, 10.0, 1.0);
+        assert_pos(&h, grand_child, 8.0, 1.0);
+
+        h.locals_mut(..)[child].translation.x = 3.0;
+        h.update();
+        assert_pos(&h, grand_child, 8.0, 3.0);
+    }
+
+    #[test]
+    fn test_reparent() {
+        let mut h = TransformHierarchy::new();
+        let a = h.add(at(5.0, 0.0));
+        let b = h.add(at(0.0, 5.0));
+        let child = h.add(at(1.0, 1.0));
+
+        h.set_parent(child, Some(b)).unwrap();
+        h.update();
+        assert_pos(&h, child, 1.0, 6.0);
+
+        h.set_parent(child, Some(a)).unwrap();
+        h.update();
+        assert_pos(&h, child, 6.0, 1.0);
+        assert!(h.children(b).is_empty());
+        assert_eq!(h.children(a), &[child]);
+
+        // cycles are rejected
+        assert!(h.set_parent(a, Some(child)).is_err());
+        assert!(h.set_parent(a, Some(a)).is_err());
+
+        h.set_parent(child, None).unwrap();
+        h.update();
+        assert_pos(&h, child, 1.0, 1.0);
+    }
+
+    #[test]
+    fn test_only_dirty_nodes_are_updated() {
+        let mut h = TransformHierarchy::new();
+        let a = h.add(at(1.0, 0.0));
+        let b = h.add(at(2.0, 0.0));
+        h.update();
+
+        // changing the transform without marking it isn't possible through the api, so poke the cache directly
+        h.world_mats[b] = Mat4::IDENTITY;
+        h.local_mut(a).translation.x = 3.0;
+        h.update();
+
+        assert_pos(&h, a, 3.0, 0.0);
+        assert_eq!(*h.world_mat(b), Mat4::IDENTITY);
+    }
+}
@@ -6,3 +6,4 @@
 
 pub mod window;
 pub mod transform;
+pub mod hierarchy;
@@ -1,6 +1,9 @@
-use glam::{Mat4, Vec3, Quat};
+use glam::{Mat3, Mat4, Quat, Vec2, Vec3};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+/// stored in a compact form, e.g. `{ translation: [1, 2, 0], scale: 2 }` - missing fields are left at identity
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "TransformData", into = "TransformData")]
 pub struct Transform {
     pub translation: Vec3,
     pub rotation: Quat,
@@ -39,6 +42,34 @@ impl Transform {
             self.translation
         )
     }
+
+    /// scales, rotates and then translates `p`
+    pub fn transform_point(&self, p: Vec3) -> Vec3 {
+        self.transform_vector(p) + self.translation
+    }
+
+    /// like `transform_point` but ignores the translation
+    pub fn transform_vector(&self, v: Vec3) -> Vec3 {
+        self.rotation * (self.scale * v)
+    }
+
+    /// only exact for uniform scales, a rotated non-uniform scale can't be undone by a single transform
+    pub fn inverse(&self) -> Self {
+        let scale = self.scale.recip();
+        let rotation = self.rotation.inverse();
+        let translation = -(scale * (rotation * self.translation));
+
+        Self::new(translation, rotation, scale)
+    }
+
+    /// `t` = 0 returns `self`, `t` = 1 returns `other`
+    pub fn interpolate(&self, other: &Transform, t: f32) -> Self {
+        Self::new(
+            self.translation.lerp(other.translation, t),
+            self.rotation.slerp(other.rotation, t),
+            self.scale.lerp(other.scale, t),
+        )
+    }
 }
 
 
@@ -101,6 +132,27 @@ impl Transform {
     pub fn rotate_around_z(&mut self, angle: f32) {
         self.rotate(Quat::from_rotation_z(angle));
     }
+
+    /// turns around the z-axis so that the local x-axis points at `target`
+    pub fn look_at_2d(&mut self, target: Vec2) {
+        let dir = target - self.translation.truncate();
+        if dir != Vec2::ZERO {
+            self.rotation = Quat::from_rotation_z(dir.y.atan2(dir.x));
+        }
+    }
+
+    /// turns so that the local negative z-axis points at `target` - the rotation is kept if the direction is undefined
+    pub fn look_at(&mut self, target: Vec3, up: Vec3) {
+        let Some(forward) = (target - self.translation).try_normalize() else {
+            return;
+        };
+        let Some(right) = forward.cross(up).try_normalize() else {
+            return;
+        };
+
+        let up = right.cross(forward);
+        self.rotation = Quat::from_mat3(&Mat3::from_cols(right, up, -forward));
+    }
 }
 
 
@@ -111,4 +163,183 @@ impl Transform {
     pub fn scale_uniform(&mut self, factor: f32) {
         self.scale *= factor;
     }
+
+    pub fn scale_by(&mut self, factor: Vec3) {
+        self.scale *= factor;
+    }
+
+    pub fn scale_x(&mut self, factor: f32) {
+        self.scale.x *= factor;
+    }
+
+    pub fn scale_y(&mut self, factor: f32) {
+        self.scale.y *= factor;
+    }
+
+    pub fn scale_z(&mut self, factor: f32) {
+        self.scale.z *= factor;
+    }
+
+    pub fn is_uniform_scale(&self) -> bool {
+        self.scale.x == self.scale.y && self.scale.y == self.scale.z
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// serialization
+// ----------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ScaleData {
+    Uniform(f32),
+    NonUniform([f32; 3]),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct TransformData {
+    #[serde(skip_serializing_if = "is_zero")]
+    translation: [f32; 3],
+    #[serde(skip_serializing_if = "is_identity")]
+    rotation: [f32; 4],
+    #[serde(skip_serializing_if = "is_one")]
+    scale: ScaleData,
+}
+
+impl Default for TransformData {
+    fn default() -> Self {
+        Transform::IDENTITY.into()
+    }
+}
+
+impl From<Transform> for TransformData {
+    fn from(val: Transform) -> Self {
+        let scale = if val.is_uniform_scale() {
+            ScaleData::Uniform(val.scale.x)
+        } else {
+            ScaleData::NonUniform(val.scale.to_array())
+        };
+
+        Self {
+            translation: val.translation.to_array(),
+            rotation: val.rotation.to_array(),
+            scale,
+        }
+    }
+}
+
+impl From<TransformData> for Transform {
+    fn from(val: TransformData) -> Self {
+        let scale = match val.scale {
+            ScaleData::Uniform(s)    => Vec3::splat(s),
+            ScaleData::NonUniform(s) => Vec3::from_array(s),
+        };
+
+        // hand-written rotations don't have to be normalized
+        let rotation = glam::Vec4::from_array(val.rotation).try_normalize().map_or(Quat::IDENTITY, Quat::from_vec4);
+
+        Self::new(Vec3::from_array(val.translation), rotation, scale)
+    }
+}
+
+fn is_zero(val: &[f32; 3]) -> bool {
+    *val == [0.0; 3]
+}
+
+fn is_identity(val: &[f32; 4]) -> bool {
+    *val == Quat::IDENTITY.to_array()
+}
+
+fn is_one(val: &ScaleData) -> bool {
+    matches!(val, ScaleData::Uniform(s) if *s == 1.0)
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn assert_vec(a: Vec3, b: Vec3) {
+        assert!((a - b).length() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_serde() {
+        let t: Transform = serde_yaml::from_str("{ translation: [1, 2, 0], scale: 2 }").unwrap();
+        assert_eq!(t, Transform::new(glam::vec3(1.0, 2.0, 0.0), Quat::IDENTITY, Vec3::splat(2.0)));
+
+        let t: Transform = serde_yaml::from_str("scale: [1, -1, 1]").unwrap();
+        assert_eq!(t.scale, glam::vec3(1.0, -1.0, 1.0));
+        assert_eq!(serde_yaml::from_str::<Transform>("{}").unwrap(), Transform::IDENTITY);
+        assert!(serde_yaml::from_str::<Transform>("position: [1, 2, 3]").is_err());
+
+        // identity parts are left out
+        assert_eq!(serde_yaml::to_string(&Transform::IDENTITY).unwrap().trim(), "{}");
+
+        let mut t = Transform::IDENTITY;
+        t.translate_x(3.0);
+        t.rotate_around_z(0.5);
+        t.scale_y(2.0);
+        let yaml = serde_yaml::to_string(&t).unwrap();
+        assert_eq!(serde_yaml::from_str::<Transform>(&yaml).unwrap(), t);
+    }
+
+    #[test]
+    fn test_points_and_inverse() {
+        let mut t = Transform::IDENTITY;
+        t.translate(glam::vec3(1.0, 2.0, 3.0));
+        t.rotate_around_z(FRAC_PI_2);
+        t.scale_uniform(2.0);
+
+        let p = glam::vec3(1.0, 0.0, 0.0);
+        assert_vec(t.transform_point(p), glam::vec3(1.0, 4.0, 3.0));
+        assert_vec(t.transform_vector(p), glam::vec3(0.0, 2.0, 0.0));
+        assert_vec(t.create_model_mat().transform_point3(p), t.transform_point(p));
+
+        let inv = t.inverse();
+        assert_vec(inv.transform_point(t.transform_point(p)), p);
+        assert!(inv.create_model_mat().abs_diff_eq(t.create_model_mat().inverse(), 1e-5));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let a = Transform::IDENTITY;
+        let mut b = Transform::IDENTITY;
+        b.translate_x(10.0);
+        b.rotate_around_z(FRAC_PI_2);
+        b.scale_by(glam::vec3(3.0, 1.0, 1.0));
+
+        assert_eq!(a.interpolate(&b, 0.0), a);
+        let half = a.interpolate(&b, 0.5);
+        assert_vec(half.translation, glam::vec3(5.0, 0.0, 0.0));
+        assert_vec(half.scale, glam::vec3(2.0, 1.0, 1.0));
+        assert!(half.rotation.abs_diff_eq(Quat::from_rotation_z(FRAC_PI_2 * 0.5), 1e-5));
+        assert!(a.interpolate(&b, 1.0).rotation.abs_diff_eq(b.rotation, 1e-5));
+    }
+
+    #[test]
+    fn test_look_at() {
+        let mut t = Transform::IDENTITY;
+        t.translate(glam::vec3(1.0, 1.0, 0.0));
+
+        t.look_at_2d(glam::vec2(1.0, 5.0));
+        assert_vec(t.transform_vector(Vec3::X), Vec3::Y);
+
+        t.look_at(glam::vec3(5.0, 1.0, 0.0), Vec3::Y);
+        assert_vec(t.transform_vector(Vec3::NEG_Z), Vec3::X);
+        assert_vec(t.transform_vector(Vec3::Y), Vec3::Y);
+
+        // the direction is undefined
+        let before = t.rotation;
+        t.look_at(t.translation, Vec3::Y);
+        t.look_at(glam::vec3(1.0, 5.0, 0.0), Vec3::Y);
+        assert_eq!(t.rotation, before);
+    }
 }
@@ -27,9 +27,12 @@ impl HellSurfaceInfo {
 
 
 #[derive(Debug, Clone, Copy)]
+/// size in physical pixels
 pub struct HellWindowExtent {
     pub width: u32,
     pub height: u32,
+    /// physical pixels per logical pixel, e.g. 2 on high dpi screens
+    pub scale_factor: f32,
 }
 
 
@@ -0,0 +1,306 @@
+use std::alloc::{self, Layout};
+use std::cell::{Cell, RefCell};
+use std::ptr::NonNull;
+use std::{mem, ptr, slice};
+
+
+
+// ----------------------------------------------------------------------------
+// arena
+// ----------------------------------------------------------------------------
+
+struct Chunk {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl Chunk {
+    const ALIGN: usize = 16;
+
+    fn new(size: usize) -> Self {
+        let layout = Layout::from_size_align(size.max(1), Self::ALIGN).expect("invalid arena chunk size");
+        // SAFETY: the layout has a non-zero size
+        let ptr = unsafe { alloc::alloc(layout) };
+        let Some(ptr) = NonNull::new(ptr) else {
+            alloc::handle_alloc_error(layout);
+        };
+
+        Self { ptr, layout }
+    }
+
+    fn size(&self) -> usize {
+        self.layout.size()
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` was allocated with exactly this layout
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
+
+/// bump allocator for short lived scratch data
+///
+/// allocations are never freed individually, `reset` frees all of them at once and keeps the memory around.
+/// only `Copy` types are supported, since destructors are never run.
+pub struct Arena {
+    chunks: RefCell<Vec<Chunk>>,
+    chunk_idx: Cell<usize>,
+    offset: Cell<usize>,
+    allocated: Cell<usize>,
+    chunk_size: usize,
+}
+
+// SAFETY: the arena owns all of its memory, handed out references borrow the arena
+unsafe impl Send for Arena {}
+
+impl Arena {
+    pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+    pub fn new() -> Self {
+        Self::with_chunk_size(Self::DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(chunk_size: usize) -> Self {
+        Self {
+            chunks: RefCell::new(Vec::new()),
+            chunk_idx: Cell::new(0),
+            offset: Cell::new(0),
+            allocated: Cell::new(0),
+            chunk_size: chunk_size.max(1),
+        }
+    }
+
+    /// bytes handed out since the last reset (including alignment padding)
+    pub fn allocated_bytes(&self) -> usize {
+        self.allocated.get()
+    }
+
+    /// bytes reserved from the global allocator
+    pub fn capacity(&self) -> usize {
+        self.chunks.borrow().iter().map(Chunk::size).sum()
+    }
+
+    /// frees all allocations - if the last round needed more than one chunk, they get merged into a single one,
+    /// so that the arena stops allocating once it has seen its peak usage
+    pub fn reset(&mut self) {
+        let chunks = self.chunks.get_mut();
+        if chunks.len() > 1 {
+            let size = chunks.iter().map(Chunk::size).sum();
+            chunks.clear();
+            chunks.push(Chunk::new(size));
+        }
+
+        self.chunk_idx.set(0);
+        self.offset.set(0);
+        self.allocated.set(0);
+    }
+
+    fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        if layout.size() == 0 {
+            // SAFETY: the alignment is never zero
+            return unsafe { NonNull::new_unchecked(layout.align() as *mut u8) };
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        let mut chunk_idx = self.chunk_idx.get();
+        let mut offset = self.offset.get();
+
+        loop {
+            if chunk_idx == chunks.len() {
+                let size = self.chunk_size.max(layout.size() + layout.align());
+                chunks.push(Chunk::new(size));
+            }
+
+            let chunk = &chunks[chunk_idx];
+            let base = chunk.ptr.as_ptr() as usize;
+            let start = (base + offset).next_multiple_of(layout.align()) - base;
+            let end = start + layout.size();
+
+            if end <= chunk.size() {
+                self.allocated.set(self.allocated.get() + (end - offset));
+                self.chunk_idx.set(chunk_idx);
+                self.offset.set(end);
+                // SAFETY: `start` is inside the chunk
+                return unsafe { NonNull::new_unchecked(chunk.ptr.as_ptr().add(start)) };
+            }
+
+            // chunks from earlier rounds are reused before new ones are allocated
+            chunk_idx += 1;
+            offset = 0;
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<T: Copy>(&self, val: T) -> &mut T {
+        let ptr = self.alloc_layout(Layout::new::<T>()).cast::<T>();
+        // SAFETY: the memory is properly aligned and not handed out to anyone else until the next reset
+        unsafe {
+            ptr.as_ptr().write(val);
+            &mut *ptr.as_ptr()
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_copy<T: Copy>(&self, src: &[T]) -> &mut [T] {
+        let ptr = self.alloc_slice_raw::<T>(src.len());
+        // SAFETY: the destination holds `src.len()` elements and can't overlap with `src`
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), ptr.as_ptr(), src.len());
+            slice::from_raw_parts_mut(ptr.as_ptr(), src.len())
+        }
+    }
+
+    /// if the iterator yields less items than it reported, the slice is shortened accordingly
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_fill_iter<T, I>(&self, iter: I) -> &mut [T]
+        where T: Copy,
+              I: IntoIterator<Item = T>,
+              I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        let cap = iter.len();
+        let ptr = self.alloc_slice_raw::<T>(cap);
+
+        let mut len = 0;
+        for val in iter.take(cap) {
+            // SAFETY: `len < cap`
+            unsafe { ptr.as_ptr().add(len).write(val) };
+            len += 1;
+        }
+
+        // SAFETY: the first `len` elements are initialized
+        unsafe { slice::from_raw_parts_mut(ptr.as_ptr(), len) }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice_fill_copy<T: Copy>(&self, len: usize, val: T) -> &mut [T] {
+        self.alloc_slice_fill_iter(std::iter::repeat_n(val, len))
+    }
+
+    fn alloc_slice_raw<T>(&self, len: usize) -> NonNull<T> {
+        let layout = Layout::array::<T>(len).expect("arena slice is too large");
+        debug_assert!(mem::align_of::<T>() <= layout.align());
+        self.alloc_layout(layout).cast::<T>()
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// frame allocator
+// ----------------------------------------------------------------------------
+
+/// one arena per frame in flight - data allocated during a frame stays valid until the same frame-slot comes around again
+pub struct FrameAllocator {
+    arenas: Vec<Arena>,
+    frame_idx: usize,
+}
+
+impl FrameAllocator {
+    pub fn new(frames_in_flight: usize) -> Self {
+        Self::with_chunk_size(frames_in_flight, Arena::DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn with_chunk_size(frames_in_flight: usize, chunk_size: usize) -> Self {
+        let arenas = (0..frames_in_flight.max(1))
+            .map(|_| Arena::with_chunk_size(chunk_size))
+            .collect();
+
+        Self { arenas, frame_idx: 0 }
+    }
+
+    pub fn frames_in_flight(&self) -> usize {
+        self.arenas.len()
+    }
+
+    pub fn frame_idx(&self) -> usize {
+        self.frame_idx
+    }
+
+    /// switches to the arena of `frame_idx` and frees everything that was allocated in it
+    pub fn begin_frame(&mut self, frame_idx: usize) {
+        self.frame_idx = frame_idx % self.arenas.len();
+        self.arenas[self.frame_idx].reset();
+    }
+
+    /// arena of the current frame
+    pub fn arena(&self) -> &Arena {
+        &self.arenas[self.frame_idx]
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alloc() {
+        let arena = Arena::with_chunk_size(32);
+        let a = arena.alloc(1u8);
+        let b = arena.alloc(2u64);
+        let c = arena.alloc_slice_copy(&[3u32; 20]);
+
+        assert_eq!(*a, 1);
+        assert_eq!(*b, 2);
+        assert_eq!(c, &[3; 20]);
+        assert_eq!(b as *const u64 as usize % mem::align_of::<u64>(), 0);
+        assert!(arena.capacity() > 32);
+    }
+
+    #[test]
+    fn test_fill_iter() {
+        let arena = Arena::new();
+        let squares = arena.alloc_slice_fill_iter((0..10_u32).map(|i| i * i));
+        assert_eq!(squares.len(), 10);
+        assert_eq!(squares[9], 81);
+
+        let empty: &mut [u64] = arena.alloc_slice_fill_iter(std::iter::empty());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_reset_merges_chunks() {
+        let mut arena = Arena::with_chunk_size(64);
+        for i in 0..32_u64 {
+            arena.alloc(i);
+        }
+        let capacity = arena.capacity();
+        assert!(capacity >= 32 * 8);
+
+        arena.reset();
+        assert_eq!(arena.allocated_bytes(), 0);
+        assert_eq!(arena.capacity(), capacity);
+
+        for i in 0..32_u64 {
+            arena.alloc(i);
+        }
+        assert_eq!(arena.capacity(), capacity);
+    }
+
+    #[test]
+    fn test_frame_allocator() {
+        let mut frames = FrameAllocator::with_chunk_size(2, 64);
+
+        frames.begin_frame(0);
+        frames.arena().alloc_slice_fill_copy(4, 1.0_f32);
+        frames.begin_frame(1);
+        assert_eq!(frames.arena().allocated_bytes(), 0);
+        frames.arena().alloc(1_u8);
+
+        frames.begin_frame(2);
+        assert_eq!(frames.frame_idx(), 0);
+        assert_eq!(frames.arena().allocated_bytes(), 0);
+    }
+}
@@ -157,6 +157,7 @@ impl<T> Node<T> {
         Self { next: None, prev: None, element, }
     }
 
+    #[allow(clippy::boxed_local)]
     fn into_element(self: Box<Self>) -> T {
         self.element
     }
@@ -2,5 +2,6 @@ pub mod hell_handle;
 pub mod stack_array;
 pub mod slot_array;
 pub mod queue_array;
+pub mod arena;
 
 pub mod experimental;
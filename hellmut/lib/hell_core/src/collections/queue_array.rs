@@ -106,18 +106,14 @@ impl<T, const N: usize> QueueArray<T, N> {
     }
 
     pub fn head(&self) -> Option<&T> {
-        let Some((head, _)) = self.head_tail else {
-            return None;
-        };
+        let (head, _) = self.head_tail?;
 
         // SAFETY: if head is set, it points to an initialized value
         unsafe { Some(self.data[head].assume_init_ref()) }
     }
 
     pub fn tail(&self) -> Option<&T> {
-        let Some((_, tail)) = self.head_tail else {
-            return None;
-        };
+        let (_, tail) = self.head_tail?;
 
         // SAFETY: if head is set, it points to an initialized value
         unsafe { Some(self.data[tail].assume_init_ref()) }
@@ -10,13 +10,7 @@ pub struct SlotArray<T, const SIZE: usize> {
 
 impl<T, const SIZE: usize> SlotArray<T, SIZE> {
     fn find_first_free_slot(&self) -> Option<usize> {
-        for idx in 0..SIZE {
-            if self.is_free[idx] {
-                return Some(idx);
-            }
-        }
-
-        None
+        self.is_free.iter().position(|is_free| *is_free)
     }
 
     pub fn has_free_slots(&self) -> bool {
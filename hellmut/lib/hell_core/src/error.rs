@@ -1,6 +1,8 @@
 use core::fmt;
 use std::{result, error, process, sync};
 
+use crate::logging::target;
+
 
 
 
@@ -60,25 +62,31 @@ pub struct HellErrorHelper;
 impl HellErrorHelper {
     pub fn render_msg_err(msg: impl Into<String>) -> HellError {
         let msg = msg.into();
-        eprintln!("[ERR]: render - {msg}");
+        tracing::error!(target: target::RENDER, "render - {msg}");
         HellError::new(HellErrorKind::RenderError, HellErrorContent::Message(msg))
     }
 
     pub fn request_msg_err(msg: impl Into<String>) -> HellError {
         let msg = msg.into();
-        eprintln!("[ERR]: request - {msg}");
+        tracing::error!(target: target::APP, "request - {msg}");
         HellError::new(HellErrorKind::RequestError, HellErrorContent::Message(msg))
     }
 
+    pub fn resource_msg_err(msg: impl Into<String>) -> HellError {
+        let msg = msg.into();
+        tracing::error!(target: target::RESOURCES, "resource - {msg}");
+        HellError::new(HellErrorKind::ResourceError, HellErrorContent::Message(msg))
+    }
+
     pub fn add_to_full_msg_err(msg: impl Into<String>) -> HellError {
         let msg = msg.into();
-        eprintln!("[ERR]: add_to_full - {msg}");
+        tracing::error!(target: target::CORE, "add_to_full - {msg}");
         HellError::new(HellErrorKind::AddToFullError, HellErrorContent::Message(msg))
     }
 
     pub fn remove_from_empty_msg_err(msg: impl Into<String>) -> HellError {
         let msg = msg.into();
-        eprintln!("[ERR]: remove_from_empty - {msg}");
+        tracing::error!(target: target::CORE, "remove_from_empty - {msg}");
         HellError::new(HellErrorKind::RemoveFromEmptyError, HellErrorContent::Message(msg))
     }
 }
@@ -0,0 +1,469 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+
+
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+type PanicPayload = Box<dyn Any + Send + 'static>;
+
+fn lock<T>(m: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    // jobs are executed inside of `catch_unwind`, so a poisoned lock can't leave the queues in an invalid state
+    m.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+
+
+// ----------------------------------------------------------------------------
+// shared state
+// ----------------------------------------------------------------------------
+
+struct Shared {
+    injector: Mutex<VecDeque<Job>>,
+    locals: Vec<Mutex<VecDeque<Job>>>,
+    queued: AtomicUsize,
+    sleep_lock: Mutex<()>,
+    sleep_cond: Condvar,
+    is_shutdown: AtomicBool,
+}
+
+thread_local! {
+    /// (address of the `Shared` the worker belongs to, worker index)
+    static CURR_WORKER: Cell<Option<(usize, usize)>> = const { Cell::new(None) };
+}
+
+impl Shared {
+    fn id(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// index of the current thread, if it is one of our workers
+    fn curr_worker(&self) -> Option<usize> {
+        CURR_WORKER.with(|w| match w.get() {
+            Some((id, idx)) if id == self.id() => Some(idx),
+            _ => None,
+        })
+    }
+
+    fn push(&self, job: Job) {
+        // count first, so `queued` never drops below the number of jobs in the queues
+        self.queued.fetch_add(1, Ordering::SeqCst);
+
+        match self.curr_worker() {
+            Some(idx) => lock(&self.locals[idx]).push_back(job),
+            None      => lock(&self.injector).push_back(job),
+        }
+
+        let _guard = lock(&self.sleep_lock);
+        self.sleep_cond.notify_one();
+    }
+
+    /// own queue (newest first) -> injector (oldest first) -> steal from the other workers (oldest first)
+    fn find_job(&self, worker: Option<usize>) -> Option<Job> {
+        let job = worker.and_then(|idx| lock(&self.locals[idx]).pop_back())
+            .or_else(|| lock(&self.injector).pop_front())
+            .or_else(|| {
+                let start = worker.map(|idx| idx + 1).unwrap_or(0);
+                (0..self.locals.len())
+                    .map(|offset| (start + offset) % self.locals.len())
+                    .filter(|idx| Some(*idx) != worker)
+                    .find_map(|idx| lock(&self.locals[idx]).pop_front())
+            });
+
+        if job.is_some() {
+            self.queued.fetch_sub(1, Ordering::SeqCst);
+        }
+
+        job
+    }
+
+    fn worker_loop(&self, idx: usize) {
+        CURR_WORKER.with(|w| w.set(Some((self.id(), idx))));
+
+        loop {
+            if let Some(job) = self.find_job(Some(idx)) {
+                job();
+                continue;
+            }
+
+            let guard = lock(&self.sleep_lock);
+            if self.is_shutdown.load(Ordering::SeqCst) && self.queued.load(Ordering::SeqCst) == 0 {
+                break;
+            }
+            if self.queued.load(Ordering::SeqCst) == 0 {
+                drop(self.sleep_cond.wait(guard));
+            }
+        }
+    }
+
+    /// runs queued jobs on the calling thread until `is_done` returns true
+    fn help_until(&self, is_done: impl Fn() -> bool) {
+        let worker = self.curr_worker();
+
+        while !is_done() {
+            match self.find_job(worker) {
+                Some(job) => job(),
+                None => thread::yield_now(),
+            }
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// job system
+// ----------------------------------------------------------------------------
+
+/// thread-pool with per-worker queues and work-stealing
+///
+/// parallel loops split their input into chunks of a fixed size, so the work
+/// done per element does not depend on the number of workers
+pub struct JobSystem {
+    shared: Arc<Shared>,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl JobSystem {
+    pub fn new(worker_count: usize) -> Self {
+        let shared = Arc::new(Shared {
+            injector: Mutex::new(VecDeque::new()),
+            locals: (0..worker_count).map(|_| Mutex::new(VecDeque::new())).collect(),
+            queued: AtomicUsize::new(0),
+            sleep_lock: Mutex::new(()),
+            sleep_cond: Condvar::new(),
+            is_shutdown: AtomicBool::new(false),
+        });
+
+        let workers = (0..worker_count)
+            .map(|idx| {
+                let shared = shared.clone();
+                thread::Builder::new()
+                    .name(format!("hell-worker-{idx}"))
+                    .spawn(move || shared.worker_loop(idx))
+                    .expect("failed to spawn worker thread")
+            })
+            .collect();
+
+        Self { shared, workers }
+    }
+
+    /// one worker per core, leaving one for the main thread
+    pub fn with_default_workers() -> Self {
+        let cores = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Self::new(cores.saturating_sub(1).max(1))
+    }
+
+    pub fn worker_count(&self) -> usize {
+        self.workers.len()
+    }
+}
+
+impl Drop for JobSystem {
+    fn drop(&mut self) {
+        {
+            let _guard = lock(&self.shared.sleep_lock);
+            self.shared.is_shutdown.store(true, Ordering::SeqCst);
+            self.shared.sleep_cond.notify_all();
+        }
+
+        for w in self.workers.drain(..) {
+            let _ = w.join();
+        }
+    }
+}
+
+// background tasks
+// ----------------
+impl JobSystem {
+    /// runs `job` on one of the workers, the result can be collected with the returned handle
+    pub fn spawn<T, F>(&self, job: F) -> JobHandle<T>
+    where T: Send + 'static,
+          F: FnOnce() -> T + Send + 'static,
+    {
+        let state = Arc::new(JobState {
+            result: Mutex::new(None),
+            is_done: AtomicBool::new(false),
+        });
+
+        let job_state = state.clone();
+        self.shared.push(Box::new(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(job));
+            *lock(&job_state.result) = Some(result);
+            job_state.is_done.store(true, Ordering::Release);
+        }));
+
+        JobHandle {
+            state,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+// scoped parallel loops
+// ---------------------
+impl JobSystem {
+    /// calls `f(chunk_idx)` for every chunk in `0..chunk_count` and returns once all of them are done
+    ///
+    /// the calling thread executes jobs as well, so this may also be used from inside a job
+    pub fn run_chunks<F>(&self, chunk_count: usize, f: F)
+    where F: Fn(usize) + Sync
+    {
+        match chunk_count {
+            0 => return,
+            1 => return f(0),
+            _ => {}
+        }
+
+        let latch = Latch {
+            remaining: AtomicUsize::new(chunk_count),
+            panic: Mutex::new(None),
+        };
+
+        for chunk_idx in 0..chunk_count {
+            let f = &f;
+            let latch = &latch;
+            let job: Box<dyn FnOnce() + Send + '_> = Box::new(move || {
+                if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| f(chunk_idx))) {
+                    lock(&latch.panic).get_or_insert(payload);
+                }
+                latch.remaining.fetch_sub(1, Ordering::AcqRel);
+            });
+
+            // SAFETY: `f` and `latch` outlive the job, since we don't return before `latch.remaining` hit zero,
+            //         which only happens after every job has finished touching them
+            let job: Job = unsafe { std::mem::transmute(job) };
+            self.shared.push(job);
+        }
+
+        self.shared.help_until(|| latch.remaining.load(Ordering::Acquire) == 0);
+
+        let payload = lock(&latch.panic).take();
+        if let Some(payload) = payload {
+            panic::resume_unwind(payload);
+        }
+    }
+
+    pub fn par_for_each_mut<T, F>(&self, data: &mut [T], chunk_size: usize, f: F)
+    where T: Send,
+          F: Fn(usize, &mut T) + Sync,
+    {
+        let chunk_size = chunk_size.max(1);
+        let len = data.len();
+        let ptr = SyncPtr(data.as_mut_ptr());
+
+        self.run_chunks(len.div_ceil(chunk_size), |chunk_idx| {
+            let start = chunk_idx * chunk_size;
+            let end = (start + chunk_size).min(len);
+            // SAFETY: chunks don't overlap and `data` is borrowed mutably for the duration of `run_chunks`
+            let chunk = unsafe { std::slice::from_raw_parts_mut(ptr.get().add(start), end - start) };
+
+            for (offset, value) in chunk.iter_mut().enumerate() {
+                f(start + offset, value);
+            }
+        });
+    }
+
+    pub fn par_for_each<T, F>(&self, data: &[T], chunk_size: usize, f: F)
+    where T: Sync,
+          F: Fn(usize, &T) + Sync,
+    {
+        let chunk_size = chunk_size.max(1);
+
+        self.run_chunks(data.len().div_ceil(chunk_size), |chunk_idx| {
+            let start = chunk_idx * chunk_size;
+            let end = (start + chunk_size).min(data.len());
+
+            for (offset, value) in data[start..end].iter().enumerate() {
+                f(start + offset, value);
+            }
+        });
+    }
+
+    /// results are returned in the same order as `data`
+    pub fn par_map<T, R, F>(&self, data: &[T], chunk_size: usize, f: F) -> Vec<R>
+    where T: Sync,
+          R: Send,
+          F: Fn(usize, &T) -> R + Sync,
+    {
+        let mut result: Vec<Option<R>> = std::iter::repeat_with(|| None).take(data.len()).collect();
+        self.par_for_each_mut(&mut result, chunk_size, |idx, r| {
+            *r = Some(f(idx, &data[idx]));
+        });
+
+        result.into_iter()
+            .map(|r| r.expect("par_map: every element is written exactly once"))
+            .collect()
+    }
+}
+
+struct Latch {
+    remaining: AtomicUsize,
+    panic: Mutex<Option<PanicPayload>>,
+}
+
+struct SyncPtr<T>(*mut T);
+
+impl<T> Clone for SyncPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SyncPtr<T> {}
+
+impl<T> SyncPtr<T> {
+    // use a method, so closures capture the whole wrapper instead of just the raw pointer
+    fn get(self) -> *mut T {
+        self.0
+    }
+}
+
+// SAFETY: only used to hand out disjoint sub-slices of `T: Send` to other threads
+unsafe impl<T: Send> Sync for SyncPtr<T> {}
+unsafe impl<T: Send> Send for SyncPtr<T> {}
+
+
+
+// ----------------------------------------------------------------------------
+// job handle
+// ----------------------------------------------------------------------------
+
+struct JobState<T> {
+    result: Mutex<Option<thread::Result<T>>>,
+    is_done: AtomicBool,
+}
+
+/// completion handle of a background job
+pub struct JobHandle<T> {
+    state: Arc<JobState<T>>,
+    shared: Arc<Shared>,
+}
+
+impl<T> JobHandle<T> {
+    pub fn is_done(&self) -> bool {
+        self.state.is_done.load(Ordering::Acquire)
+    }
+
+    /// returns the result if the job is done, hands the handle back otherwise
+    pub fn try_take(self) -> Result<T, Self> {
+        if !self.is_done() {
+            return Err(self);
+        }
+
+        Ok(self.take_result())
+    }
+
+    /// blocks until the job is done, the current thread helps out with queued jobs in the meantime
+    ///
+    /// panics if the job panicked
+    pub fn wait(self) -> T {
+        self.shared.help_until(|| self.is_done());
+        self.take_result()
+    }
+
+    fn take_result(self) -> T {
+        let result = lock(&self.state.result)
+            .take()
+            .expect("job result can only be taken once");
+
+        match result {
+            Ok(val) => val,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// global job system
+// ----------------------------------------------------------------------------
+
+static GLOBAL_JOB_SYSTEM: OnceLock<JobSystem> = OnceLock::new();
+
+/// lazily created job system shared by the whole engine
+pub fn global() -> &'static JobSystem {
+    GLOBAL_JOB_SYSTEM.get_or_init(JobSystem::with_default_workers)
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_par_for_each_mut() {
+        let jobs = JobSystem::new(4);
+        let mut data: Vec<u64> = (0..1000).collect();
+
+        jobs.par_for_each_mut(&mut data, 7, |idx, val| *val = *val * 2 + idx as u64);
+
+        assert!(data.iter().enumerate().all(|(idx, val)| *val == idx as u64 * 3));
+    }
+
+    #[test]
+    fn test_par_map_is_ordered() {
+        let jobs = JobSystem::new(3);
+        let data: Vec<f32> = (0..257).map(|i| i as f32 * 0.1).collect();
+
+        let sequential: Vec<f32> = data.iter().map(|v| v.sin() * 3.0).collect();
+        let parallel = jobs.par_map(&data, 16, |_, v| v.sin() * 3.0);
+
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn test_no_workers() {
+        let jobs = JobSystem::new(0);
+        let mut data = vec![1; 10];
+        jobs.par_for_each_mut(&mut data, 3, |_, val| *val += 1);
+        assert_eq!(data, vec![2; 10]);
+
+        let handle = jobs.spawn(|| 42);
+        assert_eq!(handle.wait(), 42);
+    }
+
+    #[test]
+    fn test_spawn() {
+        let jobs = JobSystem::new(2);
+        let handles: Vec<_> = (0..16).map(|i| jobs.spawn(move || i * i)).collect();
+        let results: Vec<_> = handles.into_iter().map(JobHandle::wait).collect();
+        assert_eq!(results, (0..16).map(|i| i * i).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_nested() {
+        let jobs = JobSystem::new(2);
+        let mut outer = vec![vec![0_u32; 64]; 8];
+
+        jobs.par_for_each_mut(&mut outer, 1, |i, inner| {
+            jobs.par_for_each_mut(inner, 8, |j, val| *val = (i * 100 + j) as u32);
+        });
+
+        assert_eq!(outer[3][5], 305);
+        assert_eq!(outer[7][63], 763);
+    }
+
+    #[test]
+    fn test_panic_is_propagated() {
+        let jobs = JobSystem::new(2);
+        let mut data = vec![0; 32];
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            jobs.par_for_each_mut(&mut data, 4, |idx, _| assert!(idx != 17));
+        }));
+        assert!(result.is_err());
+
+        // the pool is still usable afterwards
+        jobs.par_for_each_mut(&mut data, 4, |_, val| *val = 1);
+        assert_eq!(data, vec![1; 32]);
+    }
+}
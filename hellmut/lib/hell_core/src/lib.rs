@@ -1,5 +1,9 @@
 pub mod error;
 pub mod collections;
+pub mod logging;
+pub mod profiling;
+pub mod jobs;
+pub mod name;
 
 pub mod prelude {
     pub use crate::error::HellResult;
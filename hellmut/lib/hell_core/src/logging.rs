@@ -0,0 +1,152 @@
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::error::{HellResult, HellError, HellErrorKind};
+
+
+
+// ----------------------------------------------------------------------------
+// targets
+// ----------------------------------------------------------------------------
+
+/// subsystem targets, use them like: `tracing::info!(target: target::RENDER, "...")`
+/// and filter them like: `HELL_LOG="info,input=warn,render=debug"`
+pub mod target {
+    pub const CORE:      &str = "core";
+    pub const APP:       &str = "app";
+    pub const INPUT:     &str = "input";
+    pub const RENDER:    &str = "render";
+    pub const RESOURCES: &str = "resources";
+    pub const GAME:      &str = "game";
+}
+
+
+
+// ----------------------------------------------------------------------------
+// config
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct LogConfig {
+    /// filter in `EnvFilter` syntax, e.g. `"info,render=debug"`
+    pub filter: String,
+    /// mirror all log output into this file
+    pub file: Option<PathBuf>,
+    /// name of the env-var that overrides `filter`
+    pub env_var: Option<String>,
+}
+
+impl LogConfig {
+    pub const DEFAULT_FILTER: &'static str = "info";
+    pub const DEFAULT_ENV_VAR: &'static str = "HELL_LOG";
+
+    pub fn new(filter: impl Into<String>) -> Self {
+        Self {
+            filter: filter.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    pub fn with_env_var(mut self, env_var: Option<String>) -> Self {
+        self.env_var = env_var;
+        self
+    }
+
+    /// the filter from the env-var, if it is set - otherwise the configured one
+    pub fn effective_filter(&self) -> String {
+        self.env_var.as_ref()
+            .and_then(|var| std::env::var(var).ok())
+            .unwrap_or_else(|| self.filter.clone())
+    }
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            filter: Self::DEFAULT_FILTER.to_string(),
+            file: None,
+            env_var: Some(Self::DEFAULT_ENV_VAR.to_string()),
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// logging
+// ----------------------------------------------------------------------------
+
+/// keeps the filter of the global subscriber adjustable at runtime
+pub struct LogHandle {
+    filter: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogHandle {
+    pub fn set_filter(&self, filter: &str) -> HellResult<()> {
+        let filter = parse_filter(filter)?;
+        self.filter.reload(filter)?;
+        Ok(())
+    }
+}
+
+/// installs the global subscriber - can only be called once per process
+pub fn init_logging(config: &LogConfig) -> HellResult<LogHandle> {
+    let filter = parse_filter(&config.effective_filter())?;
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let file_layer = match &config.file {
+        Some(path) => {
+            let file = File::create(path)?;
+            Some(fmt::layer().with_ansi(false).with_writer(Mutex::new(file)))
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer())
+        .with(file_layer)
+        .try_init()?;
+
+    Ok(LogHandle { filter: handle })
+}
+
+fn parse_filter(filter: &str) -> HellResult<EnvFilter> {
+    EnvFilter::try_new(filter).map_err(|e| {
+        HellError::from_msg(HellErrorKind::GenericError, format!("invalid log filter '{filter}': {e}"))
+    })
+}
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_effective_filter() {
+        let config = LogConfig::new("warn,input=trace").with_env_var(None);
+        assert_eq!(config.effective_filter(), "warn,input=trace");
+
+        let config = LogConfig::new("warn").with_env_var(Some("HELL_LOG_TEST_OVERRIDE".to_string()));
+        std::env::set_var("HELL_LOG_TEST_OVERRIDE", "render=debug");
+        assert_eq!(config.effective_filter(), "render=debug");
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        assert!(parse_filter("info,render=debug").is_ok());
+        assert!(parse_filter("render=loud").is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::{OnceLock, RwLock};
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+
+
+// ----------------------------------------------------------------------------
+// hash
+// ----------------------------------------------------------------------------
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME:  u64 = 0x0000_0100_0000_01b3;
+
+/// 64 bit FNV-1a, usable in const contexts
+pub const fn hash_str(s: &str) -> u64 {
+    let bytes = s.as_bytes();
+    let mut hash = FNV_OFFSET;
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        hash ^= bytes[idx] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        idx += 1;
+    }
+
+    hash
+}
+
+
+
+// ----------------------------------------------------------------------------
+// name
+// ----------------------------------------------------------------------------
+
+/// hashed identifier for resources, uniforms, ... - comparing and hashing it is just an integer operation
+///
+/// names created at runtime with `HellName::new` are registered, so that they can be turned back into strings
+/// (for logging and serialization). names created with `hell_name!` are hashed at compile time and are only
+/// registered once the same string has been passed to `HellName::new`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HellName(u64);
+
+impl HellName {
+    /// hashes and registers the name
+    pub fn new(name: &str) -> Self {
+        let result = Self::from_static(name);
+        register(result, name);
+        result
+    }
+
+    /// only hashes the name, without registering it
+    pub const fn from_static(name: &str) -> Self {
+        Self(hash_str(name))
+    }
+
+    pub const fn from_hash(hash: u64) -> Self {
+        Self(hash)
+    }
+
+    pub const fn hash(&self) -> u64 {
+        self.0
+    }
+
+    /// reverse lookup - only works for registered names
+    pub fn to_str(&self) -> Option<&'static str> {
+        registry().read().unwrap_or_else(|e| e.into_inner()).get(&self.0).copied()
+    }
+}
+
+impl From<&str> for HellName {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&String> for HellName {
+    fn from(name: &String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl fmt::Debug for HellName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_str() {
+            Some(name) => write!(f, "HellName({:?})", name),
+            None       => write!(f, "HellName({:#018x})", self.0),
+        }
+    }
+}
+
+impl fmt::Display for HellName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_str() {
+            Some(name) => f.write_str(name),
+            None       => write!(f, "#{:016x}", self.0),
+        }
+    }
+}
+
+/// hashes a string literal at compile time: `hell_name!("view_proj")`
+#[macro_export]
+macro_rules! hell_name {
+    ($name:literal) => {{
+        const NAME: $crate::name::HellName = $crate::name::HellName::from_static($name);
+        NAME
+    }};
+}
+
+
+
+// ----------------------------------------------------------------------------
+// registry
+// ----------------------------------------------------------------------------
+
+// strings are leaked, the set of names is small and lives for the whole program
+static REGISTRY: OnceLock<RwLock<HashMap<u64, &'static str>>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<HashMap<u64, &'static str>> {
+    REGISTRY.get_or_init(Default::default)
+}
+
+fn register(name: HellName, value: &str) {
+    if let Some(known) = registry().read().unwrap_or_else(|e| e.into_inner()).get(&name.0) {
+        debug_assert_eq!(*known, value, "hash collision between names '{}' and '{}'", known, value);
+        return;
+    }
+
+    registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .entry(name.0)
+        .or_insert_with(|| Box::leak(value.into()));
+}
+
+
+
+// ----------------------------------------------------------------------------
+// map
+// ----------------------------------------------------------------------------
+
+/// the key already is a hash, so it is used as is
+#[derive(Default)]
+pub struct NameHasher(u64);
+
+impl Hasher for NameHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 = (self.0 ^ *b as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.0 = i;
+    }
+}
+
+pub type HellNameMap<V> = HashMap<HellName, V, BuildHasherDefault<NameHasher>>;
+
+
+
+// ----------------------------------------------------------------------------
+// serde
+// ----------------------------------------------------------------------------
+
+/// registered names are serialized as strings, all others as their hash
+impl Serialize for HellName {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.to_str() {
+            Some(name) => serializer.serialize_str(name),
+            None       => serializer.serialize_u64(self.0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HellName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(HellNameVisitor)
+    }
+}
+
+struct HellNameVisitor;
+
+impl<'de> Visitor<'de> for HellNameVisitor {
+    type Value = HellName;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a name or its hash")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(HellName::new(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(HellName::from_hash(v))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_const_hash() {
+        const VIEW_PROJ: HellName = hell_name!("view_proj");
+        assert_eq!(VIEW_PROJ, HellName::new("view_proj"));
+        assert_ne!(VIEW_PROJ, HellName::new("view"));
+        // reference value of FNV-1a 64
+        assert_eq!(hash_str("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn test_reverse_lookup() {
+        let name = HellName::new("test_reverse_lookup");
+        assert_eq!(name.to_str(), Some("test_reverse_lookup"));
+        assert_eq!(format!("{:?}", name), "HellName(\"test_reverse_lookup\")");
+        assert_eq!(HellName::from_static("never_registered").to_str(), None);
+    }
+
+    #[test]
+    fn test_map() {
+        let mut map = HellNameMap::default();
+        map.insert(HellName::new("sprite"), 1);
+        map.insert(HellName::new("test"), 2);
+        assert_eq!(map.get(&hell_name!("sprite")), Some(&1));
+        assert_eq!(map.get(&hell_name!("test")), Some(&2));
+    }
+
+    #[test]
+    fn test_serde() {
+        let names = vec![HellName::new("main_tex"), HellName::from_static("unregistered_tex")];
+        let yaml = serde_yaml::to_string(&names).unwrap();
+        assert!(yaml.contains("main_tex"));
+
+        let result: Vec<HellName> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(result, names);
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::error::HellResult;
+
+
+
+// ----------------------------------------------------------------------------
+// events
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct ProfileEvent {
+    pub name: &'static str,
+    /// relative to the profilers epoch
+    pub start: Duration,
+    pub duration: Duration,
+    pub thread: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameProfile {
+    pub frame_idx: u64,
+    /// relative to the profilers epoch
+    pub start: Duration,
+    pub duration: Duration,
+    pub events: Vec<ProfileEvent>,
+}
+
+impl FrameProfile {
+    /// summed up duration of all events with the given name
+    pub fn total(&self, name: &str) -> Duration {
+        self.events.iter()
+            .filter(|e| e.name == name)
+            .map(|e| e.duration)
+            .sum()
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// profiler
+// ----------------------------------------------------------------------------
+
+/// records scoped cpu-timings and keeps the last `capacity` frames around
+pub struct Profiler {
+    epoch: Instant,
+    capacity: usize,
+    frame_idx: u64,
+    current: Option<FrameProfile>,
+    pending: Vec<ProfileEvent>,
+    frames: VecDeque<FrameProfile>,
+}
+
+impl Profiler {
+    pub const DEFAULT_CAPACITY: usize = 120;
+    /// events recorded outside of a frame beyond this are dropped, in case no frame is ever started
+    pub const MAX_PENDING_EVENTS: usize = 4096;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            epoch: Instant::now(),
+            capacity: capacity.max(1),
+            frame_idx: 0,
+            current: None,
+            pending: Vec::new(),
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn epoch(&self) -> Instant {
+        self.epoch
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.frames.len() > self.capacity {
+            self.frames.pop_front();
+        }
+    }
+
+    pub fn frames(&self) -> impl Iterator<Item = &FrameProfile> {
+        self.frames.iter()
+    }
+
+    pub fn last_frame(&self) -> Option<&FrameProfile> {
+        self.frames.back()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.pending.clear();
+    }
+
+    pub fn begin_frame(&mut self) {
+        if self.current.is_some() {
+            self.end_frame();
+        }
+
+        self.current = Some(FrameProfile {
+            frame_idx: self.frame_idx,
+            start: self.epoch.elapsed(),
+            duration: Duration::ZERO,
+            // events recorded outside of a frame (e.g. while loading) end up in the next one
+            events: std::mem::take(&mut self.pending),
+        });
+        self.frame_idx += 1;
+    }
+
+    pub fn end_frame(&mut self) {
+        let Some(mut frame) = self.current.take() else {
+            return;
+        };
+
+        frame.duration = self.epoch.elapsed().saturating_sub(frame.start);
+
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(frame);
+    }
+
+    pub fn record(&mut self, event: ProfileEvent) {
+        match &mut self.current {
+            Some(frame) => frame.events.push(event),
+            None if self.pending.len() < Self::MAX_PENDING_EVENTS => self.pending.push(event),
+            None => {}
+        }
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+// chrome trace
+// ------------
+impl Profiler {
+    /// serializes all recorded frames to the chrome trace event format (`chrome://tracing`, perfetto, speedscope)
+    pub fn to_chrome_trace(&self) -> String {
+        let mut out = String::from("{\"traceEvents\":[");
+        let mut is_first = true;
+
+        for frame in &self.frames {
+            let name = format!("frame {}", frame.frame_idx);
+            write_trace_event(&mut out, &mut is_first, &name, "frame", frame.start, frame.duration, 0);
+
+            for e in &frame.events {
+                write_trace_event(&mut out, &mut is_first, e.name, "scope", e.start, e.duration, e.thread);
+            }
+        }
+
+        out.push_str("],\"displayTimeUnit\":\"ms\"}");
+        out
+    }
+
+    pub fn write_chrome_trace(&self, path: impl AsRef<Path>) -> HellResult<()> {
+        std::fs::write(path, self.to_chrome_trace())?;
+        Ok(())
+    }
+}
+
+fn write_trace_event(out: &mut String, is_first: &mut bool, name: &str, category: &str, start: Duration, duration: Duration, thread: u64) {
+    if !*is_first {
+        out.push(',');
+    }
+    *is_first = false;
+
+    out.push_str("{\"name\":\"");
+    for c in name.chars() {
+        match c {
+            '"'  => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => { let _ = write!(out, "\\u{:04x}", c as u32); }
+            c => out.push(c),
+        }
+    }
+
+    let _ = write!(
+        out,
+        "\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{}}}",
+        category,
+        start.as_secs_f64() * 1_000_000.0,
+        duration.as_secs_f64() * 1_000_000.0,
+        thread
+    );
+}
+
+
+
+// ----------------------------------------------------------------------------
+// global profiler
+// ----------------------------------------------------------------------------
+
+/// `HELL_PROFILE=1` enables the profiler, `HELL_PROFILE=0` disables it
+pub const ENV_VAR: &str = "HELL_PROFILE";
+
+static IS_ENABLED: AtomicBool = AtomicBool::new(false);
+static GLOBAL_PROFILER: OnceLock<Mutex<Profiler>> = OnceLock::new();
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+pub fn global() -> MutexGuard<'static, Profiler> {
+    GLOBAL_PROFILER
+        .get_or_init(|| Mutex::new(Profiler::default()))
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn set_enabled(is_enabled: bool) {
+    IS_ENABLED.store(is_enabled, Ordering::Relaxed);
+}
+
+/// enables or disables the profiler from `ENV_VAR`, `default` is used if it isn't set
+pub fn set_enabled_from_env(default: bool) -> bool {
+    let is_enabled = match std::env::var(ENV_VAR).as_deref() {
+        Ok("1" | "true")  => true,
+        Ok("0" | "false") => false,
+        _                 => default,
+    };
+
+    set_enabled(is_enabled);
+    is_enabled
+}
+
+#[inline]
+pub fn is_enabled() -> bool {
+    IS_ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn begin_frame() {
+    if is_enabled() {
+        global().begin_frame();
+    }
+}
+
+pub fn end_frame() {
+    if is_enabled() {
+        global().end_frame();
+    }
+}
+
+pub fn write_chrome_trace(path: impl AsRef<Path>) -> HellResult<()> {
+    global().write_chrome_trace(path)
+}
+
+/// measures the time until it is dropped and records it in the global profiler
+pub struct ProfileScope {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl ProfileScope {
+    #[inline]
+    pub fn new(name: &'static str) -> Self {
+        let start = is_enabled().then(Instant::now);
+        Self { name, start }
+    }
+}
+
+impl Drop for ProfileScope {
+    fn drop(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+
+        let duration = start.elapsed();
+        let thread = THREAD_ID.with(|id| *id);
+        let mut profiler = global();
+        let start = start.saturating_duration_since(profiler.epoch());
+
+        profiler.record(ProfileEvent { name: self.name, start, duration, thread });
+    }
+}
+
+/// profiles the rest of the enclosing scope: `profile_scope!("update_game");`
+#[macro_export]
+macro_rules! profile_scope {
+    ($name:expr) => {
+        let _profile_scope = $crate::profiling::ProfileScope::new($name);
+    };
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(name: &'static str, start_ms: u64, dur_ms: u64) -> ProfileEvent {
+        ProfileEvent {
+            name,
+            start: Duration::from_millis(start_ms),
+            duration: Duration::from_millis(dur_ms),
+            thread: 1,
+        }
+    }
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut profiler = Profiler::new(3);
+
+        for _ in 0..5 {
+            profiler.begin_frame();
+            profiler.record(event("update", 0, 1));
+            profiler.end_frame();
+        }
+
+        let idx: Vec<_> = profiler.frames().map(|f| f.frame_idx).collect();
+        assert_eq!(idx, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_pending_events() {
+        let mut profiler = Profiler::new(3);
+        profiler.record(event("load", 0, 5));
+        profiler.begin_frame();
+        profiler.record(event("update", 5, 1));
+        profiler.record(event("update", 6, 2));
+        profiler.end_frame();
+
+        let frame = profiler.last_frame().unwrap();
+        assert_eq!(frame.events.len(), 3);
+        assert_eq!(frame.total("update"), Duration::from_millis(3));
+
+        profiler.clear();
+        for idx in 0..Profiler::MAX_PENDING_EVENTS as u64 + 10 {
+            profiler.record(event("load", idx, 1));
+        }
+        profiler.begin_frame();
+        profiler.end_frame();
+        assert_eq!(profiler.last_frame().unwrap().events.len(), Profiler::MAX_PENDING_EVENTS);
+    }
+
+    #[test]
+    fn test_chrome_trace() {
+        let mut profiler = Profiler::new(3);
+        profiler.begin_frame();
+        profiler.record(event("draw \"sprite\"", 1, 2));
+        profiler.end_frame();
+
+        let json = profiler.to_chrome_trace();
+        assert!(json.starts_with("{\"traceEvents\":[{\"name\":\"frame 0\""));
+        assert!(json.contains("{\"name\":\"draw \\\"sprite\\\"\",\"cat\":\"scope\",\"ph\":\"X\",\"ts\":1000.000,\"dur\":2000.000,\"pid\":1,\"tid\":1}"));
+        assert!(json.ends_with("],\"displayTimeUnit\":\"ms\"}"));
+    }
+}
@@ -0,0 +1,279 @@
+use glam::{Vec2, Vec4};
+use hell_common::transform::Transform;
+use hell_resources::fonts::{FntFile, FntFileCharRow};
+
+use super::{Glyph, HellFont, TextAlign, TextSpan};
+
+
+
+// ----------------------------------------------------------------------------
+// params
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LayoutParams {
+    pub align: TextAlign,
+    pub line_height: f32,
+    pub line_spacing: f32,
+    pub max_width: Option<f32>,
+    pub max_lines: Option<usize>,
+    pub color: Vec4,
+}
+
+impl Default for LayoutParams {
+    fn default() -> Self {
+        Self {
+            align: TextAlign::default(),
+            line_height: 1.0,
+            line_spacing: 1.0,
+            max_width: None,
+            max_lines: None,
+            color: Vec4::ONE,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct TextLayout {
+    pub glyphs: Vec<Glyph>,
+    pub size: Vec2,
+}
+
+
+
+// ----------------------------------------------------------------------------
+// layout
+// ----------------------------------------------------------------------------
+
+/// a single character with its style
+#[derive(Clone, Copy)]
+struct Item<'a> {
+    glyph: &'a FntFileCharRow,
+    /// index of the font in the fallback chain
+    font: usize,
+    fnt: &'a FntFile,
+    color: Vec4,
+    /// relative to the line height
+    size: f32,
+    /// local units per pixel of the font
+    scale: f32,
+    /// kerning to the previous character of the paragraph, in local units
+    kerning: f32,
+    is_space: bool,
+}
+
+impl Item<'_> {
+    fn advance(&self) -> f32 {
+        self.glyph.xadvance as f32 * self.scale
+    }
+}
+
+/// width of the line without the trailing spaces
+fn line_width(line: &[Item]) -> f32 {
+    let visible = line.iter().rposition(|i| !i.is_space).map_or(0, |idx| idx + 1);
+    line[..visible].iter().enumerate()
+        .map(|(idx, i)| if idx == 0 { i.advance() } else { i.kerning + i.advance() })
+        .sum()
+}
+
+fn trim_end(line: &mut Vec<Item>) {
+    while line.last().is_some_and(|i| i.is_space) {
+        line.pop();
+    }
+}
+
+/// the origin is at the top of the first line, lines go down along -y.
+/// left aligned lines start at the origin, centered lines are centered around it and right aligned lines end at it.
+/// characters from fallback fonts are scaled to the line height and share the baseline of the primary font.
+pub(crate) fn layout(font: &HellFont, spans: &[TextSpan], params: &LayoutParams) -> TextLayout {
+    let fnt = font.fnt();
+
+    // split into paragraphs
+    // ---------------------
+    let mut paragraphs: Vec<Vec<Item>> = vec![Vec::new()];
+    for span in spans {
+        let color = span.color.unwrap_or(params.color);
+
+        for c in span.text.chars().filter(|c| *c != '\r') {
+            if c == '\n' {
+                paragraphs.push(Vec::new());
+                continue;
+            }
+
+            let Some((font_idx, glyph)) = font.glyph(c) else {
+                continue;
+            };
+            let glyph_fnt = font.font(font_idx).unwrap().fnt();
+            let scale = px_to_local(glyph_fnt, params) * span.size;
+
+            // there is no kerning between characters of different fonts
+            let paragraph = paragraphs.last_mut().unwrap();
+            let kerning = match paragraph.last() {
+                Some(prev) if prev.font == font_idx => glyph_fnt.kerning(prev.glyph.id, glyph.id) as f32 * scale,
+                _ => 0.0,
+            };
+            paragraph.push(Item { glyph, font: font_idx, fnt: glyph_fnt, color, size: span.size, scale, kerning, is_space: c.is_whitespace() });
+        }
+    }
+
+    // wrap lines
+    // ----------
+    let mut lines: Vec<Vec<Item>> = Vec::new();
+    for paragraph in paragraphs {
+        wrap_paragraph(paragraph, params.max_width, &mut lines);
+    }
+
+    if let Some(max_lines) = params.max_lines {
+        if lines.len() > max_lines {
+            lines.truncate(max_lines.max(1));
+            let last = lines.last_mut().unwrap();
+            add_ellipsis(font, last, params);
+        }
+    }
+
+    // place glyphs
+    // ------------
+    let base = fnt.common.base as f32 / fnt.common.line_height.max(1) as f32;
+
+    let mut result = TextLayout::default();
+    let mut line_top = 0.0;
+    for (line_idx, line) in lines.iter().enumerate() {
+        // the largest characters define the height and the baseline of the line
+        let line_size = line.iter().map(|i| i.size).fold(1.0, f32::max);
+        let line_height = params.line_height * line_size;
+        let baseline = line_top + base * line_height;
+        let line_width = line_width(line);
+
+        let mut cursor = match params.align {
+            TextAlign::Left   => 0.0,
+            TextAlign::Center => -line_width * 0.5,
+            TextAlign::Right  => -line_width,
+        };
+
+        for (idx, item) in line.iter().enumerate() {
+            if idx > 0 {
+                cursor += item.kerning;
+            }
+
+            let glyph = item.glyph;
+            if glyph.width > 0 && glyph.height > 0 {
+                let common = &item.fnt.common;
+                let (scale_w, scale_h) = (common.scale_w.max(1) as f32, common.scale_h.max(1) as f32);
+                let px_size = glam::vec2(glyph.width as f32, glyph.height as f32);
+                let size = px_size * item.scale;
+                let top = baseline - (common.base - glyph.yoffset) as f32 * item.scale;
+                let top_left = glam::vec2(cursor + glyph.xoffset as f32 * item.scale, -top);
+                let center = top_left + glam::vec2(size.x, -size.y) * 0.5;
+
+                result.glyphs.push(Glyph {
+                    id: glyph.id,
+                    font: item.font,
+                    transform: Transform::new(center.extend(0.0), glam::Quat::IDENTITY, size.extend(1.0)),
+                    uv_rect: glam::vec4(glyph.x as f32 / scale_w, glyph.y as f32 / scale_h, px_size.x / scale_w, px_size.y / scale_h),
+                    color: item.color,
+                });
+            }
+
+            cursor += item.advance();
+        }
+
+        result.size.x = result.size.x.max(line_width);
+        result.size.y = line_top + line_height;
+
+        if line_idx + 1 < lines.len() {
+            line_top += line_height * params.line_spacing;
+        }
+    }
+
+    result
+}
+
+/// local units per pixel of `fnt` at the line height of the params
+fn px_to_local(fnt: &FntFile, params: &LayoutParams) -> f32 {
+    params.line_height / fnt.common.line_height.max(1) as f32
+}
+
+/// breaks the paragraph on spaces - words that are longer than `max_width` are broken between characters
+fn wrap_paragraph<'a>(paragraph: Vec<Item<'a>>, max_width: Option<f32>, lines: &mut Vec<Vec<Item<'a>>>) {
+    let Some(max_width) = max_width else {
+        lines.push(paragraph);
+        return;
+    };
+
+    let mut line: Vec<Item> = Vec::new();
+    let mut width = 0.0;
+    // start of the last word in the line
+    let mut word_start: Option<usize> = None;
+
+    for item in paragraph {
+        let prev_is_space = line.last().is_some_and(|i| i.is_space);
+        if !item.is_space && prev_is_space {
+            word_start = Some(line.len());
+        }
+
+        let kerning = if line.is_empty() { 0.0 } else { item.kerning };
+        if !item.is_space && !line.is_empty() && width + kerning + item.advance() > max_width {
+            let rest = match word_start.take() {
+                Some(start) if start > 0 => line.split_off(start),
+                _ => Vec::new(),
+            };
+
+            trim_end(&mut line);
+            lines.push(std::mem::replace(&mut line, rest));
+            width = line_width(&line);
+        }
+
+        width += if line.is_empty() { item.advance() } else { item.kerning + item.advance() };
+        line.push(item);
+    }
+
+    lines.push(line);
+}
+
+/// shortens the line until the ellipsis fits into `max_width` and appends it
+fn add_ellipsis<'a>(font: &'a HellFont, line: &mut Vec<Item<'a>>, params: &LayoutParams) {
+    let fnt = font.fnt();
+    let (glyph, count) = match fnt.char('\u{2026}' as u32) {
+        Some(g) => (g, 1),
+        None => match fnt.char('.' as u32) {
+            Some(g) => (g, 3),
+            None    => return,
+        },
+    };
+
+    let (color, size) = line.last().map_or((params.color, 1.0), |i| (i.color, i.size));
+    let scale = px_to_local(fnt, params) * size;
+    let dot = Item { glyph, font: 0, fnt, color, size, scale, kerning: fnt.kerning(glyph.id, glyph.id) as f32 * scale, is_space: false };
+    let ellipsis_width = dot.advance() * count as f32 + dot.kerning * (count - 1) as f32;
+
+    trim_end(line);
+    if let Some(max_width) = params.max_width {
+        while !line.is_empty() && line_width(line) + ellipsis_width > max_width {
+            line.pop();
+            trim_end(line);
+        }
+    }
+
+    let first_kerning = match line.last() {
+        Some(prev) if prev.font == 0 => fnt.kerning(prev.glyph.id, glyph.id) as f32 * scale,
+        _ => 0.0,
+    };
+    line.push(Item { kerning: first_kerning, ..dot });
+    line.extend(std::iter::repeat_n(dot, count - 1));
+}
+
+
+
+// ----------------------------------------------------------------------------
+// measure
+// ----------------------------------------------------------------------------
+
+/// size of the unwrapped `text` with a line height of `size` - lines are only broken at '\n'
+pub fn measure(text: &str, font: &HellFont, size: f32) -> Vec2 {
+    let params = LayoutParams {
+        line_height: size,
+        ..Default::default()
+    };
+
+    layout(font, &[TextSpan::new(text)], &params).size
+}
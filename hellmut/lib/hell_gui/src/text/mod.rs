@@ -1,2 +1,15 @@
+pub(crate) mod layout;
+mod rich_text;
 mod text_mesh;
+
+pub use layout::measure;
+pub use rich_text::*;
 pub use text_mesh::*;
+
+/// the bundled bitmap font with the quad mesh 0
+#[cfg(test)]
+pub(crate) fn test_font(material: usize) -> HellFont {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../assets/fonts/font_bm_fira_code.fnt");
+    let fnt = hell_resources::fonts::FntFile::from_file(std::path::Path::new(path)).unwrap();
+    HellFont::new(0, material, std::sync::Arc::new(fnt))
+}
@@ -0,0 +1,223 @@
+use glam::Vec4;
+
+
+
+// ----------------------------------------------------------------------------
+// span
+// ----------------------------------------------------------------------------
+
+/// text that shares the same style
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    /// `None` uses the color of the text mesh
+    pub color: Option<Vec4>,
+    /// multiplied with the line height of the text mesh
+    pub size: f32,
+}
+
+impl TextSpan {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+            size: 1.0,
+        }
+    }
+
+    pub fn with_color(mut self, color: Vec4) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// rich text
+// ----------------------------------------------------------------------------
+
+/// text with inline styles, e.g. `hp: [color=red]12[/color] [size=0.5]of 100[/size]`
+///
+/// supported tags are `[color=<name|#rgb|#rrggbb|#rrggbbaa>]`, `[/color]`, `[size=<factor>]` and `[/size]` - tags can be nested.
+/// `[[` is a literal '[', tags that can't be parsed are kept as text.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RichText {
+    pub spans: Vec<TextSpan>,
+}
+
+impl From<TextSpan> for RichText {
+    fn from(span: TextSpan) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl RichText {
+    pub fn plain(text: impl Into<String>) -> Self {
+        TextSpan::new(text).into()
+    }
+
+    pub fn parse(markup: &str) -> Self {
+        let mut spans: Vec<TextSpan> = Vec::new();
+        let mut colors: Vec<Vec4> = Vec::new();
+        let mut sizes: Vec<f32> = Vec::new();
+        let mut current = String::new();
+
+        let mut rest = markup;
+        while let Some(start) = rest.find('[') {
+            current.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            if let Some(after) = rest.strip_prefix("[[") {
+                current.push('[');
+                rest = after;
+                continue;
+            }
+
+            let tag = rest.find(']').map(|end| (&rest[1..end], end));
+            let tag = tag.and_then(|(tag, end)| Tag::parse(tag, !colors.is_empty(), !sizes.is_empty()).map(|t| (t, end)));
+            let Some((tag, end)) = tag else {
+                current.push('[');
+                rest = &rest[1..];
+                continue;
+            };
+
+            // the style changes -> finish the text so far
+            let style = (colors.last().copied(), sizes.last().copied().unwrap_or(1.0));
+            Self::push_span(&mut spans, &mut current, style);
+
+            match tag {
+                Tag::Color(c) => colors.push(c),
+                Tag::Size(s)  => sizes.push(s),
+                Tag::EndColor => { colors.pop(); },
+                Tag::EndSize  => { sizes.pop(); },
+            }
+            rest = &rest[end + 1..];
+        }
+
+        current.push_str(rest);
+        let style = (colors.last().copied(), sizes.last().copied().unwrap_or(1.0));
+        Self::push_span(&mut spans, &mut current, style);
+
+        Self { spans }
+    }
+
+    /// the text without any styles
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    fn push_span(spans: &mut Vec<TextSpan>, text: &mut String, (color, size): (Option<Vec4>, f32)) {
+        if text.is_empty() {
+            return;
+        }
+
+        spans.push(TextSpan {
+            text: std::mem::take(text),
+            color,
+            size,
+        });
+    }
+}
+
+// ----------------------------------------------
+
+enum Tag {
+    Color(Vec4),
+    EndColor,
+    Size(f32),
+    EndSize,
+}
+
+impl Tag {
+    /// closing tags are only valid when there is something to close
+    fn parse(tag: &str, in_color: bool, in_size: bool) -> Option<Self> {
+        match tag.trim().split_once('=') {
+            Some(("color", val)) => parse_color(val.trim()).map(Tag::Color),
+            Some(("size", val))  => val.trim().parse().ok().filter(|s: &f32| *s > 0.0 && s.is_finite()).map(Tag::Size),
+            None if tag.trim() == "/color" && in_color => Some(Tag::EndColor),
+            None if tag.trim() == "/size" && in_size   => Some(Tag::EndSize),
+            _ => None,
+        }
+    }
+}
+
+/// named color or hex code with an optional alpha
+pub fn parse_color(val: &str) -> Option<Vec4> {
+    if let Some(hex) = val.strip_prefix('#') {
+        let channel = |idx: usize, len: usize| u8::from_str_radix(hex.get(idx * len..(idx + 1) * len)?, 16).ok();
+        let (len, count) = match hex.len() {
+            3 => (1, 3),
+            6 => (2, 3),
+            8 => (2, 4),
+            _ => return None,
+        };
+
+        let mut rgba = [1.0; 4];
+        for (idx, c) in rgba.iter_mut().enumerate().take(count) {
+            let val = channel(idx, len)?;
+            // '#f00' is the same as '#ff0000'
+            let val = if len == 1 { val * 17 } else { val };
+            *c = val as f32 / 255.0;
+        }
+
+        return Some(Vec4::from_array(rgba));
+    }
+
+    let rgba = match val.to_ascii_lowercase().as_str() {
+        "white"       => [1.0, 1.0, 1.0, 1.0],
+        "black"       => [0.0, 0.0, 0.0, 1.0],
+        "red"         => [1.0, 0.0, 0.0, 1.0],
+        "green"       => [0.0, 1.0, 0.0, 1.0],
+        "blue"        => [0.0, 0.0, 1.0, 1.0],
+        "yellow"      => [1.0, 1.0, 0.0, 1.0],
+        "cyan"        => [0.0, 1.0, 1.0, 1.0],
+        "magenta"     => [1.0, 0.0, 1.0, 1.0],
+        "orange"      => [1.0, 0.5, 0.0, 1.0],
+        "gray"|"grey" => [0.5, 0.5, 0.5, 1.0],
+        "transparent" => [0.0, 0.0, 0.0, 0.0],
+        _ => return None,
+    };
+
+    Some(Vec4::from_array(rgba))
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_spans() {
+        let txt = RichText::parse("hp: [color=red]12 [size=0.5]of[/size][/color] 100");
+
+        assert_eq!(txt.spans, vec![
+            TextSpan::new("hp: "),
+            TextSpan::new("12 ").with_color(Vec4::new(1.0, 0.0, 0.0, 1.0)),
+            TextSpan::new("of").with_color(Vec4::new(1.0, 0.0, 0.0, 1.0)).with_size(0.5),
+            TextSpan::new(" 100"),
+        ]);
+        assert_eq!(txt.plain_text(), "hp: 12 of 100");
+    }
+
+    #[test]
+    fn test_invalid_markup() {
+        // unknown, unterminated and unmatched tags are kept as text
+        let txt = RichText::parse("[b]x[/color] [[color=red] [size=-1]a[color=red");
+        assert_eq!(txt.spans, vec![TextSpan::new("[b]x[/color] [color=red] [size=-1]a[color=red")]);
+
+        assert_eq!(RichText::parse("").spans, vec![]);
+        assert_eq!(parse_color("#f00"), Some(Vec4::new(1.0, 0.0, 0.0, 1.0)));
+        assert_eq!(parse_color("#00ff0080"), Some(Vec4::new(0.0, 1.0, 0.0, 128.0 / 255.0)));
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("Grey"), Some(Vec4::new(0.5, 0.5, 0.5, 1.0)));
+    }
+}
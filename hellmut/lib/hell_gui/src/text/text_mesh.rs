@@ -1,18 +1,104 @@
+use std::sync::Arc;
+
+use glam::{Vec2, Vec4};
 use hell_common::transform::Transform;
+use hell_renderer::render_types::SdfStyle;
+use hell_resources::fonts::{FntFile, FntFileCharRow};
+use hell_resources::locale::{LocaleArg, Localization};
+
+use super::layout::{self, LayoutParams};
+use super::RichText;
 
-#[allow(dead_code)]
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct HellFont {
     mesh: usize,
     material: usize,
+    fnt: Arc<FntFile>,
+    /// searched in order for characters that are missing in this font
+    fallbacks: Vec<HellFont>,
 }
 
 impl HellFont {
-    pub fn new(mesh: usize, material: usize) -> Self {
+    pub fn new(mesh: usize, material: usize, fnt: Arc<FntFile>) -> Self {
         Self {
-            mesh, material
+            mesh, material, fnt,
+            fallbacks: Vec::new(),
+        }
+    }
+
+    /// the fallbacks of `font` are searched after it
+    pub fn with_fallback(mut self, mut font: HellFont) -> Self {
+        let nested = std::mem::take(&mut font.fallbacks);
+        self.fallbacks.push(font);
+        self.fallbacks.extend(nested);
+        self
+    }
+
+    pub fn mesh(&self) -> usize {
+        self.mesh
+    }
+
+    pub fn material(&self) -> usize {
+        self.material
+    }
+
+    pub fn fnt(&self) -> &FntFile {
+        &self.fnt
+    }
+
+    pub fn fallbacks(&self) -> &[HellFont] {
+        &self.fallbacks
+    }
+
+    /// 0 is this font, the fallbacks follow in order - see `Glyph::font`
+    pub fn font(&self, idx: usize) -> Option<&HellFont> {
+        match idx {
+            0 => Some(self),
+            _ => self.fallbacks.get(idx - 1),
         }
     }
+
+    /// index of the font that has the character and its row - falls back to '?' of this font for characters that no font has
+    pub(crate) fn glyph(&self, c: char) -> Option<(usize, &FntFileCharRow)> {
+        let found = std::iter::once(&self.fnt)
+            .chain(self.fallbacks.iter().map(|f| &f.fnt))
+            .enumerate()
+            .find_map(|(idx, fnt)| fnt.char(c as u32).map(|row| (idx, row)));
+
+        found.or_else(|| self.fnt.char('?' as u32).map(|row| (0, row)))
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// quad of a single character
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glyph {
+    pub id: u32,
+    /// font the glyph is taken from, draws use its mesh and material - see `HellFont::font`
+    pub font: usize,
+    /// scales and moves the unit quad onto the glyph, relative to the transform of the text mesh
+    pub transform: Transform,
+    /// offset (xy) and size (zw) of the glyph in the font atlas, in uv coordinates
+    pub uv_rect: Vec4,
+    pub color: Vec4,
+}
+
+/// key and args of a text that follows the active language
+#[derive(Debug, Clone, PartialEq)]
+struct LocalizedText {
+    key: String,
+    args: Vec<(String, LocaleArg)>,
+    /// of the localization the text was formatted with
+    revision: u32,
 }
 
 // ----------------------------------------------------------------------------
@@ -21,9 +107,14 @@ impl HellFont {
 pub struct TextMesh {
     font: Option<HellFont>,
     transform: Transform,
+    params: LayoutParams,
 
-    char_transforms: Vec<Transform>,
+    glyphs: Vec<Glyph>,
+    size: Vec2,
     txt: Option<String>,
+    rich_txt: RichText,
+    localized: Option<LocalizedText>,
+    sdf_style: Option<SdfStyle>,
 }
 
 impl TextMesh {
@@ -33,36 +124,352 @@ impl TextMesh {
         Self {
             font,
             transform,
+            params: LayoutParams::default(),
 
-            char_transforms: vec![],
+            glyphs: vec![],
+            size: Vec2::ZERO,
             txt: None,
+            rich_txt: RichText::default(),
+            localized: None,
+            sdf_style: None,
         }
     }
 
-    pub fn char_transforms(&self) -> &[Transform] {
-        &self.char_transforms
+    /// relative to the transform of the text mesh - characters without pixels (e.g. spaces) don't get a glyph
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+
+    /// width of the longest line and height of all lines
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    /// as it was set, including the markup
+    pub fn text(&self) -> Option<&str> {
+        self.txt.as_deref()
+    }
+
+    pub fn rich_text(&self) -> &RichText {
+        &self.rich_txt
+    }
+
+    pub fn font(&self) -> Option<&HellFont> {
+        self.font.as_ref()
+    }
+
+    pub fn transform(&self) -> &Transform {
+        &self.transform
     }
 
     pub fn transform_mut(&mut self) -> &mut Transform {
         &mut self.transform
     }
 
+    /// key of the localized text, `None` when the text was set directly
+    pub fn localized_key(&self) -> Option<&str> {
+        self.localized.as_ref().map(|l| l.key.as_str())
+    }
+
     pub fn set_text(&mut self, txt: impl Into<String>) {
         let txt = txt.into();
-        let new_len = txt.len();
-
+        self.localized = None;
+        self.rich_txt = RichText::plain(txt.clone());
         self.txt = Some(txt);
+        self.layout();
+    }
+
+    /// parses the markup of `RichText`
+    pub fn set_rich_text(&mut self, markup: impl Into<String>) {
+        let markup = markup.into();
+        self.localized = None;
+        self.rich_txt = RichText::parse(&markup);
+        self.txt = Some(markup);
+        self.layout();
+    }
+
+    pub fn set_spans(&mut self, spans: RichText) {
+        self.localized = None;
+        self.txt = Some(spans.plain_text());
+        self.rich_txt = spans;
+        self.layout();
+    }
 
-        self.char_transforms.resize_with(new_len, Transform::default);
+    /// the localized string may contain the markup of `RichText` - see `Localization::format` for the args
+    pub fn set_localized_text(&mut self, localization: &Localization, key: impl Into<String>, args: &[(&str, LocaleArg)]) {
+        let localized = LocalizedText {
+            key: key.into(),
+            args: args.iter().map(|(name, arg)| (name.to_string(), arg.clone())).collect(),
+            revision: localization.revision(),
+        };
 
-        let mut curr_x = 0.0;
-        for t in &mut self.char_transforms {
-            t.translation = glam::vec3(curr_x, 0.0, 0.0);
-            curr_x += 1.0;
+        self.apply_localized(localization, localized);
+    }
+
+    /// formats the localized text again after the language was switched, returns true if the text changed
+    pub fn localize(&mut self, localization: &Localization) -> bool {
+        match self.localized.take() {
+            Some(localized) if localized.revision != localization.revision() => {
+                let previous = self.txt.clone();
+                self.apply_localized(localization, LocalizedText { revision: localization.revision(), ..localized });
+                self.txt != previous
+            }
+            localized => {
+                self.localized = localized;
+                false
+            }
         }
     }
 
+    fn apply_localized(&mut self, localization: &Localization, localized: LocalizedText) {
+        let args: Vec<(&str, LocaleArg)> = localized.args.iter().map(|(name, arg)| (name.as_str(), arg.clone())).collect();
+        self.set_rich_text(localization.format(&localized.key, &args));
+        self.localized = Some(localized);
+    }
+
     pub fn set_font(&mut self, font: Option<HellFont>) {
         self.font = font;
+        self.layout();
+    }
+
+    pub fn set_align(&mut self, align: TextAlign) {
+        self.params.align = align;
+        self.layout();
+    }
+
+    /// height of a line in local units
+    pub fn set_line_height(&mut self, line_height: f32) {
+        self.params.line_height = line_height;
+        self.layout();
+    }
+
+    /// distance between the tops of two lines, relative to the height of the upper line
+    pub fn set_line_spacing(&mut self, line_spacing: f32) {
+        self.params.line_spacing = line_spacing;
+        self.layout();
+    }
+
+    /// lines that are wider are wrapped on word boundaries
+    pub fn set_max_width(&mut self, max_width: Option<f32>) {
+        self.params.max_width = max_width;
+        self.layout();
+    }
+
+    /// the last visible line is truncated with an ellipsis when the text has more lines
+    pub fn set_max_lines(&mut self, max_lines: Option<usize>) {
+        self.params.max_lines = max_lines;
+        self.layout();
+    }
+
+    /// used by spans without a color
+    pub fn set_color(&mut self, color: Vec4) {
+        self.params.color = color;
+        self.layout();
+    }
+
+    /// outline and shadow of fonts with a distance field atlas, has to be passed to the render data of every glyph
+    pub fn sdf_style(&self) -> Option<SdfStyle> {
+        self.sdf_style
+    }
+
+    /// `None` for bitmap fonts
+    pub fn set_sdf_style(&mut self, style: Option<SdfStyle>) {
+        self.sdf_style = style;
+    }
+}
+
+
+
+// layout
+// ------
+impl TextMesh {
+    fn layout(&mut self) {
+        let (Some(font), Some(_)) = (&self.font, &self.txt) else {
+            self.glyphs.clear();
+            self.size = Vec2::ZERO;
+            return;
+        };
+
+        let layout = layout::layout(font, &self.rich_txt.spans, &self.params);
+        self.glyphs = layout.glyphs;
+        self.size = layout.size;
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::{self, measure};
+    use hell_resources::fonts::FntFileCommon;
+    use hell_resources::locale::StringTable;
+
+    fn text(txt: &str, align: TextAlign) -> TextMesh {
+        let mut mesh = TextMesh::new(Some(text::test_font(0)));
+        mesh.set_line_height(72.0);
+        mesh.set_align(align);
+        mesh.set_text(txt);
+        mesh
+    }
+
+    #[test]
+    fn test_glyph_metrics() {
+        // '!' is 16x54 at (432, 0) with offset (14, 15), the font is monospaced with an advance of 45
+        let mesh = text("!!", TextAlign::Left);
+        let glyphs = mesh.glyphs();
+
+        assert_eq!(glyphs.len(), 2);
+        assert_eq!(glyphs[0].id, '!' as u32);
+        assert_eq!(glyphs[0].transform.scale, glam::vec3(16.0, 54.0, 1.0));
+        assert_eq!(glyphs[0].transform.translation, glam::vec3(14.0 + 8.0, -(15.0 + 27.0), 0.0));
+        assert_eq!(glyphs[1].transform.translation.x, 45.0 + 14.0 + 8.0);
+        assert_eq!(glyphs[0].uv_rect, glam::vec4(432.0 / 449.0, 0.0, 16.0 / 449.0, 54.0 / 449.0));
+        assert_eq!(mesh.size(), glam::vec2(90.0, 72.0));
+    }
+
+    #[test]
+    fn test_lines_and_align() {
+        // spaces only advance, unknown characters fall back to '?'
+        let mesh = text("! !\n!\u{4e16}", TextAlign::Right);
+        let glyphs = mesh.glyphs();
+
+        assert_eq!(glyphs.len(), 4);
+        assert_eq!(glyphs[3].id, '?' as u32);
+        assert_eq!(glyphs[2].transform.translation.y, glyphs[0].transform.translation.y - 72.0);
+        assert_eq!(mesh.size(), glam::vec2(135.0, 144.0));
+
+        // both lines end at the origin
+        assert_eq!(glyphs[0].transform.translation.x, -135.0 + 22.0);
+        assert_eq!(glyphs[2].transform.translation.x, -90.0 + 22.0);
+
+        let centered = text("!!", TextAlign::Center);
+        assert_eq!(centered.glyphs()[0].transform.translation.x, -45.0 + 22.0);
+    }
+
+    fn ids(mesh: &TextMesh) -> String {
+        mesh.glyphs().iter().map(|g| char::from_u32(g.id).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_measure() {
+        let font = text("", TextAlign::Left).font().cloned().unwrap();
+
+        assert_eq!(measure("ab\nabc", &font, 72.0), glam::vec2(135.0, 144.0));
+        assert_eq!(measure("a", &font, 36.0), glam::vec2(22.5, 36.0));
+        // trailing spaces don't count
+        assert_eq!(measure("a  ", &font, 72.0), glam::vec2(45.0, 72.0));
+    }
+
+    #[test]
+    fn test_wrap_and_ellipsis() {
+        // 4 characters fit into a line
+        let mut mesh = text("ab cd efghijklm", TextAlign::Left);
+        mesh.set_max_width(Some(200.0));
+
+        let glyphs = mesh.glyphs();
+        assert_eq!(ids(&mesh), "abcdefghijklm");
+        assert_eq!(mesh.size(), glam::vec2(180.0, 5.0 * 72.0));
+        // 'c' starts the second line
+        assert_eq!(glyphs[2].transform.translation.y, glyphs[0].transform.translation.y - 72.0);
+        assert!(glyphs[2].transform.translation.x < glyphs[1].transform.translation.x);
+
+        // the font has no '…', the ellipsis has to fit into the max width
+        mesh.set_max_lines(Some(2));
+        assert_eq!(ids(&mesh), "abc...");
+        assert_eq!(mesh.size(), glam::vec2(180.0, 144.0));
+
+        let mut mesh = text("ab\ncd\nef", TextAlign::Left);
+        mesh.set_max_lines(Some(1));
+        assert_eq!(ids(&mesh), "ab...");
+        assert_eq!(mesh.size(), glam::vec2(225.0, 72.0));
+    }
+
+    #[test]
+    fn test_rich_text() {
+        // 'a' is 40x44 with a y-offset of 25, the baseline is 66 pixels below the top of the line
+        let mut mesh = text("", TextAlign::Left);
+        mesh.set_color(glam::vec4(0.0, 0.0, 1.0, 1.0));
+        mesh.set_rich_text("[size=2][color=red]a[/color][/size]a");
+        let glyphs = mesh.glyphs();
+
+        assert_eq!(mesh.text(), Some("[size=2][color=red]a[/color][/size]a"));
+        assert_eq!(glyphs[0].transform.scale, glam::vec3(80.0, 88.0, 1.0));
+        assert_eq!(glyphs[0].color, glam::vec4(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(glyphs[1].color, glam::vec4(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(mesh.size(), glam::vec2(135.0, 144.0));
+
+        // both share the baseline of the larger character
+        assert_eq!(glyphs[0].transform.translation.y, -(132.0 - 41.0 * 2.0 + 44.0));
+        assert_eq!(glyphs[1].transform.translation.y, -(132.0 - 41.0 + 22.0));
+    }
+
+    #[test]
+    fn test_line_spacing() {
+        let mut mesh = text("a\na", TextAlign::Left);
+        mesh.set_line_spacing(1.5);
+        let glyphs = mesh.glyphs();
+
+        assert_eq!(glyphs[1].transform.translation.y, glyphs[0].transform.translation.y - 108.0);
+        assert_eq!(mesh.size(), glam::vec2(45.0, 108.0 + 72.0));
+    }
+
+    #[test]
+    fn test_font_fallback() {
+        // 36 pixels per line with the baseline 30 pixels below the top, scaled by 2 to fit the line height of 72
+        let common = FntFileCommon { line_height: 36, base: 30, scale_w: 64, scale_h: 64, pages: 1, ..Default::default() };
+        let row = FntFileCharRow { id: 0x4e16, width: 32, height: 32, xoffset: 2, yoffset: 4, xadvance: 36, chnl: 15, ..Default::default() };
+        let fallback = FntFile::new(Default::default(), common, vec![], vec![row], vec![]);
+
+        let mut mesh = text("", TextAlign::Left);
+        let font = mesh.font().cloned().unwrap().with_fallback(HellFont::new(0, 7, Arc::new(fallback)));
+        mesh.set_font(Some(font));
+        mesh.set_text("!\u{4e16}");
+        let glyphs = mesh.glyphs();
+
+        assert_eq!((glyphs[0].font, glyphs[1].font), (0, 1));
+        assert_eq!(mesh.font().unwrap().font(glyphs[1].font).unwrap().material(), 7);
+        // shares the baseline at 66 with the primary font
+        assert_eq!(glyphs[1].transform.scale, glam::vec3(64.0, 64.0, 1.0));
+        assert_eq!(glyphs[1].transform.translation, glam::vec3(45.0 + 4.0 + 32.0, -(66.0 - 26.0 * 2.0 + 32.0), 0.0));
+        assert_eq!(glyphs[1].uv_rect, glam::vec4(0.0, 0.0, 0.5, 0.5));
+        assert_eq!(mesh.size(), glam::vec2(45.0 + 72.0, 72.0));
+    }
+
+    #[test]
+    fn test_localized_text() {
+        let en = StringTable::from_yaml("language: en\nstrings: { hud.lives: '{count} lives', menu.quit: quit }").unwrap();
+        let de = StringTable::from_yaml("language: de\nstrings: { hud.lives: '{count} Leben' }").unwrap();
+        let mut loc = Localization::new(vec![en, de]).unwrap();
+
+        let mut mesh = text("", TextAlign::Left);
+        mesh.set_localized_text(&loc, "hud.lives", &[("count", 3.into())]);
+        assert_eq!(mesh.text(), Some("3 lives"));
+        assert!(!mesh.localize(&loc));
+
+        loc.set_language("de").unwrap();
+        assert!(mesh.localize(&loc));
+        assert_eq!(mesh.text(), Some("3 Leben"));
+        assert_eq!(mesh.glyphs().len(), 6);
+
+        // the fallback language has the same text
+        let mut quit = text("", TextAlign::Left);
+        quit.set_localized_text(&loc, "menu.quit", &[]);
+        loc.set_language("en").unwrap();
+        assert!(!quit.localize(&loc));
+
+        mesh.set_text("abc");
+        assert_eq!(mesh.localized_key(), None);
+        assert!(!mesh.localize(&loc));
+    }
+
+    #[test]
+    fn test_without_font() {
+        let mut mesh = TextMesh::new(None);
+        mesh.set_text("abc");
+        assert!(mesh.glyphs().is_empty());
+        assert_eq!(mesh.text(), Some("abc"));
     }
 }
@@ -0,0 +1,779 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::RangeInclusive;
+
+use glam::{Vec2, Vec4};
+use hell_common::transform::Transform;
+use hell_renderer::render_types::RenderData;
+use hell_renderer::resources::ResourceHandle;
+
+use crate::text::{layout::{self, LayoutParams}, HellFont, RichText, TextAlign, TextSpan};
+use super::{NavInput, UiInput, UiTheme, WidgetStyle};
+
+
+
+// ----------------------------------------------------------------------------
+// types
+// ----------------------------------------------------------------------------
+
+/// ui units, the origin is at the top left and y goes down
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct UiRect {
+    pub pos: Vec2,
+    pub size: Vec2,
+}
+
+impl UiRect {
+    pub const fn new(pos: Vec2, size: Vec2) -> Self {
+        Self { pos, size }
+    }
+
+    pub fn max(&self) -> Vec2 {
+        self.pos + self.size
+    }
+
+    pub fn center(&self) -> Vec2 {
+        self.pos + self.size * 0.5
+    }
+
+    pub fn contains(&self, p: Vec2) -> bool {
+        p.cmpge(self.pos).all() && p.cmplt(self.max()).all()
+    }
+
+    /// negative amounts grow the rect
+    pub fn shrink(&self, amount: f32) -> Self {
+        let size = (self.size - Vec2::splat(amount * 2.0)).max(Vec2::ZERO);
+        Self::new(self.pos + Vec2::splat(amount), size)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WidgetId(u64);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Response {
+    pub hovered: bool,
+    pub focused: bool,
+    /// released with the cursor above it or activated while focused
+    pub clicked: bool,
+    /// the value of the widget was changed
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiQuadKind {
+    /// untextured rectangle
+    Solid,
+    /// samples the atlas of the font with this index - see `HellFont::font`
+    Glyph(usize),
+    /// part of a nine slice, samples the skin
+    Image,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiQuad {
+    pub rect: UiRect,
+    pub uv_rect: Vec4,
+    pub color: Vec4,
+    pub kind: UiQuadKind,
+}
+
+struct Region {
+    rect: UiRect,
+    /// offset of the next widget from the top of the region
+    cursor: f32,
+}
+
+
+
+// ----------------------------------------------------------------------------
+// ui
+// ----------------------------------------------------------------------------
+
+/// immediate-mode widgets - call the widget functions between `begin_frame` and `end_frame` every frame
+///
+/// widgets are identified by their label and the panel they are in. labels with '##' are identified by the text after it,
+/// so the shown text can change without losing the focus, e.g. "Apply##audio" and "Anwenden##audio".
+pub struct Ui {
+    theme: UiTheme,
+    font: Option<HellFont>,
+    /// material of the nine slices in the theme
+    skin: Option<ResourceHandle>,
+
+    input: UiInput,
+    prev_input: UiInput,
+
+    /// pressed with the mouse and not released yet
+    active: Option<WidgetId>,
+    focus: Option<WidgetId>,
+    /// focusable widgets of the last frame, in order
+    focus_order: Vec<WidgetId>,
+    next_focus_order: Vec<WidgetId>,
+    is_any_hovered: bool,
+
+    id_stack: Vec<u64>,
+    regions: Vec<Region>,
+    quads: Vec<UiQuad>,
+}
+
+impl Ui {
+    pub fn new(theme: UiTheme, font: Option<HellFont>) -> Self {
+        Self {
+            theme,
+            font,
+            skin: None,
+
+            input: UiInput::default(),
+            prev_input: UiInput::default(),
+
+            active: None,
+            focus: None,
+            focus_order: Vec::new(),
+            next_focus_order: Vec::new(),
+            is_any_hovered: false,
+
+            id_stack: Vec::new(),
+            regions: Vec::new(),
+            quads: Vec::new(),
+        }
+    }
+
+    pub fn theme(&self) -> &UiTheme {
+        &self.theme
+    }
+
+    pub fn theme_mut(&mut self) -> &mut UiTheme {
+        &mut self.theme
+    }
+
+    pub fn set_font(&mut self, font: Option<HellFont>) {
+        self.font = font;
+    }
+
+    /// without a skin, nine slices of the theme are drawn as solid rects
+    pub fn set_skin(&mut self, material: Option<ResourceHandle>) {
+        self.skin = material;
+    }
+
+    pub fn focus(&self) -> Option<WidgetId> {
+        self.focus
+    }
+
+    pub fn set_focus(&mut self, focus: Option<WidgetId>) {
+        self.focus = focus;
+    }
+
+    /// the id a widget with `label` would get in the current panel
+    pub fn widget_id(&self, label: &str) -> WidgetId {
+        let id_src = label.split_once("##").map_or(label, |(_, id)| id);
+
+        let mut hasher = DefaultHasher::new();
+        self.id_stack.last().hash(&mut hasher);
+        id_src.hash(&mut hasher);
+        WidgetId(hasher.finish())
+    }
+
+    /// quads of the last frame, back to front
+    pub fn quads(&self) -> &[UiQuad] {
+        &self.quads
+    }
+}
+
+// frame
+// -----
+impl Ui {
+    /// widgets outside of a panel are stacked from the top left of the screen
+    pub fn begin_frame(&mut self, input: UiInput, screen_size: Vec2) {
+        self.prev_input = std::mem::replace(&mut self.input, input);
+        self.quads.clear();
+        self.id_stack.clear();
+        self.regions.clear();
+        self.regions.push(Region { rect: UiRect::new(Vec2::ZERO, screen_size), cursor: 0.0 });
+        self.next_focus_order.clear();
+        self.is_any_hovered = false;
+
+        self.navigate();
+    }
+
+    pub fn end_frame(&mut self) -> &[UiQuad] {
+        std::mem::swap(&mut self.focus_order, &mut self.next_focus_order);
+
+        // widgets that weren't drawn can't keep the focus
+        if self.focus.is_some_and(|f| !self.focus_order.contains(&f)) {
+            self.focus = None;
+        }
+        if !self.input.mouse_down {
+            self.active = None;
+        }
+        // clicking into empty space
+        if self.is_mouse_pressed() && !self.is_any_hovered {
+            self.focus = None;
+        }
+
+        &self.quads
+    }
+
+    fn navigate(&mut self) {
+        if self.is_nav_pressed(|n| n.cancel) {
+            self.focus = None;
+            return;
+        }
+
+        let is_next = self.is_nav_pressed(|n| n.next || n.down);
+        let is_prev = self.is_nav_pressed(|n| n.prev || n.up);
+        if (!is_next && !is_prev) || self.focus_order.is_empty() {
+            return;
+        }
+
+        let count = self.focus_order.len();
+        let current = self.focus.and_then(|f| self.focus_order.iter().position(|o| *o == f));
+        let idx = match (current, is_next) {
+            (Some(c), true)  => (c + 1) % count,
+            (Some(c), false) => (c + count - 1) % count,
+            (None, true)     => 0,
+            (None, false)    => count - 1,
+        };
+
+        self.focus = Some(self.focus_order[idx]);
+    }
+
+    fn is_mouse_pressed(&self) -> bool {
+        self.input.mouse_down && !self.prev_input.mouse_down
+    }
+
+    fn is_mouse_released(&self) -> bool {
+        !self.input.mouse_down && self.prev_input.mouse_down
+    }
+
+    fn is_nav_pressed(&self, button: impl Fn(&NavInput) -> bool) -> bool {
+        button(&self.input.nav) && !button(&self.prev_input.nav)
+    }
+}
+
+// layout
+// ------
+impl Ui {
+    /// content of the panel is stacked from top to bottom inside of `rect`
+    pub fn panel(&mut self, id: &str, rect: UiRect, add_contents: impl FnOnce(&mut Ui)) {
+        let id = self.widget_id(id);
+        let style = self.theme.panel;
+        self.push_styled_quad(rect, &style, style.background);
+
+        // panels catch the cursor, so clicks on the background don't clear the focus
+        if self.input.cursor.is_some_and(|c| rect.contains(c)) {
+            self.is_any_hovered = true;
+        }
+
+        self.id_stack.push(id.0);
+        self.regions.push(Region { rect: rect.shrink(self.theme.padding), cursor: 0.0 });
+        add_contents(self);
+        self.regions.pop();
+        self.id_stack.pop();
+    }
+
+    /// empty space between two widgets
+    pub fn space(&mut self, height: f32) {
+        self.region_mut().cursor += height;
+    }
+
+    /// takes the full width of the current region
+    pub fn allocate(&mut self, height: f32) -> UiRect {
+        let spacing = self.theme.spacing;
+        let region = self.region_mut();
+        let rect = UiRect::new(region.rect.pos + glam::vec2(0.0, region.cursor), glam::vec2(region.rect.size.x, height));
+        region.cursor += height + spacing;
+        rect
+    }
+
+    fn region_mut(&mut self) -> &mut Region {
+        self.regions.last_mut().unwrap()
+    }
+}
+
+// widgets
+// -------
+impl Ui {
+    /// supports the markup of `RichText` and wraps at the width of the region
+    pub fn label(&mut self, text: &str) {
+        let width = self.regions.last().unwrap().rect.size.x;
+        let txt = RichText::parse(text);
+        let height = self.measure_text(&txt, Some(width)).y.max(self.theme.text_size);
+
+        let rect = self.allocate(height);
+        let color = self.theme.label.text;
+        self.push_text(&txt, rect, TextAlign::Left, color);
+    }
+
+    pub fn button(&mut self, label: &str) -> Response {
+        let (id, text) = self.split_label(label);
+        let rect = self.allocate(self.theme.widget_height);
+        let response = self.interact(id, rect);
+
+        let style = self.theme.button;
+        self.push_widget_frame(id, rect, &style, &response);
+        self.push_text(&RichText::parse(text), rect, TextAlign::Center, style.text);
+
+        response
+    }
+
+    pub fn checkbox(&mut self, label: &str, value: &mut bool) -> Response {
+        let (id, text) = self.split_label(label);
+        let rect = self.allocate(self.theme.widget_height);
+        let mut response = self.interact(id, rect);
+
+        if response.clicked {
+            *value = !*value;
+            response.changed = true;
+        }
+
+        let style = self.theme.field;
+        let box_rect = UiRect::new(rect.pos, Vec2::splat(rect.size.y));
+        self.push_widget_frame(id, box_rect, &style, &response);
+        if *value {
+            let accent = self.theme.accent_color;
+            self.push_quad(box_rect.shrink(rect.size.y * 0.25), accent);
+        }
+
+        let text_rect = UiRect::new(rect.pos + glam::vec2(rect.size.y + self.theme.spacing, 0.0), rect.size - glam::vec2(rect.size.y + self.theme.spacing, 0.0));
+        self.push_text(&RichText::parse(text), text_rect, TextAlign::Left, style.text);
+
+        response
+    }
+
+    /// dragged with the mouse or changed in steps of 5% with left and right while focused
+    pub fn slider(&mut self, label: &str, value: &mut f32, range: RangeInclusive<f32>) -> Response {
+        const STEP: f32 = 0.05;
+
+        let (id, text) = self.split_label(label);
+        let rect = self.allocate(self.theme.widget_height);
+        let mut response = self.interact(id, rect);
+
+        let (min, max) = (*range.start(), *range.end());
+        let old = *value;
+
+        if self.active == Some(id) {
+            if let Some(cursor) = self.input.cursor {
+                let t = ((cursor.x - rect.pos.x) / rect.size.x.max(f32::EPSILON)).clamp(0.0, 1.0);
+                *value = min + (max - min) * t;
+            }
+        }
+        if response.focused {
+            if self.is_nav_pressed(|n| n.left) {
+                *value -= (max - min) * STEP;
+            }
+            if self.is_nav_pressed(|n| n.right) {
+                *value += (max - min) * STEP;
+            }
+        }
+
+        *value = value.clamp(min.min(max), max.max(min));
+        response.changed = *value != old;
+
+        let style = self.theme.field;
+        self.push_widget_frame(id, rect, &style, &response);
+
+        let t = if max != min { (*value - min) / (max - min) } else { 0.0 };
+        let accent = self.theme.accent_color;
+        self.push_quad(UiRect::new(rect.pos, glam::vec2(rect.size.x * t, rect.size.y)), accent);
+
+        let text = RichText::plain(format!("{text}: {:.2}", *value));
+        self.push_text(&text, rect, TextAlign::Center, style.text);
+
+        response
+    }
+
+    /// typed text is appended while the field is focused, `clicked` is set when return is pressed
+    pub fn text_field(&mut self, label: &str, text: &mut String) -> Response {
+        let (id, _) = self.split_label(label);
+        let rect = self.allocate(self.theme.widget_height);
+        let mut response = self.interact(id, rect);
+
+        if response.focused {
+            let is_erased = self.is_nav_pressed(|n| n.erase) && text.pop().is_some();
+            let old_len = text.len();
+            text.extend(self.input.text.chars().filter(|c| !c.is_control()));
+            response.changed = is_erased || text.len() != old_len;
+        }
+
+        let style = self.theme.field;
+        self.push_widget_frame(id, rect, &style, &response);
+
+        let padding = glam::vec2(self.theme.padding * 0.5, 0.0);
+        let text_rect = UiRect::new(rect.pos + padding, rect.size - padding * 2.0);
+        let txt: RichText = TextSpan::new(text.as_str()).into();
+        self.push_text(&txt, text_rect, TextAlign::Left, style.text);
+
+        // text cursor
+        if response.focused {
+            let text_width = self.measure_text(&txt, None).x;
+            let height = self.theme.text_size;
+            let pos = glam::vec2(text_rect.pos.x + text_width, rect.center().y - height * 0.5);
+            let accent = self.theme.accent_color;
+            self.push_quad(UiRect::new(pos, glam::vec2(2.0, height)), accent);
+        }
+
+        response
+    }
+}
+
+// helpers
+// -------
+impl Ui {
+    /// the text before '##' is shown, the text after it is used for the id
+    fn split_label<'a>(&self, label: &'a str) -> (WidgetId, &'a str) {
+        let text = label.split_once("##").map_or(label, |(text, _)| text);
+        (self.widget_id(label), text)
+    }
+
+    /// hit testing and focus - widgets drawn later are on top of the earlier ones
+    fn interact(&mut self, id: WidgetId, rect: UiRect) -> Response {
+        self.next_focus_order.push(id);
+
+        let is_over = self.input.cursor.is_some_and(|c| rect.contains(c));
+        let hovered = is_over && (self.active.is_none() || self.active == Some(id));
+        self.is_any_hovered |= is_over;
+
+        let mut clicked = false;
+        if hovered && self.is_mouse_pressed() {
+            self.active = Some(id);
+            self.focus = Some(id);
+        }
+        if self.active == Some(id) && self.is_mouse_released() {
+            clicked = hovered;
+            self.active = None;
+        }
+
+        let focused = self.focus == Some(id);
+        if focused && self.is_nav_pressed(|n| n.activate) {
+            clicked = true;
+        }
+
+        Response { hovered, focused, clicked, changed: false }
+    }
+
+    fn push_widget_frame(&mut self, id: WidgetId, rect: UiRect, style: &WidgetStyle, response: &Response) {
+        if response.focused {
+            let color = self.theme.focus_color;
+            self.push_quad(rect.shrink(-self.theme.focus_width), color);
+        }
+
+        let color = style.color(response.hovered, self.active == Some(id));
+        self.push_styled_quad(rect, style, color);
+    }
+
+    /// a nine slice if the style has one and there is a skin
+    fn push_styled_quad(&mut self, rect: UiRect, style: &WidgetStyle, color: Vec4) {
+        match (style.slice, self.skin) {
+            (Some(slice), Some(_)) if color.w > 0.0 => {
+                for (rect, uv_rect) in slice.slices(rect) {
+                    self.quads.push(UiQuad { rect, uv_rect, color, kind: UiQuadKind::Image });
+                }
+            }
+            _ => self.push_quad(rect, color),
+        }
+    }
+
+    fn push_quad(&mut self, rect: UiRect, color: Vec4) {
+        if color.w <= 0.0 {
+            return;
+        }
+
+        self.quads.push(UiQuad { rect, uv_rect: RenderData::FULL_UV_RECT, color, kind: UiQuadKind::Solid });
+    }
+
+    fn layout_params(&self, max_width: Option<f32>, align: TextAlign, color: Vec4) -> LayoutParams {
+        LayoutParams {
+            align,
+            line_height: self.theme.text_size,
+            max_width,
+            color,
+            ..Default::default()
+        }
+    }
+
+    fn measure_text(&self, txt: &RichText, max_width: Option<f32>) -> Vec2 {
+        let Some(font) = &self.font else {
+            return Vec2::ZERO;
+        };
+
+        let params = self.layout_params(max_width, TextAlign::Left, Vec4::ONE);
+        layout::layout(font, &txt.spans, &params).size
+    }
+
+    /// vertically centered in `rect`
+    fn push_text(&mut self, txt: &RichText, rect: UiRect, align: TextAlign, color: Vec4) {
+        let Some(font) = &self.font else {
+            return;
+        };
+
+        let params = self.layout_params(Some(rect.size.x), align, color);
+        let text = layout::layout(font, &txt.spans, &params);
+
+        let origin_x = match align {
+            TextAlign::Left   => rect.pos.x,
+            TextAlign::Center => rect.center().x,
+            TextAlign::Right  => rect.max().x,
+        };
+        let origin = glam::vec2(origin_x, rect.center().y - text.size.y * 0.5);
+
+        // glyphs are laid out with y going up
+        for g in text.glyphs {
+            let size = g.transform.scale.truncate();
+            let center = origin + glam::vec2(g.transform.translation.x, -g.transform.translation.y);
+            self.quads.push(UiQuad {
+                rect: UiRect::new(center - size * 0.5, size),
+                uv_rect: g.uv_rect,
+                color: g.color,
+                kind: UiQuadKind::Glyph(g.font),
+            });
+        }
+    }
+}
+
+// output
+// ------
+impl Ui {
+    /// adds a draw for every quad as a child of `root` - the quad mesh has to be a unit quad around the origin.
+    /// glyphs use the mesh and material of the font, solid quads `solid_mat`, which should sample a white texture.
+    pub fn write_render_data(&self, render_data: &mut RenderData, quad_mesh: usize, solid_mat: ResourceHandle, root: Option<usize>) {
+        for quad in &self.quads {
+            let (mesh, material) = match (quad.kind, &self.font, self.skin) {
+                (UiQuadKind::Glyph(idx), Some(font), _) => match font.font(idx) {
+                    Some(font) => (font.mesh(), ResourceHandle::new(font.material())),
+                    None       => continue,
+                },
+                (UiQuadKind::Image, _, Some(skin)) => (quad_mesh, skin),
+                _ => (quad_mesh, solid_mat),
+            };
+
+            // the ui goes down along y, the render data up
+            let center = quad.rect.center();
+            let trans = Transform::new(glam::vec3(center.x, -center.y, 0.0), glam::Quat::IDENTITY, quad.rect.size.extend(1.0));
+
+            let draw = render_data.len();
+            match root {
+                Some(root) => render_data.add_child_data(mesh, material, root, trans),
+                None       => render_data.add_data(mesh, material, trans),
+            };
+            render_data.set_uv_rect(draw, quad.uv_rect);
+            render_data.set_tint(draw, quad.color);
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::text;
+    use crate::ui::{Edges, NineSlice};
+    use super::*;
+
+    const SCREEN: Vec2 = Vec2::new(800.0, 600.0);
+    const PANEL: UiRect = UiRect::new(Vec2::new(100.0, 100.0), Vec2::new(300.0, 400.0));
+
+    fn ui() -> Ui {
+        Ui::new(UiTheme::default(), Some(text::test_font(7)))
+    }
+
+    fn mouse(x: f32, y: f32, down: bool) -> UiInput {
+        UiInput { cursor: Some(glam::vec2(x, y)), mouse_down: down, ..Default::default() }
+    }
+
+    fn nav(nav: NavInput) -> UiInput {
+        UiInput { nav, ..Default::default() }
+    }
+
+    /// pause menu with two buttons
+    fn menu(ui: &mut Ui, input: UiInput) -> (Response, Response) {
+        ui.begin_frame(input, SCREEN);
+        let mut result = Default::default();
+        ui.panel("pause", PANEL, |ui| {
+            ui.label("paused");
+            result = (ui.button("resume"), ui.button("quit"));
+        });
+        ui.end_frame();
+        result
+    }
+
+    #[test]
+    fn test_button_click() {
+        let mut ui = ui();
+        // the panel content starts at 112, the label takes 24 + 8, so 'resume' covers y 144..180
+        let (x, y) = (150.0, 160.0);
+
+        let (resume, _) = menu(&mut ui, mouse(x, y, false));
+        assert!(resume.hovered && !resume.clicked);
+
+        let (resume, quit) = menu(&mut ui, mouse(x, y, true));
+        assert!(!resume.clicked && resume.focused);
+        assert!(!quit.hovered);
+
+        let (resume, _) = menu(&mut ui, mouse(x, y, false));
+        assert!(resume.clicked);
+
+        // releasing outside of the button doesn't click it
+        menu(&mut ui, mouse(x, y, true));
+        let (resume, _) = menu(&mut ui, mouse(x, 400.0, false));
+        assert!(!resume.clicked);
+
+        // clicking into empty space clears the focus
+        menu(&mut ui, mouse(10.0, 10.0, true));
+        assert_eq!(ui.focus(), None);
+    }
+
+    #[test]
+    fn test_id_ignores_shown_text() {
+        let mut ui = ui();
+        let next = NavInput { next: true, ..Default::default() };
+        let button = |ui: &mut Ui, label: &str, input: UiInput| {
+            ui.begin_frame(input, SCREEN);
+            let mut response = Response::default();
+            ui.panel("pause", PANEL, |ui| response = ui.button(label));
+            ui.end_frame();
+            response
+        };
+
+        button(&mut ui, "resume##resume", UiInput::default());
+        assert!(button(&mut ui, "resume##resume", nav(next)).focused);
+        // switching the language keeps the focus
+        assert!(button(&mut ui, "Weiter##resume", UiInput::default()).focused);
+
+        assert_eq!(ui.widget_id("resume##resume"), ui.widget_id("Weiter##resume"));
+        assert_ne!(ui.widget_id("resume##resume"), ui.widget_id("resume##restart"));
+        assert_ne!(ui.widget_id("resume"), ui.widget_id("Weiter"));
+    }
+
+    #[test]
+    fn test_focus_navigation() {
+        let mut ui = ui();
+        let next = NavInput { next: true, ..Default::default() };
+        let up = NavInput { up: true, ..Default::default() };
+        let activate = NavInput { activate: true, ..Default::default() };
+
+        menu(&mut ui, UiInput::default());
+        let (resume, _) = menu(&mut ui, nav(next));
+        assert!(resume.focused);
+
+        // holding doesn't repeat
+        let (resume, _) = menu(&mut ui, nav(next));
+        assert!(resume.focused);
+
+        menu(&mut ui, UiInput::default());
+        let (_, quit) = menu(&mut ui, nav(next));
+        assert!(quit.focused);
+
+        menu(&mut ui, UiInput::default());
+        let (_, quit) = menu(&mut ui, nav(activate));
+        assert!(quit.clicked);
+
+        // wraps around in both directions
+        menu(&mut ui, UiInput::default());
+        let (resume, _) = menu(&mut ui, nav(next));
+        assert!(resume.focused);
+
+        menu(&mut ui, UiInput::default());
+        let (_, quit) = menu(&mut ui, nav(up));
+        assert!(quit.focused);
+        assert_ne!(ui.focus(), Some(ui.widget_id("quit")), "ids depend on the panel");
+    }
+
+    #[test]
+    fn test_value_widgets() {
+        let mut ui = ui();
+        let mut checked = false;
+        let mut volume = 0.5;
+        let mut name = String::from("ab");
+
+        let mut frame = |ui: &mut Ui, input: UiInput| {
+            ui.begin_frame(input, SCREEN);
+            let rs = (ui.checkbox("mute", &mut checked), ui.slider("volume", &mut volume, 0.0..=1.0), ui.text_field("name", &mut name));
+            ui.end_frame();
+            rs
+        };
+
+        // widgets are stacked from the top: checkbox 0..36, slider 44..80, text field 88..124
+        frame(&mut ui, mouse(10.0, 10.0, true));
+        let (check, ..) = frame(&mut ui, mouse(10.0, 10.0, false));
+        assert!(check.clicked && check.changed);
+
+        // the slider follows the cursor while it is dragged, even outside of it
+        frame(&mut ui, mouse(400.0, 50.0, true));
+        let (_, slider, _) = frame(&mut ui, mouse(200.0, 300.0, true));
+        assert!(slider.changed);
+        frame(&mut ui, mouse(1000.0, 300.0, false));
+
+        let right = NavInput { right: true, ..Default::default() };
+        let left = NavInput { left: true, ..Default::default() };
+        frame(&mut ui, UiInput::default());
+        frame(&mut ui, nav(left));
+
+        // typing into the focused text field
+        frame(&mut ui, mouse(10.0, 100.0, true));
+        frame(&mut ui, UiInput { text: "c\u{8}d".to_string(), ..mouse(10.0, 100.0, false) });
+        let erase = NavInput { erase: true, ..Default::default() };
+        let (.., field) = frame(&mut ui, nav(erase));
+        assert!(field.focused && field.changed);
+
+        // replacing the last character keeps the length
+        frame(&mut ui, UiInput::default());
+        let (.., field) = frame(&mut ui, UiInput { text: "x".to_string(), ..nav(erase) });
+        assert!(field.changed);
+        frame(&mut ui, nav(right));
+
+        assert!(checked);
+        assert_eq!(volume, 200.0 / 800.0 - 0.05);
+        assert_eq!(name, "abx");
+    }
+
+    #[test]
+    fn test_render_data() {
+        let mut ui = ui();
+        menu(&mut ui, UiInput::default());
+
+        let quads = ui.quads();
+        let glyphs = quads.iter().filter(|q| matches!(q.kind, UiQuadKind::Glyph(_))).count();
+        // panel and two buttons, the label has no background
+        assert_eq!(quads.len() - glyphs, 3);
+        assert_eq!(glyphs, "pausedresumequit".len());
+        assert_eq!(quads[0].rect, PANEL);
+
+        let mut render_data = RenderData::default();
+        let root = render_data.add_node(Transform::default(), None);
+        ui.write_render_data(&mut render_data, 3, ResourceHandle::new(9), Some(root));
+        render_data.transforms.update();
+
+        assert_eq!(render_data.len(), quads.len());
+        let panel = render_data.data_at(0);
+        assert_eq!((panel.mesh_idx, panel.material), (3, ResourceHandle::new(9)));
+        assert_eq!(panel.transform.translation, glam::vec3(250.0, -300.0, 0.0));
+        assert_eq!(panel.tint, UiTheme::default().panel.background);
+
+        let glyph = render_data.data_at(quads.len() - 1);
+        assert_eq!((glyph.mesh_idx, glyph.material), (0, ResourceHandle::new(7)));
+        assert_ne!(glyph.uv_rect, RenderData::FULL_UV_RECT);
+    }
+
+    #[test]
+    fn test_nine_slice_skin() {
+        let mut ui = ui();
+        ui.theme_mut().button.slice = Some(NineSlice::new(Vec4::new(0.0, 0.0, 1.0, 1.0), Vec2::splat(24.0), Edges::all(8.0)));
+
+        // without a skin the slices are drawn as solid rects
+        menu(&mut ui, UiInput::default());
+        assert!(ui.quads().iter().all(|q| q.kind != UiQuadKind::Image));
+
+        ui.set_skin(Some(ResourceHandle::new(5)));
+        menu(&mut ui, UiInput::default());
+        let images: Vec<&UiQuad> = ui.quads().iter().filter(|q| q.kind == UiQuadKind::Image).collect();
+        assert_eq!(images.len(), 2 * 9);
+        assert!(images.iter().all(|q| q.color == UiTheme::default().button.background));
+
+        let mut render_data = RenderData::default();
+        ui.write_render_data(&mut render_data, 3, ResourceHandle::new(9), None);
+        let first_image = ui.quads().iter().position(|q| q.kind == UiQuadKind::Image).unwrap();
+        assert_eq!(render_data.data_at(first_image).material, ResourceHandle::new(5));
+    }
+}
@@ -0,0 +1,578 @@
+use glam::{Vec2, Vec4};
+use hell_common::transform::Transform;
+use hell_renderer::render_types::RenderData;
+use hell_renderer::resources::ResourceHandle;
+
+use super::UiRect;
+
+
+
+// ----------------------------------------------------------------------------
+// style
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Length {
+    /// the size of the content
+    #[default]
+    Auto,
+    /// ui units
+    Px(f32),
+    /// of the content size of the parent (or the screen for roots), e.g. `Percent(50.0)`
+    Percent(f32),
+}
+
+impl Length {
+    /// `None` if the length depends on the content
+    fn resolve(self, parent: Option<f32>) -> Option<f32> {
+        match self {
+            Length::Auto       => None,
+            Length::Px(v)      => Some(v),
+            Length::Percent(p) => parent.map(|s| s * p / 100.0),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FlexDirection {
+    Row,
+    #[default]
+    Column,
+}
+
+impl FlexDirection {
+    fn main(self, v: Vec2) -> f32 {
+        match self {
+            FlexDirection::Row    => v.x,
+            FlexDirection::Column => v.y,
+        }
+    }
+
+    fn cross(self, v: Vec2) -> f32 {
+        match self {
+            FlexDirection::Row    => v.y,
+            FlexDirection::Column => v.x,
+        }
+    }
+
+    fn vec(self, main: f32, cross: f32) -> Vec2 {
+        match self {
+            FlexDirection::Row    => glam::vec2(main, cross),
+            FlexDirection::Column => glam::vec2(cross, main),
+        }
+    }
+}
+
+/// placement of the children along the main axis
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+}
+
+/// placement of the children along the cross axis
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    /// children with an `Auto` cross size fill the parent
+    #[default]
+    Stretch,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Edges {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl Edges {
+    pub const ZERO: Edges = Edges::all(0.0);
+
+    pub const fn all(v: f32) -> Self {
+        Self { left: v, right: v, top: v, bottom: v }
+    }
+
+    pub const fn xy(x: f32, y: f32) -> Self {
+        Self { left: x, right: x, top: y, bottom: y }
+    }
+
+    pub fn top_left(&self) -> Vec2 {
+        glam::vec2(self.left, self.top)
+    }
+
+    pub fn bottom_right(&self) -> Vec2 {
+        glam::vec2(self.right, self.bottom)
+    }
+
+    /// sum of both sides on each axis
+    pub fn size(&self) -> Vec2 {
+        self.top_left() + self.bottom_right()
+    }
+}
+
+/// sizes include the padding but not the margin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexStyle {
+    /// direction of the children
+    pub direction: FlexDirection,
+    pub width: Length,
+    pub height: Length,
+    pub min_size: Vec2,
+    pub max_size: Vec2,
+    pub padding: Edges,
+    pub margin: Edges,
+    /// between two children
+    pub gap: f32,
+    /// share of the free space of the parent along its main axis
+    pub grow: f32,
+    /// share of the missing space of the parent along its main axis, weighted with the natural size
+    pub shrink: f32,
+    pub justify: Justify,
+    pub align_items: Align,
+    /// overrides the `align_items` of the parent
+    pub align_self: Option<Align>,
+    /// the node is not drawn without a background
+    pub background: Option<Vec4>,
+}
+
+impl Default for FlexStyle {
+    fn default() -> Self {
+        Self {
+            direction: FlexDirection::default(),
+            width: Length::Auto,
+            height: Length::Auto,
+            min_size: Vec2::ZERO,
+            max_size: Vec2::INFINITY,
+            padding: Edges::ZERO,
+            margin: Edges::ZERO,
+            gap: 0.0,
+            grow: 0.0,
+            shrink: 1.0,
+            justify: Justify::default(),
+            align_items: Align::default(),
+            align_self: None,
+            background: None,
+        }
+    }
+}
+
+impl FlexStyle {
+    pub fn row() -> Self {
+        Self { direction: FlexDirection::Row, ..Default::default() }
+    }
+
+    pub fn column() -> Self {
+        Self { direction: FlexDirection::Column, ..Default::default() }
+    }
+
+    fn clamp(&self, size: Vec2) -> Vec2 {
+        size.min(self.max_size).max(self.min_size)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// tree
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct FlexNode {
+    style: FlexStyle,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// size of leaves with an `Auto` size, e.g. measured text
+    content_size: Vec2,
+    rect: UiRect,
+}
+
+/// retained ui layout with row and column containers - the layout is only solved again after changes
+///
+/// nodes are addressed by their index like in `TransformHierarchy`, nodes without a parent are laid out against the screen.
+#[derive(Debug, Default, Clone)]
+pub struct FlexTree {
+    nodes: Vec<FlexNode>,
+    screen_size: Vec2,
+    is_dirty: bool,
+}
+
+impl FlexTree {
+    pub fn new(screen_size: Vec2) -> Self {
+        Self {
+            nodes: Vec::new(),
+            screen_size,
+            is_dirty: false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.is_dirty = false;
+    }
+
+    pub fn add(&mut self, style: FlexStyle) -> usize {
+        self.nodes.push(FlexNode {
+            style,
+            parent: None,
+            children: Vec::new(),
+            content_size: Vec2::ZERO,
+            rect: UiRect::default(),
+        });
+        self.is_dirty = true;
+
+        self.len() - 1
+    }
+
+    pub fn add_child(&mut self, parent: usize, style: FlexStyle) -> usize {
+        let idx = self.add(style);
+        self.nodes[idx].parent = Some(parent);
+        self.nodes[parent].children.push(idx);
+        idx
+    }
+
+    pub fn parent(&self, idx: usize) -> Option<usize> {
+        self.nodes[idx].parent
+    }
+
+    pub fn children(&self, idx: usize) -> &[usize] {
+        &self.nodes[idx].children
+    }
+
+    pub fn style(&self, idx: usize) -> &FlexStyle {
+        &self.nodes[idx].style
+    }
+
+    pub fn style_mut(&mut self, idx: usize) -> &mut FlexStyle {
+        self.is_dirty = true;
+        &mut self.nodes[idx].style
+    }
+
+    pub fn set_content_size(&mut self, idx: usize, size: Vec2) {
+        if self.nodes[idx].content_size != size {
+            self.nodes[idx].content_size = size;
+            self.is_dirty = true;
+        }
+    }
+
+    pub fn screen_size(&self) -> Vec2 {
+        self.screen_size
+    }
+
+    /// e.g. after the window was resized
+    pub fn set_screen_size(&mut self, size: Vec2) {
+        if self.screen_size != size {
+            self.screen_size = size;
+            self.is_dirty = true;
+        }
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.is_dirty
+    }
+
+    /// the rect of the last layout
+    pub fn rect(&self, idx: usize) -> UiRect {
+        self.nodes[idx].rect
+    }
+
+    /// transform of a unit quad centered on the origin that covers the rect - the ui goes down along y, the transform up
+    pub fn transform(&self, idx: usize) -> Transform {
+        let rect = self.rect(idx);
+        let center = rect.center();
+        Transform::new(glam::vec3(center.x, -center.y, 0.0), glam::Quat::IDENTITY, rect.size.extend(1.0))
+    }
+}
+
+// layout
+// ------
+impl FlexTree {
+    /// solves the layout if anything changed since the last update, returns whether it did
+    pub fn update(&mut self) -> bool {
+        if !self.is_dirty {
+            return false;
+        }
+
+        let roots: Vec<usize> = (0..self.len()).filter(|idx| self.nodes[*idx].parent.is_none()).collect();
+        for root in roots {
+            let style = self.nodes[root].style;
+            let avail = (self.screen_size - style.margin.size()).max(Vec2::ZERO);
+            let natural = self.natural_size(root, (Some(self.screen_size.x), Some(self.screen_size.y)));
+
+            // roots without a size fill the screen
+            let width  = style.width.resolve(Some(self.screen_size.x)).map_or(avail.x, |_| natural.x);
+            let height = style.height.resolve(Some(self.screen_size.y)).map_or(avail.y, |_| natural.y);
+            let size = style.clamp(glam::vec2(width, height));
+
+            self.place(root, UiRect::new(style.margin.top_left(), size));
+        }
+
+        self.is_dirty = false;
+        true
+    }
+
+    /// size the node wants without any growing or shrinking. percentages resolve against the known axes of `parent_inner`
+    fn natural_size(&self, idx: usize, parent_inner: (Option<f32>, Option<f32>)) -> Vec2 {
+        let node = &self.nodes[idx];
+        let style = &node.style;
+        let width  = style.width.resolve(parent_inner.0);
+        let height = style.height.resolve(parent_inner.1);
+
+        let size = match (width, height) {
+            (Some(w), Some(h)) => glam::vec2(w, h),
+            _ => {
+                // percentages of children can only be resolved on the axes whose size is already known
+                let padding = style.padding.size();
+                let inner = (width.map(|w| (w - padding.x).max(0.0)), height.map(|h| (h - padding.y).max(0.0)));
+                let content = if node.children.is_empty() {
+                    node.content_size
+                } else {
+                    let dir = style.direction;
+                    let mut main = style.gap * (node.children.len() - 1) as f32;
+                    let mut cross: f32 = 0.0;
+                    for child in &node.children {
+                        let outer = self.natural_size(*child, inner) + self.nodes[*child].style.margin.size();
+                        main += dir.main(outer);
+                        cross = cross.max(dir.cross(outer));
+                    }
+                    dir.vec(main, cross)
+                };
+
+                let auto = content + style.padding.size();
+                glam::vec2(width.unwrap_or(auto.x), height.unwrap_or(auto.y))
+            }
+        };
+
+        style.clamp(size)
+    }
+
+    fn place(&mut self, idx: usize, rect: UiRect) {
+        self.nodes[idx].rect = rect;
+
+        let style = self.nodes[idx].style;
+        let children = self.nodes[idx].children.clone();
+        if children.is_empty() {
+            return;
+        }
+
+        let dir = style.direction;
+        let inner = UiRect::new(rect.pos + style.padding.top_left(), (rect.size - style.padding.size()).max(Vec2::ZERO));
+        let inner_main = dir.main(inner.size);
+        let inner_cross = dir.cross(inner.size);
+
+        let naturals: Vec<Vec2> = children.iter().map(|c| self.natural_size(*c, (Some(inner.size.x), Some(inner.size.y)))).collect();
+        let styles: Vec<FlexStyle> = children.iter().map(|c| self.nodes[*c].style).collect();
+        let fixed = style.gap * (children.len() - 1) as f32 + styles.iter().map(|s| dir.main(s.margin.size())).sum::<f32>();
+
+        // grow or shrink along the main axis
+        // ----------------------------------
+        let mut sizes: Vec<f32> = naturals.iter().map(|n| dir.main(*n)).collect();
+        let free = inner_main - fixed - sizes.iter().sum::<f32>();
+        if free > 0.0 {
+            let total: f32 = styles.iter().map(|s| s.grow).sum();
+            if total > 0.0 {
+                for (size, s) in sizes.iter_mut().zip(&styles) {
+                    *size = (*size + free * s.grow / total).min(dir.main(s.max_size));
+                }
+            }
+        } else if free < 0.0 {
+            let total: f32 = styles.iter().zip(&sizes).map(|(s, size)| s.shrink * size).sum();
+            if total > 0.0 {
+                for (size, s) in sizes.iter_mut().zip(&styles) {
+                    let shrunk = *size + free * s.shrink * *size / total;
+                    *size = shrunk.max(dir.main(s.min_size)).max(0.0);
+                }
+            }
+        }
+
+        // justify
+        // -------
+        let free = (inner_main - fixed - sizes.iter().sum::<f32>()).max(0.0);
+        let count = children.len() as f32;
+        let (mut cursor, between) = match style.justify {
+            Justify::Start        => (0.0, 0.0),
+            Justify::Center       => (free * 0.5, 0.0),
+            Justify::End          => (free, 0.0),
+            Justify::SpaceBetween => (0.0, if count > 1.0 { free / (count - 1.0) } else { 0.0 }),
+            Justify::SpaceAround  => (free / count * 0.5, free / count),
+        };
+
+        // align and place
+        // ---------------
+        for (i, child) in children.iter().enumerate() {
+            let s = &styles[i];
+            let cross_avail = (inner_cross - dir.cross(s.margin.size())).max(0.0);
+            let align = s.align_self.unwrap_or(style.align_items);
+            let cross_size = match dir {
+                FlexDirection::Row    => s.height,
+                FlexDirection::Column => s.width,
+            };
+
+            let cross = match (cross_size, align) {
+                (Length::Auto, Align::Stretch) => cross_avail,
+                _                              => dir.cross(naturals[i]),
+            };
+            let cross = cross.min(dir.cross(s.max_size)).max(dir.cross(s.min_size));
+
+            let cross_offset = match align {
+                Align::Start | Align::Stretch => 0.0,
+                Align::Center                 => (cross_avail - cross) * 0.5,
+                Align::End                    => cross_avail - cross,
+            };
+
+            cursor += dir.main(s.margin.top_left());
+            let pos = inner.pos + dir.vec(cursor, dir.cross(s.margin.top_left()) + cross_offset);
+            self.place(*child, UiRect::new(pos, dir.vec(sizes[i], cross)));
+
+            cursor += sizes[i] + dir.main(s.margin.bottom_right()) + style.gap + between;
+        }
+    }
+}
+
+// output
+// ------
+impl FlexTree {
+    /// adds a quad for every node with a background, parents are drawn before their children
+    pub fn write_render_data(&self, render_data: &mut RenderData, quad_mesh: usize, solid_mat: ResourceHandle, root: Option<usize>) {
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let Some(color) = node.style.background else {
+                continue;
+            };
+
+            let draw = render_data.len();
+            let trans = self.transform(idx);
+            match root {
+                Some(root) => render_data.add_child_data(quad_mesh, solid_mat, root, trans),
+                None       => render_data.add_data(quad_mesh, solid_mat, trans),
+            };
+            render_data.set_tint(draw, color);
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Vec2 = Vec2::new(800.0, 600.0);
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> UiRect {
+        UiRect::new(glam::vec2(x, y), glam::vec2(w, h))
+    }
+
+    #[test]
+    fn test_row_grow_and_align() {
+        let mut tree = FlexTree::new(SCREEN);
+        let root = tree.add(FlexStyle {
+            padding: Edges::all(10.0),
+            gap: 20.0,
+            align_items: Align::Center,
+            ..FlexStyle::row()
+        });
+        let a = tree.add_child(root, FlexStyle { width: Length::Px(100.0), height: Length::Px(50.0), ..Default::default() });
+        let b = tree.add_child(root, FlexStyle { height: Length::Px(50.0), grow: 1.0, ..Default::default() });
+        let c = tree.add_child(root, FlexStyle { width: Length::Px(100.0), align_self: Some(Align::Stretch), margin: Edges::xy(0.0, 5.0), ..Default::default() });
+
+        assert!(tree.update());
+        assert_eq!(tree.rect(root), rect(0.0, 0.0, 800.0, 600.0));
+        assert_eq!(tree.rect(a), rect(10.0, 275.0, 100.0, 50.0));
+        // 780 - 2 * 100 - 2 * 20
+        assert_eq!(tree.rect(b), rect(130.0, 275.0, 540.0, 50.0));
+        assert_eq!(tree.rect(c), rect(690.0, 15.0, 100.0, 570.0));
+        assert!(!tree.update());
+    }
+
+    #[test]
+    fn test_percent_min_max() {
+        let mut tree = FlexTree::new(SCREEN);
+        let root = tree.add(FlexStyle { justify: Justify::SpaceBetween, align_items: Align::End, ..FlexStyle::column() });
+        let a = tree.add_child(root, FlexStyle { width: Length::Percent(50.0), height: Length::Percent(10.0), ..Default::default() });
+        let b = tree.add_child(root, FlexStyle { width: Length::Percent(90.0), height: Length::Px(10.0), max_size: glam::vec2(300.0, f32::INFINITY), ..Default::default() });
+        let c = tree.add_child(root, FlexStyle { height: Length::Px(1.0), min_size: glam::vec2(0.0, 20.0), align_self: Some(Align::Start), ..Default::default() });
+        tree.set_content_size(c, glam::vec2(64.0, 0.0));
+
+        tree.update();
+        assert_eq!(tree.rect(a), rect(400.0, 0.0, 400.0, 60.0));
+        assert_eq!(tree.rect(b), rect(500.0, 315.0, 300.0, 10.0));
+        assert_eq!(tree.rect(c), rect(0.0, 580.0, 64.0, 20.0));
+    }
+
+    #[test]
+    fn test_percent_of_partly_sized_parent() {
+        let mut tree = FlexTree::new(SCREEN);
+        let root = tree.add(FlexStyle::row());
+        let panel = tree.add_child(root, FlexStyle { width: Length::Px(200.0), padding: Edges::all(10.0), ..FlexStyle::column() });
+        let item = tree.add_child(panel, FlexStyle { width: Length::Percent(50.0), ..Default::default() });
+        tree.set_content_size(item, glam::vec2(300.0, 20.0));
+
+        // only the width of the panel is known, the item still resolves its percentage against it
+        assert_eq!(tree.natural_size(item, (Some(180.0), None)), glam::vec2(90.0, 20.0));
+        assert_eq!(tree.natural_size(panel, (Some(SCREEN.x), Some(SCREEN.y))), glam::vec2(200.0, 40.0));
+
+        tree.update();
+        assert_eq!(tree.rect(item), rect(10.0, 10.0, 90.0, 20.0));
+    }
+
+    #[test]
+    fn test_auto_size_and_shrink() {
+        let mut tree = FlexTree::new(SCREEN);
+        let root = tree.add(FlexStyle { justify: Justify::Center, align_items: Align::Center, ..FlexStyle::row() });
+        let panel = tree.add_child(root, FlexStyle { padding: Edges::all(8.0), gap: 4.0, ..FlexStyle::column() });
+        let rows: Vec<usize> = (0..3).map(|_| tree.add_child(panel, FlexStyle::default())).collect();
+        for row in &rows {
+            tree.set_content_size(*row, glam::vec2(100.0, 20.0));
+        }
+
+        tree.update();
+        // 3 * 20 + 2 * 4 + 2 * 8
+        assert_eq!(tree.rect(panel), rect(342.0, 258.0, 116.0, 84.0));
+        assert_eq!(tree.rect(rows[1]), rect(350.0, 290.0, 100.0, 20.0));
+
+        // two children that don't fit shrink relative to their size
+        let mut tree = FlexTree::new(glam::vec2(300.0, 100.0));
+        let root = tree.add(FlexStyle::row());
+        let a = tree.add_child(root, FlexStyle { width: Length::Px(200.0), ..Default::default() });
+        let b = tree.add_child(root, FlexStyle { width: Length::Px(400.0), ..Default::default() });
+        tree.update();
+        assert_eq!(tree.rect(a).size.x, 100.0);
+        assert_eq!(tree.rect(b).size.x, 200.0);
+    }
+
+    #[test]
+    fn test_relayout_on_screen_change() {
+        let mut tree = FlexTree::new(SCREEN);
+        let root = tree.add(FlexStyle { justify: Justify::End, ..FlexStyle::row() });
+        let bar = tree.add_child(root, FlexStyle { width: Length::Percent(25.0), ..Default::default() });
+        tree.update();
+        assert_eq!(tree.rect(bar), rect(600.0, 0.0, 200.0, 600.0));
+
+        tree.set_screen_size(SCREEN);
+        assert!(!tree.is_dirty());
+        tree.set_screen_size(glam::vec2(1600.0, 900.0));
+        assert!(tree.update());
+        assert_eq!(tree.rect(bar), rect(1200.0, 0.0, 400.0, 900.0));
+
+        let trans = tree.transform(bar);
+        assert_eq!(trans.translation, glam::vec3(1400.0, -450.0, 0.0));
+        assert_eq!(trans.scale, glam::vec3(400.0, 900.0, 1.0));
+    }
+}
@@ -0,0 +1,59 @@
+use glam::Vec2;
+use hell_input::{InputManager, KeyCode, MouseButton};
+
+
+
+// ----------------------------------------------------------------------------
+// input
+// ----------------------------------------------------------------------------
+
+/// buttons that move and use the focus - they can be mapped from the keyboard as well as from a gamepad
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NavInput {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub next: bool,
+    pub prev: bool,
+    pub activate: bool,
+    pub cancel: bool,
+    /// removes the last character of the focused text field
+    pub erase: bool,
+}
+
+/// the input state of the current frame - `Ui` detects presses and releases by comparing it to the last frame
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UiInput {
+    /// in ui units, `None` if there is no cursor
+    pub cursor: Option<Vec2>,
+    pub mouse_down: bool,
+    pub nav: NavInput,
+    /// typed since the last frame
+    pub text: String,
+}
+
+impl UiInput {
+    /// arrows, tab, return and backspace drive the navigation. the cursor is divided by `px_per_unit` to get ui units
+    pub fn from_input(input: &InputManager, px_per_unit: f32) -> Self {
+        let key = |k: KeyCode| input.key_state(k).is_down();
+        let is_shift = key(KeyCode::ShiftLeft) || key(KeyCode::ShiftRight);
+
+        Self {
+            cursor: input.cursor_pos().map(|p| p / px_per_unit),
+            mouse_down: input.mouse_button_state(MouseButton::Left).is_down(),
+            nav: NavInput {
+                up: key(KeyCode::ArrowUp),
+                down: key(KeyCode::ArrowDown),
+                left: key(KeyCode::ArrowLeft),
+                right: key(KeyCode::ArrowRight),
+                next: key(KeyCode::Tab) && !is_shift,
+                prev: key(KeyCode::Tab) && is_shift,
+                activate: key(KeyCode::Return),
+                cancel: false,
+                erase: key(KeyCode::Backspace),
+            },
+            text: input.typed_text().to_string(),
+        }
+    }
+}
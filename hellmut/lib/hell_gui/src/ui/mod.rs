@@ -0,0 +1,13 @@
+mod context;
+mod flex;
+mod input;
+mod nine_slice;
+mod screen;
+mod theme;
+
+pub use context::*;
+pub use flex::*;
+pub use input::*;
+pub use nine_slice::*;
+pub use screen::*;
+pub use theme::*;
@@ -0,0 +1,136 @@
+use glam::{Vec2, Vec4};
+use serde::Deserialize;
+
+use super::{Edges, UiRect};
+
+
+
+// ----------------------------------------------------------------------------
+// nine slice
+// ----------------------------------------------------------------------------
+
+/// sprite that is split into corners, edges and a center, so it can be stretched without distorting its border
+///
+/// the corners keep their size, the edges are stretched along one axis and the center along both.
+/// written as `{ uv_rect: [0, 0, 0.5, 0.5], size: [48, 48], border: [16, 16, 16, 16] }` in yaml files, the border is left, right, top, bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(from = "NineSliceData")]
+pub struct NineSlice {
+    /// part of the texture that contains the sprite
+    pub uv_rect: Vec4,
+    /// size of the sprite in texture pixels
+    pub size: Vec2,
+    /// in texture pixels
+    pub border: Edges,
+    /// ui units per texture pixel of the border
+    pub scale: f32,
+}
+
+impl NineSlice {
+    pub fn new(uv_rect: Vec4, size: Vec2, border: Edges) -> Self {
+        Self { uv_rect, size, border, scale: 1.0 }
+    }
+
+    /// the nine parts that cover `rect` with their uv rects - parts without an area are skipped
+    pub fn slices(&self, rect: UiRect) -> Vec<(UiRect, Vec4)> {
+        let border = self.border.size() * self.scale;
+        // borders that don't fit are shrunk
+        let fit = (rect.size / border.max(Vec2::splat(f32::EPSILON))).min(Vec2::ONE);
+        let top_left = self.border.top_left() * self.scale * fit;
+        let bottom_right = self.border.bottom_right() * self.scale * fit;
+
+        let xs = [rect.pos.x, rect.pos.x + top_left.x, rect.max().x - bottom_right.x, rect.max().x];
+        let ys = [rect.pos.y, rect.pos.y + top_left.y, rect.max().y - bottom_right.y, rect.max().y];
+
+        let uv_pos = self.uv_rect.truncate().truncate();
+        let uv_size = glam::vec2(self.uv_rect.z, self.uv_rect.w);
+        let size = self.size.max(Vec2::ONE);
+        let uv_min = uv_pos + self.border.top_left() / size * uv_size;
+        let uv_max = uv_pos + uv_size - self.border.bottom_right() / size * uv_size;
+        let us = [uv_pos.x, uv_min.x, uv_max.x, uv_pos.x + uv_size.x];
+        let vs = [uv_pos.y, uv_min.y, uv_max.y, uv_pos.y + uv_size.y];
+
+        let mut result = Vec::with_capacity(9);
+        for row in 0..3 {
+            for col in 0..3 {
+                let part = UiRect::new(glam::vec2(xs[col], ys[row]), glam::vec2(xs[col + 1] - xs[col], ys[row + 1] - ys[row]));
+                if part.size.x <= 0.0 || part.size.y <= 0.0 {
+                    continue;
+                }
+
+                let uv_rect = glam::vec4(us[col], vs[row], us[col + 1] - us[col], vs[row + 1] - vs[row]);
+                result.push((part, uv_rect));
+            }
+        }
+
+        result
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NineSliceData {
+    #[serde(default = "NineSliceData::full_uv_rect")]
+    uv_rect: [f32; 4],
+    size: [f32; 2],
+    border: [f32; 4],
+    #[serde(default = "NineSliceData::default_scale")]
+    scale: f32,
+}
+
+impl NineSliceData {
+    fn full_uv_rect() -> [f32; 4] {
+        [0.0, 0.0, 1.0, 1.0]
+    }
+
+    fn default_scale() -> f32 {
+        1.0
+    }
+}
+
+impl From<NineSliceData> for NineSlice {
+    fn from(val: NineSliceData) -> Self {
+        let [left, right, top, bottom] = val.border;
+
+        Self {
+            uv_rect: Vec4::from_array(val.uv_rect),
+            size: Vec2::from_array(val.size),
+            border: Edges { left, right, top, bottom },
+            scale: val.scale,
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slices() {
+        let slice = NineSlice::new(glam::vec4(0.5, 0.0, 0.5, 0.5), glam::vec2(32.0, 32.0), Edges::all(8.0));
+        let parts = slice.slices(UiRect::new(glam::vec2(10.0, 20.0), glam::vec2(100.0, 50.0)));
+
+        assert_eq!(parts.len(), 9);
+        // corners keep their size, the center is stretched
+        assert_eq!(parts[0], (UiRect::new(glam::vec2(10.0, 20.0), glam::vec2(8.0, 8.0)), glam::vec4(0.5, 0.0, 0.125, 0.125)));
+        assert_eq!(parts[4], (UiRect::new(glam::vec2(18.0, 28.0), glam::vec2(84.0, 34.0)), glam::vec4(0.625, 0.125, 0.25, 0.25)));
+        assert_eq!(parts[8].0, UiRect::new(glam::vec2(102.0, 62.0), glam::vec2(8.0, 8.0)));
+
+        // the border is shrunk when the rect is too small and the empty center is skipped
+        let parts = slice.slices(UiRect::new(Vec2::ZERO, glam::vec2(8.0, 100.0)));
+        assert_eq!(parts.len(), 6);
+        assert_eq!(parts[0].0.size, glam::vec2(4.0, 8.0));
+    }
+
+    #[test]
+    fn test_slice_from_yaml() {
+        let slice: NineSlice = serde_yaml::from_str("{ size: [48, 48], border: [16, 16, 8, 8], scale: 0.5 }").unwrap();
+        assert_eq!(slice.uv_rect, glam::vec4(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(slice.border, Edges::xy(16.0, 8.0));
+        assert_eq!(slice.scale, 0.5);
+    }
+}
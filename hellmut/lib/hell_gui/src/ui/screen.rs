@@ -0,0 +1,182 @@
+use glam::Vec2;
+use hell_common::transform::Transform;
+use hell_common::window::HellWindowExtent;
+
+use super::UiRect;
+
+
+
+// ----------------------------------------------------------------------------
+// anchor
+// ----------------------------------------------------------------------------
+
+/// point of a rect that stays in place when the screen changes its size
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// position of the anchor inside of a rect, from (0, 0) at the top left to (1, 1) at the bottom right
+    pub fn factor(self) -> Vec2 {
+        match self {
+            Anchor::TopLeft     => glam::vec2(0.0, 0.0),
+            Anchor::Top         => glam::vec2(0.5, 0.0),
+            Anchor::TopRight    => glam::vec2(1.0, 0.0),
+            Anchor::Left        => glam::vec2(0.0, 0.5),
+            Anchor::Center      => glam::vec2(0.5, 0.5),
+            Anchor::Right       => glam::vec2(1.0, 0.5),
+            Anchor::BottomLeft  => glam::vec2(0.0, 1.0),
+            Anchor::Bottom      => glam::vec2(0.5, 1.0),
+            Anchor::BottomRight => glam::vec2(1.0, 1.0),
+        }
+    }
+
+    pub fn point(self, rect: UiRect) -> Vec2 {
+        rect.pos + rect.size * self.factor()
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// screen
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UiScaleMode {
+    /// ui units are logical pixels - the ui keeps its physical size on every screen
+    Dpi,
+    /// the ui is scaled so that this size fits into the window - the ui covers the same part of every screen
+    Reference(Vec2),
+}
+
+/// maps ui units to the physical pixels of the window
+///
+/// the ui render pass works in physical pixels with the origin at the top left and y going up,
+/// `root_transform` converts ui units to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiScreen {
+    pub mode: UiScaleMode,
+    /// applied on top of the mode, e.g. from the settings of the player
+    pub user_scale: f32,
+    physical_size: Vec2,
+    scale_factor: f32,
+}
+
+impl UiScreen {
+    pub fn new(mode: UiScaleMode, window_extent: HellWindowExtent) -> Self {
+        let mut result = Self {
+            mode,
+            user_scale: 1.0,
+            physical_size: Vec2::ONE,
+            scale_factor: 1.0,
+        };
+        result.set_window_extent(window_extent);
+        result
+    }
+
+    pub fn set_window_extent(&mut self, window_extent: HellWindowExtent) {
+        self.physical_size = glam::vec2(window_extent.width as f32, window_extent.height as f32).max(Vec2::ONE);
+        self.scale_factor = window_extent.scale_factor.max(f32::EPSILON);
+    }
+
+    pub fn physical_size(&self) -> Vec2 {
+        self.physical_size
+    }
+
+    /// physical pixels per ui unit
+    pub fn scale(&self) -> f32 {
+        let scale = match self.mode {
+            UiScaleMode::Dpi => self.scale_factor,
+            UiScaleMode::Reference(size) => {
+                let fit = self.physical_size / size.max(Vec2::ONE);
+                fit.x.min(fit.y)
+            }
+        };
+
+        (scale * self.user_scale).max(f32::EPSILON)
+    }
+
+    /// size of the screen in ui units
+    pub fn size(&self) -> Vec2 {
+        self.physical_size / self.scale()
+    }
+
+    pub fn rect(&self) -> UiRect {
+        UiRect::new(Vec2::ZERO, self.size())
+    }
+
+    /// e.g. for the cursor position
+    pub fn to_ui(&self, physical: Vec2) -> Vec2 {
+        physical / self.scale()
+    }
+
+    pub fn anchor_point(&self, anchor: Anchor) -> Vec2 {
+        anchor.point(self.rect())
+    }
+
+    /// rect of `size` whose `anchor` is placed at the same anchor of the screen, moved by `offset`
+    pub fn anchored_rect(&self, anchor: Anchor, offset: Vec2, size: Vec2) -> UiRect {
+        let pos = self.anchor_point(anchor) + offset - size * anchor.factor();
+        UiRect::new(pos, size)
+    }
+
+    /// parent transform for ui render data
+    pub fn root_transform(&self) -> Transform {
+        let scale = self.scale();
+        Transform::new(glam::Vec3::ZERO, glam::Quat::IDENTITY, glam::vec3(scale, scale, 1.0))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extent(width: u32, height: u32, scale_factor: f32) -> HellWindowExtent {
+        HellWindowExtent { width, height, scale_factor }
+    }
+
+    #[test]
+    fn test_reference_scale() {
+        let reference = UiScaleMode::Reference(glam::vec2(800.0, 600.0));
+        let small = UiScreen::new(reference, extent(800, 600, 1.0));
+        let large = UiScreen::new(reference, extent(3840, 2160, 2.0));
+
+        assert_eq!(small.scale(), 1.0);
+        assert_eq!(large.scale(), 3.6);
+        assert_eq!(large.size().y, 600.0);
+
+        // the hud covers the same part of the screen
+        let hud = |s: &UiScreen| {
+            let rect = s.anchored_rect(Anchor::BottomRight, glam::vec2(-10.0, -10.0), glam::vec2(100.0, 50.0));
+            (rect.pos * s.scale() / s.physical_size(), rect.size.y * s.scale() / s.physical_size().y)
+        };
+        assert!((hud(&small).1 - hud(&large).1).abs() < 1e-5);
+        assert_eq!(small.anchored_rect(Anchor::BottomRight, glam::vec2(-10.0, -10.0), glam::vec2(100.0, 50.0)).pos, glam::vec2(690.0, 540.0));
+        assert_eq!(large.to_ui(glam::vec2(1920.0, 1080.0)), large.anchor_point(Anchor::Center));
+    }
+
+    #[test]
+    fn test_dpi_scale() {
+        let mut screen = UiScreen::new(UiScaleMode::Dpi, extent(1600, 1200, 2.0));
+        assert_eq!(screen.size(), glam::vec2(800.0, 600.0));
+
+        screen.user_scale = 0.5;
+        assert_eq!(screen.size(), glam::vec2(1600.0, 1200.0));
+        assert_eq!(screen.root_transform().scale, glam::vec3(1.0, 1.0, 1.0));
+    }
+}
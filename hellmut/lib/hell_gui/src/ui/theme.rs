@@ -0,0 +1,172 @@
+use std::{fs, path::Path};
+
+use glam::Vec4;
+use hell_core::error::HellResult;
+use serde::{Deserialize, Deserializer};
+
+use crate::text::parse_color;
+use super::NineSlice;
+
+
+
+// ----------------------------------------------------------------------------
+// widget style
+// ----------------------------------------------------------------------------
+
+/// colors of a widget in its different states
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WidgetStyle {
+    #[serde(deserialize_with = "de_color")]
+    pub background: Vec4,
+    #[serde(deserialize_with = "de_color")]
+    pub hovered: Vec4,
+    /// while the widget is pressed
+    #[serde(deserialize_with = "de_color")]
+    pub active: Vec4,
+    #[serde(deserialize_with = "de_color")]
+    pub text: Vec4,
+    /// drawn from the skin of the `Ui` and tinted with the state color instead of a solid rect
+    pub slice: Option<NineSlice>,
+}
+
+impl Default for WidgetStyle {
+    fn default() -> Self {
+        Self {
+            background: glam::vec4(0.20, 0.20, 0.24, 1.0),
+            hovered:    glam::vec4(0.28, 0.28, 0.34, 1.0),
+            active:     glam::vec4(0.14, 0.14, 0.17, 1.0),
+            text:       Vec4::ONE,
+            slice:      None,
+        }
+    }
+}
+
+impl WidgetStyle {
+    pub fn color(&self, is_hovered: bool, is_active: bool) -> Vec4 {
+        match (is_hovered, is_active) {
+            (_, true)     => self.active,
+            (true, false) => self.hovered,
+            _             => self.background,
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// theme
+// ----------------------------------------------------------------------------
+
+/// sizes are in ui units, colors can be written as names or hex codes in yaml files (see `parse_color`)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiTheme {
+    /// line height of all text
+    pub text_size: f32,
+    /// between the border of a panel and its content
+    pub padding: f32,
+    /// between two widgets
+    pub spacing: f32,
+    pub widget_height: f32,
+    /// width of the outline around the focused widget
+    pub focus_width: f32,
+    #[serde(deserialize_with = "de_color")]
+    pub focus_color: Vec4,
+    /// checkmarks, slider fills and the text cursor
+    #[serde(deserialize_with = "de_color")]
+    pub accent_color: Vec4,
+
+    pub panel: WidgetStyle,
+    pub label: WidgetStyle,
+    pub button: WidgetStyle,
+    /// checkbox boxes, slider tracks and text fields
+    pub field: WidgetStyle,
+}
+
+impl Default for UiTheme {
+    fn default() -> Self {
+        Self {
+            text_size: 24.0,
+            padding: 12.0,
+            spacing: 8.0,
+            widget_height: 36.0,
+            focus_width: 2.0,
+            focus_color: glam::vec4(1.0, 0.8, 0.2, 1.0),
+            accent_color: glam::vec4(0.35, 0.6, 1.0, 1.0),
+
+            panel: WidgetStyle {
+                background: glam::vec4(0.08, 0.08, 0.1, 0.9),
+                hovered:    glam::vec4(0.08, 0.08, 0.1, 0.9),
+                active:     glam::vec4(0.08, 0.08, 0.1, 0.9),
+                text:       Vec4::ONE,
+                slice:      None,
+            },
+            label: WidgetStyle {
+                background: Vec4::ZERO,
+                hovered:    Vec4::ZERO,
+                active:     Vec4::ZERO,
+                text:       Vec4::ONE,
+                slice:      None,
+            },
+            button: WidgetStyle::default(),
+            field: WidgetStyle {
+                background: glam::vec4(0.12, 0.12, 0.14, 1.0),
+                hovered:    glam::vec4(0.16, 0.16, 0.19, 1.0),
+                active:     glam::vec4(0.10, 0.10, 0.12, 1.0),
+                text:       Vec4::ONE,
+                slice:      None,
+            },
+        }
+    }
+}
+
+impl UiTheme {
+    pub fn from_file(path: impl AsRef<Path>) -> HellResult<Self> {
+        let raw = fs::read_to_string(path)?;
+        Self::from_yaml(&raw)
+    }
+
+    pub fn from_yaml(raw: &str) -> HellResult<Self> {
+        let theme: Self = serde_yaml::from_str(raw)?;
+        Ok(theme)
+    }
+}
+
+fn de_color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec4, D::Error> {
+    let raw = String::deserialize(deserializer)?;
+    parse_color(&raw).ok_or_else(|| serde::de::Error::custom(format!("invalid color '{raw}'")))
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::ui::Edges;
+
+    use super::*;
+
+    #[test]
+    fn test_theme_from_yaml() {
+        let theme = UiTheme::from_yaml("
+            text_size: 18
+            focus_color: '#ff0000'
+            button:
+              background: black
+              slice: { size: [32, 32], border: [8, 8, 8, 8] }
+        ").unwrap();
+
+        assert_eq!(theme.text_size, 18.0);
+        assert_eq!(theme.focus_color, glam::vec4(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(theme.button.background, glam::vec4(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(theme.button.hovered, WidgetStyle::default().hovered);
+        assert_eq!(theme.button.slice.map(|s| s.border), Some(Edges::all(8.0)));
+        assert_eq!(theme.panel.slice, None);
+        assert_eq!(theme.padding, UiTheme::default().padding);
+
+        assert!(UiTheme::from_yaml("accent_color: not-a-color").is_err());
+        assert!(UiTheme::from_yaml("unknown: 1").is_err());
+    }
+}
@@ -1,7 +1,10 @@
 use core::fmt;
 
+use glam::Vec2;
 use hell_core::error::HellResult;
+use hell_core::logging::target;
 use strum::EnumCount;
+use tracing::trace;
 use crate::keycodes::KeyCode;
 
 
@@ -51,22 +54,38 @@ bitflags::bitflags! {
     }
 }
 
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, EnumCount)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
 
 
 
 pub struct InputManager {
     modifier_states: ModifiersState,
     key_states: [KeyState; KeyCode::COUNT],
+    mouse_states: [KeyState; MouseButton::COUNT],
+    /// in window pixels, `None` while the cursor is outside of the window
+    cursor_pos: Option<Vec2>,
+    typed_text: String,
 }
 
 impl InputManager {
     pub fn new() -> Self {
         let modifier_states = ModifiersState::from_bits(0).unwrap();
         let key_states = [KeyState::NeverUsed; KeyCode::COUNT];
+        let mouse_states = [KeyState::NeverUsed; MouseButton::COUNT];
 
         Self {
             modifier_states,
             key_states,
+            mouse_states,
+            cursor_pos: None,
+            typed_text: String::new(),
         }
     }
 
@@ -78,7 +97,7 @@ impl InputManager {
             (_, s) => s,
         };
 
-        println!("INPUT: udpate key state: {:?} => {:?}", keycode, new_state);
+        trace!(target: target::INPUT, "update key state: {:?} => {:?}", keycode, new_state);
 
         *state = new_state;
 
@@ -95,11 +114,58 @@ impl InputManager {
 
     pub fn reset_released_keys(&mut self) {
         self.key_states.iter_mut()
+            .chain(self.mouse_states.iter_mut())
             .filter(|s| **s == KeyState::Released)
             .for_each(|s| *s = KeyState::Inactive);
     }
 }
 
+// mouse
+// -----
+impl InputManager {
+    pub fn update_mouse_button_state(&mut self, button: MouseButton, new_state: KeyState) {
+        let state = &mut self.mouse_states[button as usize];
+        *state = match (*state, new_state) {
+            (KeyState::Pressed | KeyState::Held, KeyState::Pressed) => KeyState::Held,
+            (_, s) => s,
+        };
+
+        trace!(target: target::INPUT, "update mouse button state: {:?} => {:?}", button, *state);
+    }
+
+    pub fn mouse_button_state(&self, button: MouseButton) -> KeyState {
+        self.mouse_states[button as usize]
+    }
+
+    pub fn update_cursor_pos(&mut self, pos: Option<Vec2>) {
+        self.cursor_pos = pos;
+    }
+
+    pub fn cursor_pos(&self) -> Option<Vec2> {
+        self.cursor_pos
+    }
+}
+
+// text
+// ----
+impl InputManager {
+    /// control characters are ignored
+    pub fn push_typed_char(&mut self, c: char) {
+        if !c.is_control() {
+            self.typed_text.push(c);
+        }
+    }
+
+    /// everything that was typed since the last call to `clear_typed_text`
+    pub fn typed_text(&self) -> &str {
+        &self.typed_text
+    }
+
+    pub fn clear_typed_text(&mut self) {
+        self.typed_text.clear();
+    }
+}
+
 
 impl Default for InputManager {
     fn default() -> Self {
@@ -97,6 +97,7 @@ pub enum KeyCode {
     Escape,
     Return,
     Backspace,
+    Tab,
 }
 
 impl TryFrom<u32> for KeyCode {
@@ -7,5 +7,5 @@
 mod input;
 mod keycodes;
 
-pub use input::{InputManager, KeyState};
+pub use input::{InputManager, KeyState, MouseButton};
 pub use keycodes::KeyCode;
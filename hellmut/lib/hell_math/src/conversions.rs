@@ -0,0 +1,112 @@
+use crate::{Mat2, Mat3, Mat4, Quat, Vec2, Vec3, Vec4, IVec4, UVec4};
+
+
+
+// ----------------------------------------------------------------------------
+// glam conversions
+// ----------------------------------------------------------------------------
+
+macro_rules! impl_glam_vec {
+    ($vec:ident <=> $glam:ty : { $($field:ident),+ }) => {
+        impl From<$glam> for $vec {
+            fn from(val: $glam) -> Self {
+                Self::new($(val.$field),+)
+            }
+        }
+
+        impl From<$vec> for $glam {
+            fn from(val: $vec) -> Self {
+                <$glam>::new($(val.$field),+)
+            }
+        }
+    };
+}
+
+macro_rules! impl_glam_mat {
+    ($mat:ident <=> $glam:ty : { $($field:ident : $glam_field:ident),+ }) => {
+        impl From<$glam> for $mat {
+            fn from(val: $glam) -> Self {
+                Self::from_cols($(val.$glam_field.into()),+)
+            }
+        }
+
+        impl From<$mat> for $glam {
+            fn from(val: $mat) -> Self {
+                <$glam>::from_cols($(val.$field.into()),+)
+            }
+        }
+    };
+}
+
+impl_glam_vec!(Vec2  <=> glam::Vec2:  { x, y });
+impl_glam_vec!(Vec3  <=> glam::Vec3:  { x, y, z });
+impl_glam_vec!(Vec4  <=> glam::Vec4:  { x, y, z, w });
+impl_glam_vec!(IVec4 <=> glam::IVec4: { x, y, z, w });
+impl_glam_vec!(UVec4 <=> glam::UVec4: { x, y, z, w });
+
+impl_glam_mat!(Mat2 <=> glam::Mat2: { x: x_axis, y: y_axis });
+impl_glam_mat!(Mat3 <=> glam::Mat3: { x: x_axis, y: y_axis, z: z_axis });
+impl_glam_mat!(Mat4 <=> glam::Mat4: { x: x_axis, y: y_axis, z: z_axis, w: w_axis });
+
+impl From<glam::Quat> for Quat {
+    fn from(val: glam::Quat) -> Self {
+        Self::from_xyzw(val.x, val.y, val.z, val.w)
+    }
+}
+
+impl From<Quat> for glam::Quat {
+    fn from(val: Quat) -> Self {
+        glam::Quat::from_xyzw(val.x, val.y, val.z, val.w)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    pub fn scalar() -> impl Strategy<Value = f32> {
+        -100.0_f32..100.0
+    }
+
+    pub fn vec3() -> impl Strategy<Value = glam::Vec3> {
+        (scalar(), scalar(), scalar()).prop_map(|(x, y, z)| glam::vec3(x, y, z))
+    }
+
+    pub fn vec4() -> impl Strategy<Value = glam::Vec4> {
+        (scalar(), scalar(), scalar(), scalar()).prop_map(|(x, y, z, w)| glam::vec4(x, y, z, w))
+    }
+
+    pub fn quat() -> impl Strategy<Value = glam::Quat> {
+        (vec3(), -10.0_f32..10.0).prop_map(|(axis, angle)| {
+            glam::Quat::from_axis_angle(axis.try_normalize().unwrap_or(glam::Vec3::X), angle)
+        })
+    }
+
+    pub fn mat4() -> impl Strategy<Value = glam::Mat4> {
+        (vec4(), vec4(), vec4(), vec4()).prop_map(|(x, y, z, w)| glam::Mat4::from_cols(x, y, z, w))
+    }
+
+    proptest! {
+        #[test]
+        fn test_round_trip(v in vec4(), q in quat(), m in mat4()) {
+            prop_assert_eq!(glam::Vec4::from(Vec4::from(v)), v);
+            prop_assert_eq!(glam::Vec3::from(Vec3::from(v.truncate())), v.truncate());
+            prop_assert_eq!(glam::Vec2::from(Vec2::from(v.truncate().truncate())), v.truncate().truncate());
+            prop_assert_eq!(glam::Quat::from(Quat::from(q)), q);
+            prop_assert_eq!(glam::Mat4::from(Mat4::from(m)), m);
+
+            let m3 = glam::Mat3::from_mat4(m);
+            prop_assert_eq!(glam::Mat3::from(Mat3::from(m3)), m3);
+            let m2 = glam::Mat2::from_cols(m.x_axis.truncate().truncate(), m.y_axis.truncate().truncate());
+            prop_assert_eq!(glam::Mat2::from(Mat2::from(m2)), m2);
+
+            let i = glam::ivec4(v.x as i32, v.y as i32, v.z as i32, v.w as i32);
+            prop_assert_eq!(glam::IVec4::from(IVec4::from(i)), i);
+        }
+    }
+}
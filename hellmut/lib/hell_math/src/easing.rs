@@ -0,0 +1,181 @@
+use std::f32::consts::PI;
+
+
+
+// ----------------------------------------------------------------------------
+// interpolation
+// ----------------------------------------------------------------------------
+
+pub fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// the `t` for which `lerp(a, b, t)` returns `val`, `a` and `b` must differ
+pub fn inverse_lerp(a: f32, b: f32, val: f32) -> f32 {
+    (val - a) / (b - a)
+}
+
+/// maps `val` from the range [`from_min`, `from_max`] to [`to_min`, `to_max`] without clamping
+pub fn remap(val: f32, from_min: f32, from_max: f32, to_min: f32, to_max: f32) -> f32 {
+    lerp(to_min, to_max, inverse_lerp(from_min, from_max, val))
+}
+
+pub fn remap_clamped(val: f32, from_min: f32, from_max: f32, to_min: f32, to_max: f32) -> f32 {
+    lerp(to_min, to_max, inverse_lerp(from_min, from_max, val).clamp(0.0, 1.0))
+}
+
+/// hermite interpolation from 0 to 1 between both edges
+pub fn smoothstep(edge0: f32, edge1: f32, val: f32) -> f32 {
+    let t = inverse_lerp(edge0, edge1, val).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// like `smoothstep` but with zero first and second derivatives at the edges
+pub fn smootherstep(edge0: f32, edge1: f32, val: f32) -> f32 {
+    let t = inverse_lerp(edge0, edge1, val).clamp(0.0, 1.0);
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+
+
+// ----------------------------------------------------------------------------
+// easing
+// ----------------------------------------------------------------------------
+
+/// maps the progress of an animation in [0, 1] to its eased value - 0 and 1 are kept, back and elastic overshoot in between
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticOut,
+    BounceOut,
+}
+
+impl Easing {
+    /// `t` is clamped to [0, 1]
+    pub fn apply(self, t: f32) -> f32 {
+        const BACK: f32 = 1.70158;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear     => t,
+            Easing::QuadIn     => t * t,
+            Easing::QuadOut    => 1.0 - (1.0 - t).powi(2),
+            Easing::QuadInOut  => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+            Easing::CubicIn    => t * t * t,
+            Easing::CubicOut   => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 },
+            Easing::SineIn     => 1.0 - (t * PI / 2.0).cos(),
+            Easing::SineOut    => (t * PI / 2.0).sin(),
+            Easing::SineInOut  => -((PI * t).cos() - 1.0) / 2.0,
+            Easing::ExpoIn     => if t == 0.0 { 0.0 } else { 2_f32.powf(10.0 * t - 10.0) },
+            Easing::ExpoOut    => if t == 1.0 { 1.0 } else { 1.0 - 2_f32.powf(-10.0 * t) },
+            Easing::ExpoInOut  => match t {
+                _ if t == 0.0 || t == 1.0 => t,
+                _ if t < 0.5 => 2_f32.powf(20.0 * t - 10.0) / 2.0,
+                _            => (2.0 - 2_f32.powf(-20.0 * t + 10.0)) / 2.0,
+            },
+            Easing::BackIn     => (BACK + 1.0) * t * t * t - BACK * t * t,
+            Easing::BackOut    => 1.0 + (BACK + 1.0) * (t - 1.0).powi(3) + BACK * (t - 1.0).powi(2),
+            Easing::BackInOut  => if t < 0.5 {
+                (2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT) / 2.0
+            } else {
+                ((2.0 * t - 2.0).powi(2) * ((BACK_IN_OUT + 1.0) * (t * 2.0 - 2.0) + BACK_IN_OUT) + 2.0) / 2.0
+            },
+            Easing::ElasticOut => match t {
+                _ if t == 0.0 || t == 1.0 => t,
+                _ => 2_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (2.0 * PI / 3.0)).sin() + 1.0,
+            },
+            Easing::BounceOut  => bounce_out(t),
+        }
+    }
+
+    /// eases between `a` and `b`
+    pub fn interpolate(self, a: f32, b: f32, t: f32) -> f32 {
+        lerp(a, b, self.apply(t))
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 18] = [
+        Easing::Linear, Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut, Easing::CubicIn, Easing::CubicOut,
+        Easing::CubicInOut, Easing::SineIn, Easing::SineOut, Easing::SineInOut, Easing::ExpoIn, Easing::ExpoOut,
+        Easing::ExpoInOut, Easing::BackIn, Easing::BackOut, Easing::BackInOut, Easing::ElasticOut, Easing::BounceOut,
+    ];
+
+    #[test]
+    fn test_easing_end_points() {
+        for e in ALL {
+            assert!(e.apply(0.0).abs() < 1e-5, "{e:?}: {}", e.apply(0.0));
+            assert!((e.apply(1.0) - 1.0).abs() < 1e-5, "{e:?}: {}", e.apply(1.0));
+            assert_eq!(e.apply(-1.0), e.apply(0.0));
+            assert_eq!(e.apply(2.0), e.apply(1.0));
+        }
+
+        // the in-out variants are symmetric around the center
+        for e in [Easing::QuadInOut, Easing::CubicInOut, Easing::SineInOut, Easing::ExpoInOut, Easing::BackInOut] {
+            assert!((e.apply(0.5) - 0.5).abs() < 1e-5, "{e:?}");
+            assert!((e.apply(0.2) + e.apply(0.8) - 1.0).abs() < 1e-5, "{e:?}");
+        }
+
+        assert!(Easing::BackIn.apply(0.2) < 0.0);
+        assert!(Easing::QuadIn.apply(0.5) < 0.5 && Easing::QuadOut.apply(0.5) > 0.5);
+        assert_eq!(Easing::Linear.interpolate(10.0, 20.0, 0.25), 12.5);
+    }
+
+    #[test]
+    fn test_interpolation() {
+        assert_eq!(inverse_lerp(10.0, 20.0, 15.0), 0.5);
+        assert_eq!(remap(5.0, 0.0, 10.0, 100.0, 200.0), 150.0);
+        assert_eq!(remap(20.0, 0.0, 10.0, 100.0, 200.0), 300.0);
+        assert_eq!(remap_clamped(20.0, 0.0, 10.0, 100.0, 200.0), 200.0);
+
+        assert_eq!(smoothstep(0.0, 2.0, -1.0), 0.0);
+        assert_eq!(smoothstep(0.0, 2.0, 1.0), 0.5);
+        assert_eq!(smoothstep(0.0, 2.0, 3.0), 1.0);
+        assert!(smoothstep(0.0, 1.0, 0.1) < 0.1);
+        assert_eq!(smootherstep(0.0, 2.0, 1.0), 0.5);
+        assert!(smootherstep(0.0, 1.0, 0.1) < smoothstep(0.0, 1.0, 0.1));
+    }
+}
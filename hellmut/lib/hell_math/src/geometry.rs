@@ -0,0 +1,264 @@
+use crate::Vec2;
+
+
+
+// ----------------------------------------------------------------------------
+// rect
+// ----------------------------------------------------------------------------
+
+/// axis aligned, `min` is the corner with the smallest coordinates
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Rect {
+    pub const fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_pos_size(pos: Vec2, size: Vec2) -> Self {
+        Self::new(pos, pos + size)
+    }
+
+    pub fn from_center_size(center: Vec2, size: Vec2) -> Self {
+        let half = size * 0.5;
+        Self::new(center - half, center + half)
+    }
+
+    /// the rect around both points, in any order
+    pub fn from_corners(a: Vec2, b: Vec2) -> Self {
+        Self::new(
+            Vec2::new(a.x.min(b.x), a.y.min(b.y)),
+            Vec2::new(a.x.max(b.x), a.y.max(b.y)),
+        )
+    }
+}
+
+impl Rect {
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.max - self.min
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn area(&self) -> f32 {
+        self.width() * self.height()
+    }
+
+    /// points on the border are inside
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.contains_point(other.min) && self.contains_point(other.max)
+    }
+
+    /// touching rects intersect
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.x <= other.max.x && self.max.x >= other.min.x && self.min.y <= other.max.y && self.max.y >= other.min.y
+    }
+
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        self.intersects(other).then(|| Rect::new(
+            Vec2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            Vec2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        ))
+    }
+
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::new(
+            Vec2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Vec2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /// grows every side by `amount`, negative values shrink the rect
+    pub fn expand(&self, amount: f32) -> Rect {
+        Rect::new(self.min - amount, self.max + amount)
+    }
+
+    pub fn translate(&self, offset: Vec2) -> Rect {
+        Rect::new(self.min + offset, self.max + offset)
+    }
+
+    pub fn closest_point(&self, p: Vec2) -> Vec2 {
+        Vec2::new(p.x.clamp(self.min.x, self.max.x), p.y.clamp(self.min.y, self.max.y))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// circle
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub const fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::from_center_size(self.center, Vec2::splat(self.radius * 2.0))
+    }
+
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        (p - self.center).mag_sq() <= self.radius * self.radius
+    }
+
+    pub fn intersects(&self, other: &Circle) -> bool {
+        let radius = self.radius + other.radius;
+        (other.center - self.center).mag_sq() <= radius * radius
+    }
+
+    pub fn intersects_rect(&self, rect: &Rect) -> bool {
+        self.contains_point(rect.closest_point(self.center))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// line
+// ----------------------------------------------------------------------------
+
+/// line segment between `start` and `end`
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Line {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl Line {
+    const EPSILON: f32 = 1e-6;
+
+    pub const fn new(start: Vec2, end: Vec2) -> Self {
+        Self { start, end }
+    }
+
+    pub fn delta(&self) -> Vec2 {
+        self.end - self.start
+    }
+
+    pub fn length(&self) -> f32 {
+        self.delta().mag()
+    }
+
+    /// `t` = 0 is the start, `t` = 1 the end
+    pub fn at(&self, t: f32) -> Vec2 {
+        self.start.lerp(self.end, t)
+    }
+
+    /// fraction of the point on the segment that is closest to `p`
+    pub fn closest_fraction(&self, p: Vec2) -> f32 {
+        let delta = self.delta();
+        let len_sq = delta.mag_sq();
+        if len_sq <= Self::EPSILON {
+            return 0.0;
+        }
+
+        ((p - self.start).dot(delta) / len_sq).clamp(0.0, 1.0)
+    }
+
+    pub fn closest_point(&self, p: Vec2) -> Vec2 {
+        self.at(self.closest_fraction(p))
+    }
+
+    pub fn distance_to_point(&self, p: Vec2) -> f32 {
+        self.closest_point(p).distance(p)
+    }
+
+    /// the point where both segments cross, parallel segments never intersect
+    pub fn intersection(&self, other: &Line) -> Option<Vec2> {
+        let d0 = self.delta();
+        let d1 = other.delta();
+        let denom = cross(d0, d1);
+        if denom.abs() <= Self::EPSILON {
+            return None;
+        }
+
+        let offset = other.start - self.start;
+        let t = cross(offset, d1) / denom;
+        let u = cross(offset, d0) / denom;
+
+        ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then(|| self.at(t))
+    }
+}
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect() {
+        let a = Rect::from_pos_size(Vec2::ZERO, Vec2::new(4.0, 2.0));
+        let b = Rect::from_center_size(Vec2::new(4.0, 2.0), Vec2::new(2.0, 2.0));
+
+        assert_eq!(a.center(), Vec2::new(2.0, 1.0));
+        assert_eq!(a.area(), 8.0);
+        assert!(a.contains_point(Vec2::new(4.0, 2.0)));
+        assert!(!a.contains_point(Vec2::new(4.1, 2.0)));
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(Vec2::new(3.0, 1.0), Vec2::new(4.0, 2.0))));
+        assert_eq!(a.union(&b), Rect::new(Vec2::ZERO, Vec2::new(5.0, 3.0)));
+        assert!(a.intersection(&b.translate(Vec2::new(5.0, 0.0))).is_none());
+        assert!(a.expand(1.0).contains_rect(&a));
+        assert_eq!(Rect::from_corners(Vec2::new(4.0, 0.0), Vec2::new(0.0, 2.0)), a);
+    }
+
+    #[test]
+    fn test_circle() {
+        let c = Circle::new(Vec2::ZERO, 1.0);
+
+        assert!(c.contains_point(Vec2::new(0.0, 1.0)));
+        assert!(!c.contains_point(Vec2::new(0.8, 0.8)));
+        assert!(c.intersects(&Circle::new(Vec2::new(1.5, 0.0), 0.5)));
+        assert!(!c.intersects(&Circle::new(Vec2::new(1.5, 0.0), 0.4)));
+
+        let rect = Rect::new(Vec2::new(0.8, 0.8), Vec2::new(2.0, 2.0));
+        assert!(!c.intersects_rect(&rect));
+        assert!(c.intersects_rect(&rect.translate(Vec2::new(-0.2, -0.2))));
+        assert_eq!(c.bounds(), Rect::new(Vec2::splat(-1.0), Vec2::ONE));
+    }
+
+    #[test]
+    fn test_line() {
+        let a = Line::new(Vec2::ZERO, Vec2::new(4.0, 0.0));
+        let b = Line::new(Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0));
+
+        assert_eq!(a.length(), 4.0);
+        assert_eq!(a.closest_point(Vec2::new(2.0, 3.0)), Vec2::new(2.0, 0.0));
+        assert_eq!(a.distance_to_point(Vec2::new(7.0, 4.0)), 5.0);
+        assert_eq!(a.intersection(&b), Some(Vec2::new(1.0, 0.0)));
+
+        // parallel and out of reach
+        assert!(a.intersection(&Line::new(Vec2::new(0.0, 1.0), Vec2::new(4.0, 1.0))).is_none());
+        assert!(a.intersection(&Line::new(Vec2::new(5.0, -1.0), Vec2::new(5.0, 1.0))).is_none());
+    }
+}
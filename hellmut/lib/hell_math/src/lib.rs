@@ -6,7 +6,15 @@
 
 mod vec;
 mod mat;
+mod quat;
+mod conversions;
+
+pub mod geometry;
+pub mod easing;
+pub mod random;
+pub mod noise;
 
 
 pub use vec::{Vec2, Vec3, Vec4, IVec4, UVec4};
-pub use mat::Mat4;
+pub use mat::{Mat2, Mat3, Mat4};
+pub use quat::Quat;
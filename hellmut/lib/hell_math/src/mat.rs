@@ -1,7 +1,7 @@
 use std::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Neg, Index, IndexMut};
 
-use crate::vec::Vec3;
-use crate::Vec4;
+use crate::vec::{Vec2, Vec3};
+use crate::{Quat, Vec4};
 
 
 
@@ -9,6 +9,7 @@ use crate::Vec4;
 macro_rules! impl_matrix_common {
     ($mat:ident : $vec:ident : $scalar:ident => $mat_size:literal : { $($field_idx:literal : $field:ident),+ }) => {
         #[repr(C)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
         pub struct $mat {
             $(pub $field: $vec),+
         }
@@ -29,6 +30,18 @@ macro_rules! impl_matrix_common {
                     $($field),+
                 }
             }
+
+            pub fn from_diagonal(diagonal: $vec) -> Self {
+                Self::from_cols(
+                    $($vec::with_one_at($field_idx).mul(diagonal[$field_idx])),+
+                )
+            }
+        }
+
+        impl Default for $mat {
+            fn default() -> Self {
+                Self::IDENTITY
+            }
         }
 
         // add operations
@@ -124,10 +137,7 @@ macro_rules! impl_matrix_common {
             type Output = $vec;
             fn index(&self, index: usize) -> &Self::Output {
                 match index {
-                    0 => &self.x,
-                    1 => &self.y,
-                    2 => &self.z,
-                    3 => &self.w,
+                    $($field_idx => &self.$field,)+
                     _ => panic!("index out of bounds!"),
                 }
             }
@@ -136,15 +146,30 @@ macro_rules! impl_matrix_common {
         impl IndexMut<usize> for $mat {
             fn index_mut(&mut self, index: usize) -> &mut Self::Output {
                 match index {
-                    0 => &mut self.x,
-                    1 => &mut self.y,
-                    2 => &mut self.z,
-                    3 => &mut self.w,
+                    $($field_idx => &mut self.$field,)+
                     _ => panic!("index out of bounds!"),
                 }
             }
         }
 
+        // additional operations
+        // ---------------------
+        impl $mat {
+            pub fn transpose(&self) -> Self {
+                let mut result = *self;
+                for col in 0..$mat_size {
+                    for row in 0..$mat_size {
+                        result[col][row] = self[row][col];
+                    }
+                }
+                result
+            }
+
+            pub fn abs_diff_eq(&self, rhs: Self, max_abs_diff: $scalar) -> bool {
+                true $(&& self.$field.abs_diff_eq(rhs.$field, max_abs_diff))+
+            }
+        }
+
     };
 }
 
@@ -163,8 +188,80 @@ macro_rules! impl_matrix_signed{
     };
 }
 
-impl_matrix_common!(Mat4: Vec4: f32 => 2: { 0:x, 1:y, 2:z, 3:w });
-impl_matrix_signed!(Mat4: Vec4: f32 => 2: { 0:x, 1:y, 2:z, 3:w });
+impl_matrix_common!(Mat2: Vec2: f32 => 2: { 0:x, 1:y });
+impl_matrix_signed!(Mat2: Vec2: f32 => 2: { 0:x, 1:y });
+
+impl_matrix_common!(Mat3: Vec3: f32 => 3: { 0:x, 1:y, 2:z });
+impl_matrix_signed!(Mat3: Vec3: f32 => 3: { 0:x, 1:y, 2:z });
+
+impl_matrix_common!(Mat4: Vec4: f32 => 4: { 0:x, 1:y, 2:z, 3:w });
+impl_matrix_signed!(Mat4: Vec4: f32 => 4: { 0:x, 1:y, 2:z, 3:w });
+
+
+
+// ----------------------------------------------------------------------------
+// mat2
+// ----------------------------------------------------------------------------
+
+impl Mat2 {
+    pub fn from_angle(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::from_cols(Vec2::new(cos, sin), Vec2::new(-sin, cos))
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.x.x * self.y.y - self.y.x * self.x.y
+    }
+
+    pub fn inverse(self) -> Self {
+        let rcp_det = self.determinant().recip();
+        Self::from_cols(
+            Vec2::new(self.y.y, -self.x.y),
+            Vec2::new(-self.y.x, self.x.x),
+        ) * rcp_det
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// mat3
+// ----------------------------------------------------------------------------
+
+impl Mat3 {
+    pub fn from_quat(rotation: Quat) -> Self {
+        let Quat { x, y, z, w } = rotation;
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx, xy, xz) = (x * x2, x * y2, x * z2);
+        let (yy, yz, zz) = (y * y2, y * z2, z * z2);
+        let (wx, wy, wz) = (w * x2, w * y2, w * z2);
+
+        Self::from_cols(
+            Vec3::new(1.0 - (yy + zz), xy + wz, xz - wy),
+            Vec3::new(xy - wz, 1.0 - (xx + zz), yz + wx),
+            Vec3::new(xz + wy, yz - wx, 1.0 - (xx + yy)),
+        )
+    }
+
+    pub fn determinant(&self) -> f32 {
+        self.z.dot(self.x.cross(&self.y))
+    }
+
+    pub fn inverse(self) -> Self {
+        let tmp0 = self.y.cross(&self.z);
+        let tmp1 = self.z.cross(&self.x);
+        let tmp2 = self.x.cross(&self.y);
+        let rcp_det = self.z.dot(tmp2).recip();
+
+        Self::from_cols(tmp0 * rcp_det, tmp1 * rcp_det, tmp2 * rcp_det).transpose()
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// mat4
+// ----------------------------------------------------------------------------
 
 
 impl Mat4 {
@@ -238,21 +335,23 @@ impl Mat4 {
         let rcp_det = dot1.recip();
         inverse.mul(rcp_det)
     }
+
+    pub fn transform_point3(&self, p: Vec3) -> Vec3 {
+        self.mul_vec(&p.extend(1.0)).truncate()
+    }
+
+    pub fn transform_vector3(&self, v: Vec3) -> Vec3 {
+        self.mul_vec(&v.extend(0.0)).truncate()
+    }
 }
 
 // oder: Mt * Mr * Ms * V
 impl Mat4 {
-    // TODO:
+    /// scales the first three columns, so the scale is applied before the existing transformation
     pub fn scale(mut self, val: &[f32; 3]) -> Self {
-        // self[0][0] *= val[0];
-        // self[1][1] *= val[1];
-        // self[2][2] *= val[2];
-        self.x[0] *= val[0];
-        self.y[1] *= val[1];
-        self.y[2] *= val[2];
-        // self[0] *= val[0];
-        // self[1].y *= val[1];
-        // self[2].z *= val[2];
+        self.x *= val[0];
+        self.y *= val[1];
+        self.z *= val[2];
         self
     }
 
@@ -284,16 +383,37 @@ impl Mat4 {
 
         let w = Vec4::new(0.0, 0.0, 0.0, 1.0);
 
-        let scale_mat = Mat4::from_cols(x, y, z, w);
-        self * scale_mat
+        let rotation_mat = Mat4::from_cols(x, y, z, w);
+        self * rotation_mat
     }
 
+    /// the translation is added in world space, after the existing transformation
     pub fn translate(mut self, val: &Vec3) -> Self {
         let val = Vec4::new(val.x, val.y, val.z, 0.0);
         self.w += val;
         self
     }
 
+    pub fn from_quat(rotation: Quat) -> Self {
+        let rot = Mat3::from_quat(rotation);
+        Self::from_cols(
+            rot.x.extend(0.0),
+            rot.y.extend(0.0),
+            rot.z.extend(0.0),
+            Vec4::new(0.0, 0.0, 0.0, 1.0),
+        )
+    }
+
+    pub fn from_scale_rotation_translation(scale: Vec3, rotation: Quat, translation: Vec3) -> Self {
+        let rot = Mat3::from_quat(rotation);
+        Self::from_cols(
+            (rot.x * scale.x).extend(0.0),
+            (rot.y * scale.y).extend(0.0),
+            (rot.z * scale.z).extend(0.0),
+            translation.extend(1.0),
+        )
+    }
+
     pub fn from_scale(val: &[f32; 3]) -> Self {
         Self::IDENTITY.scale(val)
     }
@@ -349,6 +469,7 @@ impl Mat4 {
         Self::from_cols(x, y, z, w)
     }
 
+    /// `vertical_fov` is in degrees - y points down and the depth is reversed, so the near plane ends up at 1
     pub fn from_perspective_rh(vertical_fov: f32, aspect_ratio: f32, z_near: f32, z_far: f32) -> Self {
         let fov_rad = vertical_fov * 2.0 * std::f32::consts::PI / 360.0;
         let focal_length = 1.0 / (fov_rad / 2.0).tan();
@@ -387,8 +508,9 @@ impl Mat4 {
 
 impl Mat4 {
     pub fn look_at_rh(pos: &Vec3, direction: &Vec3, up: &Vec3) -> Self {
+        let direction = direction.unit();
         let cam_right = direction.cross(up).unit();
-        let cam_up = cam_right.cross(direction);
+        let cam_up = cam_right.cross(&direction);
 
         let cam_dir = direction.neg();
 
@@ -404,3 +526,92 @@ impl Mat4 {
         (translation * rotation).inverse()
     }
 }
+
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversions::tests::{mat4, quat, vec3, vec4};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_mat4_ops(a in mat4(), b in mat4(), v in vec4()) {
+            let (ha, hb) = (Mat4::from(a), Mat4::from(b));
+
+            prop_assert!((ha * hb).abs_diff_eq((a * b).into(), 1e-1));
+            prop_assert!((ha * Vec4::from(v)).abs_diff_eq((a * v).into(), 1e-1));
+            prop_assert!((ha + hb).abs_diff_eq((a + b).into(), 1e-4));
+            prop_assert!(ha.transpose().abs_diff_eq(a.transpose().into(), 0.0));
+        }
+
+        #[test]
+        fn test_mat4_inverse(s in vec3(), r in quat(), t in vec3()) {
+            prop_assume!(s.abs().min_element() > 0.1);
+            let m = glam::Mat4::from_scale_rotation_translation(s, r, t);
+            let hm = Mat4::from_scale_rotation_translation(s.into(), r.into(), t.into());
+
+            prop_assert!(hm.abs_diff_eq(m.into(), 1e-3));
+            prop_assert!(hm.inverse().abs_diff_eq(m.inverse().into(), 1e-2));
+            prop_assert!((hm.inverse() * hm).abs_diff_eq(Mat4::IDENTITY, 1e-3));
+            prop_assert!(hm.transform_point3(t.into()).abs_diff_eq(m.transform_point3(t).into(), 1e-1));
+            prop_assert!(hm.transform_vector3(t.into()).abs_diff_eq(m.transform_vector3(t).into(), 1e-1));
+        }
+
+        #[test]
+        fn test_mat3_mat2(a in mat4()) {
+            let m3 = glam::Mat3::from_mat4(a);
+            prop_assume!(m3.determinant().abs() > 1.0);
+            let hm3 = Mat3::from(m3);
+            prop_assert!((hm3.determinant() - m3.determinant()).abs() <= m3.determinant().abs() * 1e-3);
+            prop_assert!((hm3.inverse() * hm3).abs_diff_eq(Mat3::IDENTITY, 1e-2));
+            prop_assert!(hm3.inverse().abs_diff_eq(m3.inverse().into(), 1e-3));
+
+            let m2 = glam::Mat2::from_cols(a.x_axis.truncate().truncate(), a.y_axis.truncate().truncate());
+            prop_assume!(m2.determinant().abs() > 1.0);
+            let hm2 = Mat2::from(m2);
+            prop_assert!((hm2.determinant() - m2.determinant()).abs() < 1e-1);
+            prop_assert!(hm2.inverse().abs_diff_eq(m2.inverse().into(), 1e-3));
+        }
+    }
+
+    #[test]
+    fn test_builders() {
+        let axis = Vec3::new(0.0, 0.0, 1.0);
+        let m = Mat4::from_translate(&Vec3::new(1.0, 2.0, 3.0))
+            .rotate(&axis, 0.5)
+            .scale(&[2.0, 3.0, 4.0]);
+        let expected = glam::Mat4::from_scale_rotation_translation(
+            glam::vec3(2.0, 3.0, 4.0),
+            glam::Quat::from_rotation_z(0.5),
+            glam::vec3(1.0, 2.0, 3.0),
+        );
+        assert!(m.abs_diff_eq(expected.into(), 1e-5));
+        assert!(Mat4::from_diagonal(Vec4::ONE).abs_diff_eq(Mat4::IDENTITY, 0.0));
+    }
+
+    #[test]
+    fn test_look_at() {
+        let pos = glam::vec3(1.0, 2.0, 3.0);
+        let dir = glam::vec3(0.5, -1.0, -2.0);
+        let m = Mat4::look_at_rh(&pos.into(), &dir.into(), &Vec3::new(0.0, 1.0, 0.0));
+        assert!(m.abs_diff_eq(glam::Mat4::look_to_rh(pos, dir, glam::Vec3::Y).into(), 1e-5));
+    }
+
+    #[test]
+    fn test_perspective() {
+        // glam maps the near plane to 0 and keeps y up
+        let glam_mat = glam::Mat4::perspective_rh(60_f32.to_radians(), 1.5, 0.1, 100.0);
+        let flip_y = glam::Mat4::from_scale(glam::vec3(1.0, -1.0, 1.0));
+        // z = w - z
+        let reverse_z = glam::Mat4::from_cols(glam::Vec4::X, glam::Vec4::Y, -glam::Vec4::Z, glam::Vec4::Z + glam::Vec4::W);
+        let m = Mat4::from_perspective_rh(60.0, 1.5, 0.1, 100.0);
+
+        let expected = reverse_z * flip_y * glam_mat;
+        assert!(m.abs_diff_eq(expected.into(), 1e-5), "{m:?} != {expected:?}");
+    }
+}
@@ -0,0 +1,246 @@
+use std::f32::consts::FRAC_1_SQRT_2 as DIAG;
+
+use crate::easing::lerp;
+use crate::random::Rng;
+
+
+
+// ----------------------------------------------------------------------------
+// value noise
+// ----------------------------------------------------------------------------
+
+/// random values on the integer grid, smoothly interpolated in between - results are in [-1, 1]
+#[derive(Debug, Clone)]
+pub struct ValueNoise {
+    perm: Permutation,
+}
+
+impl ValueNoise {
+    pub fn new(seed: u64) -> Self {
+        Self { perm: Permutation::new(seed) }
+    }
+
+    pub fn sample_1d(&self, x: f32) -> f32 {
+        let x0 = x.floor();
+        let t = fade(x - x0);
+        let x0 = x0 as i32;
+
+        lerp(self.value(x0, 0), self.value(x0 + 1, 0), t)
+    }
+
+    pub fn sample_2d(&self, x: f32, y: f32) -> f32 {
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (fade(x - x0), fade(y - y0));
+        let (x0, y0) = (x0 as i32, y0 as i32);
+
+        let bottom = lerp(self.value(x0, y0), self.value(x0 + 1, y0), tx);
+        let top = lerp(self.value(x0, y0 + 1), self.value(x0 + 1, y0 + 1), tx);
+        lerp(bottom, top, ty)
+    }
+
+    fn value(&self, x: i32, y: i32) -> f32 {
+        self.perm.hash(x, y) as f32 / 127.5 - 1.0
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// simplex noise
+// ----------------------------------------------------------------------------
+
+/// 2d simplex noise - smoother and with less grid artifacts than value noise, results are in [-1, 1]
+#[derive(Debug, Clone)]
+pub struct SimplexNoise {
+    perm: Permutation,
+}
+
+impl SimplexNoise {
+    const F2: f32 = 0.366_025_42; // (sqrt(3) - 1) / 2
+    const G2: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+
+    // scales the sum of the corner contributions to [-1, 1]
+    const SCALE: f32 = 70.0;
+
+    const GRADIENTS: [(f32, f32); 8] = [
+        (1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0),
+        (DIAG, DIAG), (-DIAG, DIAG), (DIAG, -DIAG), (-DIAG, -DIAG),
+    ];
+
+    pub fn new(seed: u64) -> Self {
+        Self { perm: Permutation::new(seed) }
+    }
+
+    pub fn sample_1d(&self, x: f32) -> f32 {
+        self.sample_2d(x, 0.0)
+    }
+
+    pub fn sample_2d(&self, x: f32, y: f32) -> f32 {
+        // skew into the grid of simplices and find the cell
+        let s = (x + y) * Self::F2;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+
+        let t = (i + j) * Self::G2;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+
+        // upper or lower triangle of the cell
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+
+        let x1 = x0 - i1 as f32 + Self::G2;
+        let y1 = y0 - j1 as f32 + Self::G2;
+        let x2 = x0 - 1.0 + 2.0 * Self::G2;
+        let y2 = y0 - 1.0 + 2.0 * Self::G2;
+
+        let (i, j) = (i as i32, j as i32);
+        let n0 = self.corner(i, j, x0, y0);
+        let n1 = self.corner(i + i1, j + j1, x1, y1);
+        let n2 = self.corner(i + 1, j + 1, x2, y2);
+
+        (Self::SCALE * (n0 + n1 + n2)).clamp(-1.0, 1.0)
+    }
+
+    fn corner(&self, i: i32, j: i32, x: f32, y: f32) -> f32 {
+        let t = 0.5 - x * x - y * y;
+        if t <= 0.0 {
+            return 0.0;
+        }
+
+        let (gx, gy) = Self::GRADIENTS[self.perm.hash(i, j) as usize % Self::GRADIENTS.len()];
+        let t2 = t * t;
+        t2 * t2 * (gx * x + gy * y)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// fractal noise
+// ----------------------------------------------------------------------------
+
+/// sums octaves of `sample` with rising frequency and falling amplitude - the result stays in the range of `sample`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fbm {
+    pub octaves: u32,
+    /// frequency multiplier per octave
+    pub lacunarity: f32,
+    /// amplitude multiplier per octave
+    pub gain: f32,
+}
+
+impl Default for Fbm {
+    fn default() -> Self {
+        Self {
+            octaves: 4,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl Fbm {
+    pub fn sample_2d(&self, x: f32, y: f32, sample: impl Fn(f32, f32) -> f32) -> f32 {
+        let mut sum = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut total_amplitude = 0.0;
+
+        for _ in 0..self.octaves {
+            sum += sample(x * frequency, y * frequency) * amplitude;
+            total_amplitude += amplitude;
+            amplitude *= self.gain;
+            frequency *= self.lacunarity;
+        }
+
+        if total_amplitude > 0.0 { sum / total_amplitude } else { 0.0 }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// helpers
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct Permutation {
+    table: [u8; 256],
+}
+
+impl Permutation {
+    fn new(seed: u64) -> Self {
+        let mut table = [0; 256];
+        for (idx, val) in table.iter_mut().enumerate() {
+            *val = idx as u8;
+        }
+        Rng::new(seed).shuffle(&mut table);
+
+        Self { table }
+    }
+
+    fn hash(&self, x: i32, y: i32) -> u8 {
+        let x = self.table[(x & 255) as usize];
+        self.table[((y & 255) as u8 ^ x) as usize]
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(f: impl Fn(f32, f32) -> f32) -> Vec<f32> {
+        (0..400).map(|i| f(i as f32 * 0.137, (i / 20) as f32 * 0.291)).collect()
+    }
+
+    #[test]
+    fn test_value_noise() {
+        let noise = ValueNoise::new(3);
+        let values = samples(|x, y| noise.sample_2d(x, y));
+
+        assert!(values.iter().all(|v| (-1.0..=1.0).contains(v)));
+        assert_eq!(values, samples(|x, y| ValueNoise::new(3).sample_2d(x, y)));
+        assert_ne!(values, samples(|x, y| ValueNoise::new(4).sample_2d(x, y)));
+
+        // continuous and equal to the lattice values on the grid
+        assert!((noise.sample_1d(2.0) - noise.sample_1d(2.001)).abs() < 1e-2);
+        assert_eq!(noise.sample_1d(5.0), noise.sample_2d(5.0, 0.0));
+    }
+
+    #[test]
+    fn test_simplex_noise() {
+        let noise = SimplexNoise::new(11);
+        let values = samples(|x, y| noise.sample_2d(x, y));
+
+        assert!(values.iter().all(|v| (-1.0..=1.0).contains(v)));
+        assert_eq!(noise.sample_2d(0.0, 0.0), 0.0);
+        assert!((noise.sample_2d(3.3, 1.2) - noise.sample_2d(3.301, 1.2)).abs() < 1e-2);
+
+        // roughly centered and not flat
+        let mean = values.iter().sum::<f32>() / values.len() as f32;
+        let max = values.iter().fold(0.0_f32, |m, v| m.max(v.abs()));
+        assert!(mean.abs() < 0.2, "{mean}");
+        assert!(max > 0.3, "{max}");
+    }
+
+    #[test]
+    fn test_fbm() {
+        let noise = SimplexNoise::new(5);
+        let fbm = Fbm::default();
+
+        let values = samples(|x, y| fbm.sample_2d(x, y, |x, y| noise.sample_2d(x, y)));
+        assert!(values.iter().all(|v| (-1.0..=1.0).contains(v)));
+
+        let single = Fbm { octaves: 1, ..fbm };
+        assert_eq!(single.sample_2d(1.3, 0.7, |x, y| noise.sample_2d(x, y)), noise.sample_2d(1.3, 0.7));
+        assert_eq!(Fbm { octaves: 0, ..fbm }.sample_2d(1.0, 1.0, |_, _| 1.0), 0.0);
+    }
+}
@@ -0,0 +1,254 @@
+use std::ops::{Mul, MulAssign, Neg};
+
+use crate::{Mat3, Vec3, Vec4};
+
+
+
+// ----------------------------------------------------------------------------
+// quat
+// ----------------------------------------------------------------------------
+
+/// rotation, `w` is the scalar part
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Quat {
+    pub const IDENTITY: Quat = Quat::from_xyzw(0.0, 0.0, 0.0, 1.0);
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+// creators
+// --------
+impl Quat {
+    pub const fn from_xyzw(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    pub const fn from_array(val: [f32; 4]) -> Self {
+        Self::from_xyzw(val[0], val[1], val[2], val[3])
+    }
+
+    pub const fn from_vec4(val: Vec4) -> Self {
+        Self::from_xyzw(val.x, val.y, val.z, val.w)
+    }
+
+    /// `axis` has to be normalized
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        let v = axis * sin;
+        Self::from_xyzw(v.x, v.y, v.z, cos)
+    }
+
+    pub fn from_rotation_x(angle: f32) -> Self {
+        Self::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), angle)
+    }
+
+    pub fn from_rotation_y(angle: f32) -> Self {
+        Self::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), angle)
+    }
+
+    pub fn from_rotation_z(angle: f32) -> Self {
+        Self::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), angle)
+    }
+
+    /// `mat` has to be a pure rotation
+    pub fn from_mat3(mat: &Mat3) -> Self {
+        let (m00, m01, m02) = (mat.x.x, mat.x.y, mat.x.z);
+        let (m10, m11, m12) = (mat.y.x, mat.y.y, mat.y.z);
+        let (m20, m21, m22) = (mat.z.x, mat.z.y, mat.z.z);
+
+        // pick the largest component to stay numerically stable
+        let result = if m22 <= 0.0 {
+            let dif10 = m11 - m00;
+            let omm22 = 1.0 - m22;
+            if dif10 <= 0.0 {
+                let four_xsq = omm22 - dif10;
+                Self::from_xyzw(four_xsq, m01 + m10, m02 + m20, m12 - m21).mul_scalar(0.5 / four_xsq.sqrt())
+            } else {
+                let four_ysq = omm22 + dif10;
+                Self::from_xyzw(m01 + m10, four_ysq, m12 + m21, m20 - m02).mul_scalar(0.5 / four_ysq.sqrt())
+            }
+        } else {
+            let sum10 = m11 + m00;
+            let opm22 = 1.0 + m22;
+            if sum10 <= 0.0 {
+                let four_zsq = opm22 - sum10;
+                Self::from_xyzw(m02 + m20, m12 + m21, four_zsq, m01 - m10).mul_scalar(0.5 / four_zsq.sqrt())
+            } else {
+                let four_wsq = opm22 + sum10;
+                Self::from_xyzw(m12 - m21, m20 - m02, m01 - m10, four_wsq).mul_scalar(0.5 / four_wsq.sqrt())
+            }
+        };
+
+        result.normalize()
+    }
+}
+
+// conversions
+// -----------
+impl Quat {
+    pub const fn to_array(&self) -> [f32; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
+    pub const fn to_vec4(&self) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, self.w)
+    }
+
+    pub const fn xyz(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+}
+
+// operations
+// ----------
+impl Quat {
+    pub fn dot(&self, rhs: Self) -> f32 {
+        self.to_vec4().dot(rhs.to_vec4())
+    }
+
+    pub fn length(&self) -> f32 {
+        self.dot(*self).sqrt()
+    }
+
+    pub fn normalize(self) -> Self {
+        self.mul_scalar(self.length().recip())
+    }
+
+    pub fn conjugate(self) -> Self {
+        Self::from_xyzw(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// same as `conjugate` for normalized quaternions
+    pub fn inverse(self) -> Self {
+        self.conjugate().mul_scalar(self.dot(self).recip())
+    }
+
+    pub fn mul_quat(&self, rhs: &Self) -> Self {
+        let (x0, y0, z0, w0) = (self.x, self.y, self.z, self.w);
+        let (x1, y1, z1, w1) = (rhs.x, rhs.y, rhs.z, rhs.w);
+
+        Self::from_xyzw(
+            w0 * x1 + x0 * w1 + y0 * z1 - z0 * y1,
+            w0 * y1 - x0 * z1 + y0 * w1 + z0 * x1,
+            w0 * z1 + x0 * y1 - y0 * x1 + z0 * w1,
+            w0 * w1 - x0 * x1 - y0 * y1 - z0 * z1,
+        )
+    }
+
+    pub fn mul_vec3(&self, rhs: Vec3) -> Vec3 {
+        let q = self.xyz();
+        let t = q.cross(&rhs) * 2.0;
+        rhs + t * self.w + q.cross(&t)
+    }
+
+    fn mul_scalar(self, rhs: f32) -> Self {
+        Self::from_vec4(self.to_vec4() * rhs)
+    }
+
+    /// spherical interpolation along the shorter arc, both quaternions have to be normalized
+    pub fn slerp(self, mut end: Self, t: f32) -> Self {
+        const DOT_THRESHOLD: f32 = 0.9995;
+
+        let mut dot = self.dot(end);
+        if dot < 0.0 {
+            end = -end;
+            dot = -dot;
+        }
+
+        // the quaternions are almost the same, lerp to avoid dividing by ~0
+        if dot > DOT_THRESHOLD {
+            return Self::from_vec4(self.to_vec4().lerp(end.to_vec4(), t)).normalize();
+        }
+
+        let theta = dot.acos();
+        let scale_start = (theta * (1.0 - t)).sin();
+        let scale_end = (theta * t).sin();
+        let rcp_sin = theta.sin().recip();
+
+        Self::from_vec4((self.to_vec4() * scale_start + end.to_vec4() * scale_end) * rcp_sin)
+    }
+
+    pub fn abs_diff_eq(&self, rhs: Self, max_abs_diff: f32) -> bool {
+        self.to_vec4().abs_diff_eq(rhs.to_vec4(), max_abs_diff)
+    }
+}
+
+// operators
+// ---------
+impl Mul<Self> for Quat {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.mul_quat(&rhs)
+    }
+}
+
+impl MulAssign<Self> for Quat {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.mul_quat(&rhs);
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+    fn mul(self, rhs: Vec3) -> Self::Output {
+        self.mul_vec3(rhs)
+    }
+}
+
+impl Neg for Quat {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self::from_xyzw(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversions::tests::{quat, vec3};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_ops(a in quat(), b in quat(), v in vec3(), t in 0.0_f32..1.0) {
+            let (ha, hb) = (Quat::from(a), Quat::from(b));
+
+            prop_assert!((ha * hb).abs_diff_eq((a * b).into(), 1e-5));
+            prop_assert!((ha * Vec3::from(v)).abs_diff_eq((a * v).into(), 1e-3));
+            prop_assert!(ha.inverse().abs_diff_eq(a.inverse().into(), 1e-5));
+            prop_assert!(Mat3::from_quat(ha).abs_diff_eq(glam::Mat3::from_quat(a).into(), 1e-5));
+
+            // q and -q are the same rotation
+            let back = Quat::from_mat3(&Mat3::from_quat(ha));
+            prop_assert!(back.abs_diff_eq(ha, 1e-3) || back.abs_diff_eq(-ha, 1e-3));
+
+            let slerp = glam::Quat::from(ha.slerp(hb, t));
+            let expected = a.slerp(b, t);
+            prop_assert!((slerp * v).abs_diff_eq(expected * v, 1e-2));
+        }
+    }
+
+    #[test]
+    fn test_axis_angle() {
+        let q = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+        assert!((q * Vec3::new(1.0, 0.0, 0.0)).abs_diff_eq(Vec3::new(0.0, 1.0, 0.0), 1e-6));
+        assert_eq!(Quat::default(), Quat::IDENTITY);
+    }
+}
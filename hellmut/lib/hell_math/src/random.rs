@@ -0,0 +1,260 @@
+use std::ops::Range;
+
+use crate::Vec2;
+
+
+
+// ----------------------------------------------------------------------------
+// rng
+// ----------------------------------------------------------------------------
+
+/// seeded pcg32 generator - the same seed always produces the same sequence on every platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+    inc: u64,
+}
+
+impl Rng {
+    const MULTIPLIER: u64 = 6364136223846793005;
+    const DEFAULT_STREAM: u64 = 1442695040888963407;
+
+    pub fn new(seed: u64) -> Self {
+        Self::with_stream(seed, Self::DEFAULT_STREAM)
+    }
+
+    /// generators with the same seed but different streams produce independent sequences
+    pub fn with_stream(seed: u64, stream: u64) -> Self {
+        let mut rng = Self { state: 0, inc: (stream << 1) | 1 };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(Self::MULTIPLIER).wrapping_add(self.inc);
+
+        let xor_shifted = (((old >> 18) ^ old) >> 27) as u32;
+        let rot = (old >> 59) as u32;
+        xor_shifted.rotate_right(rot)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        ((self.next_u32() as u64) << 32) | self.next_u32() as u64
+    }
+
+    /// in [0, 1)
+    pub fn next_f32(&mut self) -> f32 {
+        // the upper 24 bits fit exactly into the mantissa
+        (self.next_u32() >> 8) as f32 * (1.0 / (1u32 << 24) as f32)
+    }
+
+    pub fn next_bool(&mut self) -> bool {
+        self.next_u32() & 1 == 1
+    }
+}
+
+// ranges
+// ------
+impl Rng {
+    /// in [`range.start`, `range.end`)
+    pub fn range_f32(&mut self, range: Range<f32>) -> f32 {
+        let val = range.start + (range.end - range.start) * self.next_f32();
+
+        // rounding can land exactly on the excluded end
+        if val >= range.end && range.start < range.end {
+            return range.end.next_down();
+        }
+        val
+    }
+
+    /// in [`range.start`, `range.end`), an empty range returns `range.start`
+    pub fn range_u32(&mut self, range: Range<u32>) -> u32 {
+        let span = range.end.saturating_sub(range.start);
+        if span == 0 {
+            return range.start;
+        }
+
+        // reject the values that would make the lower numbers more likely
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let val = self.next_u32();
+            if val >= threshold {
+                return range.start + val % span;
+            }
+        }
+    }
+
+    /// in [`range.start`, `range.end`), an empty range returns `range.start`
+    pub fn range_u64(&mut self, range: Range<u64>) -> u64 {
+        let span = range.end.saturating_sub(range.start);
+        if span == 0 {
+            return range.start;
+        }
+
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let val = self.next_u64();
+            if val >= threshold {
+                return range.start + val % span;
+            }
+        }
+    }
+
+    pub fn range_i32(&mut self, range: Range<i32>) -> i32 {
+        if range.end <= range.start {
+            return range.start;
+        }
+
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add(self.range_u32(0..span) as i32)
+    }
+
+    pub fn range_usize(&mut self, range: Range<usize>) -> usize {
+        let span = range.end.saturating_sub(range.start);
+        match u32::try_from(span) {
+            Ok(span) => range.start + self.range_u32(0..span) as usize,
+            Err(_)   => range.start + self.range_u64(0..span as u64) as usize,
+        }
+    }
+
+    /// true with the probability `p` in [0, 1]
+    pub fn chance(&mut self, p: f32) -> bool {
+        self.next_f32() < p
+    }
+
+    /// point on the unit circle
+    pub fn unit_vec2(&mut self) -> Vec2 {
+        let angle = self.range_f32(0.0..std::f32::consts::TAU);
+        Vec2::new(angle.cos(), angle.sin())
+    }
+}
+
+// collections
+// -----------
+impl Rng {
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+
+        Some(&items[self.range_usize(0..items.len())])
+    }
+
+    /// index of the chosen weight - negative weights count as 0, `None` if no weight is positive
+    pub fn choose_weighted(&mut self, weights: &[f32]) -> Option<usize> {
+        let total: f32 = weights.iter().map(|w| w.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = self.next_f32() * total;
+        let mut last = None;
+        for (idx, w) in weights.iter().enumerate().filter(|(_, w)| **w > 0.0) {
+            if target < *w {
+                return Some(idx);
+            }
+            target -= w;
+            last = Some(idx);
+        }
+
+        // rounding errors can let the target run past the last weight
+        last
+    }
+
+    /// fisher-yates
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range_usize(0..i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reproducible() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        let mut c = Rng::new(43);
+        let mut d = Rng::with_stream(42, 7);
+
+        let seq_a: Vec<_> = (0..16).map(|_| a.next_u32()).collect();
+        let seq_b: Vec<_> = (0..16).map(|_| b.next_u32()).collect();
+        let seq_c: Vec<_> = (0..16).map(|_| c.next_u32()).collect();
+        let seq_d: Vec<_> = (0..16).map(|_| d.next_u32()).collect();
+
+        assert_eq!(seq_a, seq_b);
+        assert_ne!(seq_a, seq_c);
+        assert_ne!(seq_a, seq_d);
+
+        // the reference pcg32 implementation, seeded with 42 and stream 54
+        let mut reference = Rng::with_stream(42, 54);
+        assert_eq!(reference.next_u32(), 0xa15c02b7);
+        assert_eq!(reference.next_u32(), 0x7b47f409);
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut rng = Rng::new(1);
+        let mut counts = [0; 4];
+
+        for _ in 0..4000 {
+            let f = rng.range_f32(-2.0..3.0);
+            assert!((-2.0..3.0).contains(&f));
+
+            let i = rng.range_i32(-5..-1);
+            assert!((-5..-1).contains(&i));
+            counts[(i + 5) as usize] += 1;
+        }
+
+        // roughly uniform
+        assert!(counts.iter().all(|c| (800..1200).contains(c)), "{counts:?}");
+        assert_eq!(rng.range_u32(3..3), 3);
+        assert_eq!(rng.range_i32(-3..-3), -3);
+        assert_eq!(rng.range_u64(3..3), 3);
+
+        // only a single value in between
+        let end = 1.0_f32.next_up();
+        assert!((0..100).all(|_| rng.range_f32(1.0..end) == 1.0));
+
+        let big = u64::MAX - 10;
+        assert!((0..100).all(|_| (big..u64::MAX).contains(&rng.range_u64(big..u64::MAX))));
+        #[cfg(target_pointer_width = "64")]
+        assert!((0..100).any(|_| rng.range_usize(0..1 << 40) > u32::MAX as usize));
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+        assert!((rng.unit_vec2().mag() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_collections() {
+        let mut rng = Rng::new(7);
+
+        assert_eq!(rng.choose::<u8>(&[]), None);
+        assert_eq!(rng.choose(&[5]), Some(&5));
+        assert_eq!(rng.choose_weighted(&[0.0, -1.0]), None);
+
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[rng.choose_weighted(&[1.0, 0.0, 2.0]).unwrap()] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!((800..1200).contains(&counts[0]), "{counts:?}");
+
+        let mut items: Vec<_> = (0..32).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..32).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..32).collect::<Vec<_>>());
+    }
+}
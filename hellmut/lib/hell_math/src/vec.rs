@@ -6,7 +6,8 @@ use std::convert::{AsRef, AsMut};
 #[macro_export]
 macro_rules! impl_vector_common {
     ($vec:ident : $scalar:ident => $vec_size:literal : { $($field_idx:literal : $field:ident),+ }) => {
-        #[derive(Clone, Copy)]
+        #[repr(C)]
+        #[derive(Debug, Default, Clone, Copy, PartialEq)]
         pub struct $vec {
             $(pub $field: $scalar),+
         }
@@ -17,6 +18,9 @@ macro_rules! impl_vector_common {
         impl $vec {
             pub const SCALAR_ZERO: $scalar = 0 as $scalar;
             pub const SCALAR_ONE: $scalar = 1 as $scalar;
+
+            pub const ZERO: Self = Self::splat(Self::SCALAR_ZERO);
+            pub const ONE: Self = Self::splat(Self::SCALAR_ONE);
         }
 
         // creators
@@ -34,6 +38,12 @@ macro_rules! impl_vector_common {
                 }
             }
 
+            pub const fn splat(val: $scalar) -> Self {
+                Self {
+                    $($field: val),+
+                }
+            }
+
             pub const fn from_array(val: [$scalar; $vec_size]) -> Self {
                 Self {
                     $($field: val[$field_idx]),+
@@ -42,10 +52,14 @@ macro_rules! impl_vector_common {
 
             pub fn from_slice(val: &[$scalar]) -> Self {
                 Self {
-                    $($field: val[$field_idx].clone()),+
+                    $($field: val[$field_idx]),+
                 }
             }
 
+            pub const fn to_array(&self) -> [$scalar; $vec_size] {
+                [$(self.$field),+]
+            }
+
             pub const fn with_one_at(idx: usize) -> Self {
                 $(let $field = if $field_idx == idx { Self::SCALAR_ONE } else { Self::SCALAR_ZERO };)+
 
@@ -383,6 +397,23 @@ macro_rules! impl_vector_float {
                 let mag = self.mag();
                 self.div_assign(mag);
             }
+
+            pub fn mag_sq(&self) -> $scalar {
+                self.dot(*self)
+            }
+
+            pub fn distance(&self, rhs: Self) -> $scalar {
+                (*self - rhs).mag()
+            }
+
+            /// `t` = 0 returns `self`, `t` = 1 returns `rhs`
+            pub fn lerp(self, rhs: Self, t: $scalar) -> Self {
+                self + (rhs - self) * t
+            }
+
+            pub fn abs_diff_eq(&self, rhs: Self, max_abs_diff: $scalar) -> bool {
+                true $(&& (self.$field - rhs.$field).abs() <= max_abs_diff)+
+            }
         }
     };
 }
@@ -425,9 +456,66 @@ impl From<Vec4> for (f32, f32, f32, f32) {
 impl Vec3 {
     pub fn cross(&self, rhs: &Self) -> Self {
         Self::new(
-            (self.y * rhs.z) - (self.z - rhs.y),
-            (self.z * rhs.x) - (self.x - rhs.z),
-            (self.x * rhs.y) - (self.y - rhs.x)
+            (self.y * rhs.z) - (self.z * rhs.y),
+            (self.z * rhs.x) - (self.x * rhs.z),
+            (self.x * rhs.y) - (self.y * rhs.x)
         )
     }
+
+    pub const fn extend(self, w: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+}
+
+impl Vec4 {
+    pub const fn truncate(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversions::tests::{scalar, vec3, vec4};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_vec3_ops(a in vec3(), b in vec3(), s in scalar()) {
+            let (ha, hb) = (Vec3::from(a), Vec3::from(b));
+
+            prop_assert!((ha + hb).abs_diff_eq((a + b).into(), 1e-4));
+            prop_assert!((ha - hb).abs_diff_eq((a - b).into(), 1e-4));
+            prop_assert!((ha * s).abs_diff_eq((a * s).into(), 1e-2));
+            prop_assert!((ha * hb).abs_diff_eq((a * b).into(), 1e-2));
+            prop_assert!((-ha).abs_diff_eq((-a).into(), 0.0));
+            prop_assert!((ha.dot(hb) - a.dot(b)).abs() < 1e-1);
+            prop_assert!(ha.cross(&hb).abs_diff_eq(a.cross(b).into(), 1e-1));
+            prop_assert!((ha.mag() - a.length()).abs() < 1e-3);
+            prop_assert!(ha.lerp(hb, 0.3).abs_diff_eq(a.lerp(b, 0.3).into(), 1e-3));
+        }
+
+        #[test]
+        fn test_vec4_ops(a in vec4(), b in vec4()) {
+            let (ha, hb) = (Vec4::from(a), Vec4::from(b));
+
+            prop_assert!((ha + hb).abs_diff_eq((a + b).into(), 1e-4));
+            prop_assert!((ha.dot(hb) - a.dot(b)).abs() < 1e-1);
+            prop_assume!(a.length() > 1e-3);
+            prop_assert!(ha.unit().abs_diff_eq(a.normalize().into(), 1e-5));
+        }
+    }
+
+    #[test]
+    fn test_layout() {
+        let mut v = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(v.as_ref(), &[1.0, 2.0, 3.0]);
+        v.as_mut()[1] = 5.0;
+        assert_eq!(v.to_array(), [1.0, 5.0, 3.0]);
+        assert_eq!(v.extend(4.0).truncate(), v);
+    }
 }
@@ -0,0 +1,50 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use glam::Vec2;
+use hell_physics::collision::fixtures::random_bounds;
+use hell_physics::collision::{brute_force_pairs, SpatialHash, AABB2D};
+
+fn level_bounds(count: usize) -> Vec<AABB2D> {
+    // keep the density roughly constant, like a level that gets longer
+    let extent = (count as f32).sqrt() * 4.0;
+    random_bounds(count, 42, AABB2D::new(Vec2::ZERO, Vec2::splat(extent)), 0.5, 1.5)
+}
+
+fn bench_pairs(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broadphase_pairs");
+
+    for count in [100, 500, 2000] {
+        let bounds = level_bounds(count);
+
+        group.bench_with_input(BenchmarkId::new("brute_force", count), &bounds, |b, bounds| {
+            let mut pairs = Vec::new();
+            b.iter(|| {
+                brute_force_pairs(black_box(bounds), &mut pairs);
+                pairs.len()
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("spatial_hash", count), &bounds, |b, bounds| {
+            let mut hash = SpatialHash::new(2.0);
+            let ids: Vec<_> = bounds.iter().map(|b| hash.insert(*b)).collect();
+            let mut pairs = Vec::new();
+            let mut offset = 0.0_f32;
+
+            // incremental update of every proxy + pair search, like a frame with everything moving
+            b.iter(|| {
+                offset = (offset + 0.05) % 1.0;
+                let delta = glam::vec2(offset, 0.0);
+                for (bounds, id) in bounds.iter().zip(&ids) {
+                    hash.update(*id, AABB2D::new(bounds.min + delta, bounds.max + delta));
+                }
+
+                hash.find_pairs(&mut pairs);
+                pairs.len()
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_pairs);
+criterion_main!(benches);
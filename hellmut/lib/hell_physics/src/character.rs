@@ -0,0 +1,505 @@
+use std::{fs, path::Path};
+
+use glam::{Mat4, Vec2, Vec3};
+use hell_core::error::HellResult;
+
+use crate::collision::{CollisionLayers, Shape2D};
+use crate::world::{PhysicsWorld, QueryFilter, QueryHit};
+
+
+
+// ----------------------------------------------------------------------------
+// config
+// ----------------------------------------------------------------------------
+
+/// everything that defines how a character feels - usually loaded from a yaml file
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct CharacterConfig {
+    pub max_speed: f32,
+    pub ground_acceleration: f32,
+    pub air_acceleration: f32,
+    pub gravity: f32,
+    /// gravity is scaled by this while falling, makes jumps feel less floaty
+    pub fall_gravity_multiplier: f32,
+    /// gravity is scaled by this while rising without holding jump - releasing jump early results in lower jumps
+    pub jump_cut_gravity_multiplier: f32,
+    pub jump_speed: f32,
+    pub max_fall_speed: f32,
+    /// jumping is still possible for this long after walking off a ledge
+    pub coyote_time: f32,
+    /// jumps pressed this long before landing are executed on landing
+    pub jump_buffer_time: f32,
+    /// in degrees, steeper surfaces are walls
+    pub max_slope_angle: f32,
+    /// obstacles up to this height are walked over, the character also sticks to the ground when walking down steps
+    pub step_height: f32,
+    /// gap that is kept between the character and other colliders
+    pub skin_width: f32,
+    pub max_slides: u32,
+}
+
+impl CharacterConfig {
+    pub fn from_file(path: impl AsRef<Path>) -> HellResult<Self> {
+        let raw = fs::read_to_string(path)?;
+        let config: Self = serde_yaml::from_str(&raw)?;
+        Ok(config)
+    }
+
+    /// surfaces with a normal at least this steep are walkable
+    pub fn min_ground_normal_y(&self) -> f32 {
+        self.max_slope_angle.to_radians().cos()
+    }
+}
+
+impl Default for CharacterConfig {
+    fn default() -> Self {
+        Self {
+            max_speed: 6.0,
+            ground_acceleration: 60.0,
+            air_acceleration: 30.0,
+            gravity: -30.0,
+            fall_gravity_multiplier: 1.5,
+            jump_cut_gravity_multiplier: 2.0,
+            jump_speed: 12.0,
+            max_fall_speed: 20.0,
+            coyote_time: 0.1,
+            jump_buffer_time: 0.1,
+            max_slope_angle: 50.0,
+            step_height: 0.3,
+            skin_width: 0.01,
+            max_slides: 4,
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// controller
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CharacterInput {
+    /// in [-1, 1]
+    pub move_x: f32,
+    /// whether the jump button is held - presses are detected by the controller
+    pub jump: bool,
+}
+
+/// kinematic platformer character - it moves by shape casts against the world and is never pushed by it
+pub struct CharacterController {
+    pub config: CharacterConfig,
+    /// local-space collider, relative to `position`
+    pub shape: Shape2D,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// colliders the character moves against, exclude the character's own body here
+    pub filter: QueryFilter,
+    /// colliders in these layers can be passed from below and only block the character from above
+    pub one_way_layers: CollisionLayers,
+
+    is_grounded: bool,
+    ground_normal: Vec2,
+    is_jumping: bool,
+    was_jump_held: bool,
+    coyote_timer: f32,
+    jump_buffer_timer: f32,
+}
+
+impl CharacterController {
+    pub fn new(config: CharacterConfig, shape: impl Into<Shape2D>, position: Vec2) -> Self {
+        Self {
+            config,
+            shape: shape.into(),
+            position,
+            velocity: Vec2::ZERO,
+            filter: QueryFilter::default(),
+            one_way_layers: CollisionLayers::NONE,
+
+            is_grounded: false,
+            ground_normal: Vec2::Y,
+            is_jumping: false,
+            was_jump_held: false,
+            coyote_timer: 0.0,
+            jump_buffer_timer: 0.0,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: QueryFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_one_way_layers(mut self, layers: CollisionLayers) -> Self {
+        self.one_way_layers = layers;
+        self
+    }
+
+    pub fn is_grounded(&self) -> bool {
+        self.is_grounded
+    }
+
+    /// `Vec2::Y` while in the air
+    pub fn ground_normal(&self) -> Vec2 {
+        self.ground_normal
+    }
+
+    /// world-space collider
+    pub fn world_shape(&self) -> Shape2D {
+        self.shape_at(self.position)
+    }
+
+    /// puts the character somewhere else without moving it through the world
+    pub fn teleport(&mut self, position: Vec2) {
+        self.position = position;
+        self.velocity = Vec2::ZERO;
+        self.is_grounded = false;
+        self.is_jumping = false;
+    }
+}
+
+// update
+// ------
+impl CharacterController {
+    const EPSILON: f32 = 1e-8;
+
+    pub fn update(&mut self, world: &PhysicsWorld, input: &CharacterInput, delta_time: f32) {
+        let config = &self.config;
+
+        // timers
+        if self.is_grounded {
+            self.coyote_timer = config.coyote_time;
+        } else {
+            self.coyote_timer -= delta_time;
+        }
+
+        let is_jump_pressed = input.jump && !self.was_jump_held;
+        self.was_jump_held = input.jump;
+        if is_jump_pressed {
+            self.jump_buffer_timer = config.jump_buffer_time;
+        } else {
+            self.jump_buffer_timer -= delta_time;
+        }
+
+        // horizontal
+        let target_speed = input.move_x.clamp(-1.0, 1.0) * config.max_speed;
+        let acceleration = if self.is_grounded { config.ground_acceleration } else { config.air_acceleration };
+        self.velocity.x = move_towards(self.velocity.x, target_speed, acceleration * delta_time);
+
+        // jump
+        let does_jump = self.jump_buffer_timer > 0.0 && self.coyote_timer > 0.0;
+        if does_jump {
+            self.velocity.y = config.jump_speed;
+            self.is_jumping = true;
+            self.is_grounded = false;
+            self.jump_buffer_timer = 0.0;
+            self.coyote_timer = 0.0;
+        }
+
+        // gravity
+        let gravity = if self.velocity.y < 0.0 {
+            config.gravity * config.fall_gravity_multiplier
+        } else if self.is_jumping && !input.jump {
+            config.gravity * config.jump_cut_gravity_multiplier
+        } else {
+            config.gravity
+        };
+        self.velocity.y = (self.velocity.y + gravity * delta_time).max(-config.max_fall_speed);
+        if self.velocity.y <= 0.0 {
+            self.is_jumping = false;
+        }
+
+        self.move_and_slide(world, self.velocity * delta_time, does_jump);
+    }
+
+    /// moves as far as possible and slides along everything that is in the way
+    fn move_and_slide(&mut self, world: &PhysicsWorld, motion: Vec2, did_jump: bool) {
+        let was_grounded = self.is_grounded;
+        let skin = self.config.skin_width;
+        let mut remaining = motion;
+
+        for _ in 0..self.config.max_slides {
+            if remaining.length_squared() <= Self::EPSILON * Self::EPSILON {
+                break;
+            }
+
+            let Some(hit) = self.cast(world, self.position, remaining) else {
+                self.position += remaining;
+                break;
+            };
+
+            self.position += remaining * hit.fraction + hit.normal * skin;
+            remaining *= 1.0 - hit.fraction;
+
+            if self.is_walkable(hit.normal) {
+                // keep the horizontal speed when walking up a slope, instead of getting slowed down by it
+                // walls only count as walkable with a max slope angle of 90 degrees, those just get slid along
+                let tangent = glam::vec2(hit.normal.y, -hit.normal.x);
+                remaining = if tangent.x.abs() > f32::EPSILON {
+                    tangent * (remaining.x / tangent.x)
+                } else {
+                    tangent * remaining.dot(tangent)
+                };
+                self.velocity.y = self.velocity.y.max(0.0);
+                continue;
+            }
+
+            if was_grounded && !did_jump && remaining.x.abs() > Self::EPSILON {
+                if let Some(position) = self.try_step_up(world, remaining.x) {
+                    self.position = position;
+                    remaining = Vec2::ZERO;
+                    continue;
+                }
+            }
+
+            // walls and ceilings
+            remaining -= hit.normal * remaining.dot(hit.normal);
+            let speed = self.velocity.dot(hit.normal);
+            if speed < 0.0 {
+                self.velocity -= hit.normal * speed;
+            }
+        }
+
+        self.update_ground(world, was_grounded && !did_jump);
+    }
+
+    /// lifts the character by the step height, moves it forwards and puts it back down
+    fn try_step_up(&self, world: &PhysicsWorld, forward: f32) -> Option<Vec2> {
+        let skin = self.config.skin_width;
+        let up = Vec2::Y * self.config.step_height;
+        let lifted = match self.cast(world, self.position, up) {
+            Some(hit) => self.position + up * hit.fraction - Vec2::Y * skin,
+            None => self.position + up,
+        };
+
+        let forward = Vec2::X * forward;
+        if self.cast(world, lifted, forward).is_some() {
+            return None;
+        }
+
+        let moved = lifted + forward;
+        let down = -Vec2::Y * (lifted.y - self.position.y + skin);
+        let hit = self.cast(world, moved, down).filter(|hit| hit.fraction > 0.0 && self.is_walkable(hit.normal))?;
+        Some(moved + down * hit.fraction + hit.normal * skin)
+    }
+
+    /// grounded characters stick to the ground when walking down slopes or steps
+    fn update_ground(&mut self, world: &PhysicsWorld, should_snap: bool) {
+        self.is_grounded = false;
+        self.ground_normal = Vec2::Y;
+        if self.velocity.y > 0.0 {
+            return;
+        }
+
+        let skin = self.config.skin_width;
+        let distance = skin * 2.0 + if should_snap { self.config.step_height } else { 0.0 };
+        let down = -Vec2::Y * distance;
+
+        let Some(hit) = self.cast(world, self.position, down).filter(|hit| self.is_walkable(hit.normal)) else {
+            return;
+        };
+
+        self.position += down * hit.fraction + hit.normal * skin;
+        self.velocity.y = 0.0;
+        self.is_grounded = true;
+        self.ground_normal = hit.normal;
+    }
+
+    fn cast(&self, world: &PhysicsWorld, position: Vec2, motion: Vec2) -> Option<QueryHit> {
+        let shape = self.shape_at(position);
+        let solid_filter = QueryFilter { mask: self.filter.mask - self.one_way_layers, ..self.filter };
+        let solid = world.cast_shape(&shape, motion, &solid_filter);
+        if self.one_way_layers.is_empty() || motion.y >= 0.0 {
+            return solid;
+        }
+
+        // one-way platforms only block characters that are completely above them
+        let one_way_filter = QueryFilter { mask: self.filter.mask & self.one_way_layers, ..self.filter };
+        let one_way = world.cast_shape(&shape, motion, &one_way_filter)
+            .filter(|hit| hit.fraction > 0.0 && self.is_walkable(hit.normal));
+
+        match (solid, one_way) {
+            (Some(s), Some(o)) => Some(if o.fraction < s.fraction { o } else { s }),
+            (s, o) => s.or(o),
+        }
+    }
+
+    fn shape_at(&self, position: Vec2) -> Shape2D {
+        self.shape.transform_mat(&Mat4::from_translation(Vec3::from((position, 0.0))))
+    }
+
+    fn is_walkable(&self, normal: Vec2) -> bool {
+        normal.y >= self.config.min_ground_normal_y()
+    }
+}
+
+fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    if (target - current).abs() <= max_delta {
+        target
+    } else {
+        current + (target - current).signum() * max_delta
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{ConvexPolygon, AABB2D};
+    use crate::world::RigidBody;
+    use crate::PhysicsConfig;
+
+    const ONE_WAY: CollisionLayers = CollisionLayers::layer(1);
+    const DT: f32 = 1.0 / 60.0;
+
+    /// flat ground from -20 to 20, a small and a high step on the right, a slope on the left and a one-way platform
+    fn level() -> PhysicsWorld {
+        let mut world = PhysicsWorld::new(PhysicsConfig::new(0.0));
+        let mut add = |body: RigidBody| { world.add_body(body); };
+
+        add(RigidBody::fixed(AABB2D::new(glam::vec2(-20.0, -1.0), glam::vec2(20.0, 0.0))));
+        add(RigidBody::fixed(AABB2D::new(glam::vec2(5.0, 0.0), glam::vec2(8.0, 0.2))));
+        add(RigidBody::fixed(AABB2D::new(glam::vec2(8.0, 0.0), glam::vec2(9.0, 3.0))));
+        add(RigidBody::fixed(ConvexPolygon::new(vec![
+            glam::vec2(-5.0, 0.0), glam::vec2(-10.0, 0.0), glam::vec2(-10.0, 5.0 * 0.5_f32.tan()),
+        ]).unwrap()));
+        add(
+            RigidBody::fixed(AABB2D::new(glam::vec2(-2.0, 2.0), glam::vec2(2.0, 2.2)))
+                .with_layers(ONE_WAY, CollisionLayers::ALL)
+        );
+
+        world
+    }
+
+    fn character(x: f32) -> CharacterController {
+        CharacterController::new(CharacterConfig::default(), AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)), glam::vec2(x, 0.5))
+            .with_one_way_layers(ONE_WAY)
+    }
+
+    fn run(world: &PhysicsWorld, c: &mut CharacterController, input: CharacterInput, seconds: f32) {
+        for _ in 0..(seconds / DT).round() as u32 {
+            c.update(world, &input, DT);
+        }
+    }
+
+    const IDLE: CharacterInput = CharacterInput { move_x: 0.0, jump: false };
+    const JUMP: CharacterInput = CharacterInput { move_x: 0.0, jump: true };
+
+    #[test]
+    fn test_falls_and_lands() {
+        let world = level();
+        let mut c = character(8.5);
+        c.position.y = 10.0;
+
+        let mut max_fall_speed = 0.0_f32;
+        for _ in 0..120 {
+            c.update(&world, &IDLE, DT);
+            max_fall_speed = max_fall_speed.max(-c.velocity.y);
+        }
+
+        // landed on the high step
+        assert!(c.is_grounded());
+        assert!((c.position.y - 3.5).abs() < 0.05, "y: {}", c.position.y);
+        assert_eq!(c.velocity.y, 0.0);
+        assert!(max_fall_speed <= c.config.max_fall_speed + 1e-4);
+    }
+
+    #[test]
+    fn test_jump_buffer_and_coyote_time() {
+        let world = level();
+
+        // jump pressed shortly before landing
+        let mut c = character(0.0);
+        c.position.y = 0.7;
+        c.velocity.y = -3.0;
+        c.update(&world, &JUMP, DT);
+        assert!(!c.is_grounded());
+        run(&world, &mut c, JUMP, 0.05);
+        assert!(c.velocity.y > 0.0, "buffered jump wasn't executed");
+
+        // jump pressed too early
+        let mut c = character(0.0);
+        c.position.y = 3.0;
+        run(&world, &mut c, JUMP, 0.5);
+        assert!(c.is_grounded());
+
+        // walk off the high step, then jump in mid-air
+        let mut c = character(8.5);
+        c.position.y = 3.5;
+        run(&world, &mut c, IDLE, 0.1);
+        assert!(c.is_grounded());
+        run(&world, &mut c, CharacterInput { move_x: 1.0, jump: false }, 0.3);
+        assert!(!c.is_grounded() && c.position.x > 9.5);
+        c.coyote_timer = c.config.coyote_time * 0.5;
+        c.update(&world, &JUMP, DT);
+        assert!(c.velocity.y > 0.0, "coyote jump wasn't executed");
+    }
+
+    #[test]
+    fn test_walls_and_steps() {
+        let world = level();
+        let mut c = character(2.0);
+        run(&world, &mut c, IDLE, 0.1);
+        run(&world, &mut c, CharacterInput { move_x: 1.0, jump: false }, 2.0);
+
+        // walked up the small step, stopped by the high one
+        assert!(c.is_grounded());
+        assert!((c.position.y - 0.7).abs() < 0.05, "y: {}", c.position.y);
+        assert!((c.position.x - 7.5).abs() < 0.05, "x: {}", c.position.x);
+        assert_eq!(c.velocity.x, 0.0);
+    }
+
+    #[test]
+    fn test_slope() {
+        let world = level();
+        let mut c = character(-3.0);
+        run(&world, &mut c, IDLE, 0.1);
+        run(&world, &mut c, CharacterInput { move_x: -1.0, jump: false }, 0.8);
+
+        // walked up the slope without losing contact
+        assert!(c.is_grounded());
+        assert!(c.ground_normal().x > 0.4);
+        assert!(c.position.y > 1.0);
+
+        // and back down
+        run(&world, &mut c, CharacterInput { move_x: 1.0, jump: false }, 1.0);
+        assert!(c.is_grounded());
+        assert!((c.position.y - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_vertical_walkable_wall() {
+        let world = level();
+        let mut c = character(2.0);
+        c.config.max_slope_angle = 90.0;
+        run(&world, &mut c, IDLE, 0.1);
+        run(&world, &mut c, CharacterInput { move_x: 1.0, jump: false }, 2.0);
+
+        assert!(c.position.is_finite() && c.velocity.is_finite(), "position: {}, velocity: {}", c.position, c.velocity);
+        assert!(c.position.x < 7.6, "x: {}", c.position.x);
+    }
+
+    #[test]
+    fn test_one_way_platform() {
+        let world = level();
+        let mut c = character(0.0);
+        run(&world, &mut c, IDLE, 0.1);
+
+        // jumps through the platform from below and lands on top of it
+        run(&world, &mut c, JUMP, 1.5);
+        assert!(c.is_grounded());
+        assert!((c.position.y - 2.7).abs() < 0.05, "y: {}", c.position.y);
+    }
+
+    #[test]
+    fn test_config_from_yaml() {
+        let config: CharacterConfig = serde_yaml::from_str("max_speed: 3.0\ncoyote_time: 0.2\n").unwrap();
+        assert_eq!(config.max_speed, 3.0);
+        assert_eq!(config.coyote_time, 0.2);
+        assert_eq!(config.jump_speed, CharacterConfig::default().jump_speed);
+    }
+}
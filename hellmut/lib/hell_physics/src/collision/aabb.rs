@@ -1,7 +1,7 @@
-use glam::{Vec2, Vec4};
+use glam::{Vec2, Vec3};
 use hell_common::transform::Transform;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct AABB2D {
     pub min: Vec2,
     pub max: Vec2,
@@ -17,17 +17,75 @@ impl Default for AABB2D {
 }
 
 impl AABB2D {
-    pub fn transform(&self, t: &Transform) -> Self {
-        let model = t.create_model_mat();
-        let min = model * Vec4::from((self.min, 0.0, 1.0));
-        let max = model * Vec4::from((self.max, 0.0, 1.0));
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
 
+    pub fn from_center_half_extents(center: Vec2, half_extents: Vec2) -> Self {
+        Self::new(center - half_extents, center + half_extents)
+    }
+
+    /// smallest box that contains all points
+    pub fn from_points(points: impl IntoIterator<Item = Vec2>) -> Self {
+        let mut min = Vec2::splat(f32::MAX);
+        let mut max = Vec2::splat(f32::MIN);
+        for p in points {
+            min = min.min(p);
+            max = max.max(p);
+        }
+
+        Self { min, max }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec2 {
+        (self.max - self.min) * 0.5
+    }
+
+    pub fn corners(&self) -> [Vec2; 4] {
+        [
+            self.min,
+            glam::vec2(self.max.x, self.min.y),
+            self.max,
+            glam::vec2(self.min.x, self.max.y),
+        ]
+    }
+
+    pub fn merge(&self, other: &AABB2D) -> Self {
         Self {
-            min: glam::vec2(min.x, min.y),
-            max: glam::vec2(max.x, max.y),
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
         }
     }
 
+    pub fn translate(&self, offset: Vec2) -> Self {
+        Self::new(self.min + offset, self.max + offset)
+    }
+
+    pub fn expand(&self, margin: f32) -> Self {
+        Self::new(self.min - Vec2::splat(margin), self.max + Vec2::splat(margin))
+    }
+
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        p.cmpge(self.min).all() && p.cmple(self.max).all()
+    }
+
+    /// world-space bounds of the transformed box - all four corners are transformed, so rotations and negative scales are handled
+    pub fn transform(&self, t: &Transform) -> Self {
+        let model = t.create_model_mat();
+        Self::from_points(
+            self.corners()
+                .into_iter()
+                .map(|c| model.transform_point3(Vec3::from((c, 0.0))).truncate())
+        )
+    }
+
     pub fn does_overlap(&self, other: &AABB2D) -> bool {
         for i in 0..2 {
             if (self.max[i] < other.min[i]) ||
@@ -38,3 +96,34 @@ impl AABB2D {
         true
     }
 }
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+
+    #[test]
+    fn test_transform_rotated() {
+        let mut t = Transform::default();
+        t.rotate_around_z(std::f32::consts::FRAC_PI_4);
+        let bounds = AABB2D::default().transform(&t);
+
+        let expected = std::f32::consts::SQRT_2;
+        assert!((bounds.max - Vec2::splat(expected)).length() < 1e-5);
+        assert!((bounds.min + Vec2::splat(expected)).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_transform_negative_scale() {
+        let t = Transform::new(glam::vec3(10.0, 0.0, 0.0), Quat::IDENTITY, glam::vec3(-2.0, 1.0, 1.0));
+        let bounds = AABB2D::new(Vec2::ZERO, Vec2::ONE).transform(&t);
+
+        assert_eq!(bounds.min, glam::vec2(8.0, 0.0));
+        assert_eq!(bounds.max, glam::vec2(10.0, 1.0));
+        assert!(bounds.does_overlap(&AABB2D::from_center_half_extents(glam::vec2(9.0, 0.5), Vec2::splat(0.1))));
+    }
+}
@@ -0,0 +1,491 @@
+use std::collections::HashMap;
+
+use glam::{IVec2, Vec2};
+use hell_common::transform::Transform;
+
+use super::AABB2D;
+
+
+
+// ----------------------------------------------------------------------------
+// proxy
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ProxyId(u32);
+
+impl ProxyId {
+    pub fn idx(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CellRange {
+    min: IVec2,
+    max: IVec2,
+}
+
+impl CellRange {
+    fn cells(self) -> impl Iterator<Item = IVec2> {
+        (self.min.y..=self.max.y).flat_map(move |y| (self.min.x..=self.max.x).map(move |x| IVec2::new(x, y)))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Proxy {
+    bounds: AABB2D,
+    /// `None` for oversized proxies
+    cells: Option<CellRange>,
+}
+
+
+
+// ----------------------------------------------------------------------------
+// spatial hash
+// ----------------------------------------------------------------------------
+
+/// uniform grid broadphase - every proxy is stored in all cells its bounds touch
+///
+/// the cell size should be around the size of a typical collider, bigger colliders simply span multiple cells.
+/// colliders spanning more than `MAX_CELL_SPAN` cells, or with non-finite bounds, are kept out of the grid and tested against everything.
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<ProxyId>>,
+    proxies: Vec<Option<Proxy>>,
+    free: Vec<ProxyId>,
+    oversized: Vec<ProxyId>,
+}
+
+impl SpatialHash {
+    pub const DEFAULT_CELL_SIZE: f32 = 4.0;
+    pub const MAX_CELL_SPAN: i32 = 64;
+    /// keeps cell coordinates far away from overflowing
+    const MAX_CELL_COORD: f32 = (1 << 30) as f32;
+
+    pub fn new(cell_size: f32) -> Self {
+        debug_assert!(cell_size > 0.0);
+
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            proxies: Vec::new(),
+            free: Vec::new(),
+            oversized: Vec::new(),
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.proxies.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.proxies.clear();
+        self.free.clear();
+        self.oversized.clear();
+    }
+
+    pub fn bounds(&self, id: ProxyId) -> Option<&AABB2D> {
+        self.proxies.get(id.idx())?.as_ref().map(|p| &p.bounds)
+    }
+
+    fn cell_of(&self, p: Vec2) -> IVec2 {
+        (p / self.cell_size).floor().as_ivec2()
+    }
+
+    /// `None` if the bounds are too big for the grid
+    fn cell_range(&self, bounds: &AABB2D) -> Option<CellRange> {
+        let min = (bounds.min / self.cell_size).floor();
+        let max = (bounds.max / self.cell_size).floor();
+
+        let is_valid = min.is_finite() && max.is_finite()
+            && min.abs().max(max.abs()).max_element() < Self::MAX_CELL_COORD
+            && (max - min).max_element() < Self::MAX_CELL_SPAN as f32;
+        is_valid.then(|| CellRange { min: min.as_ivec2(), max: max.as_ivec2() })
+    }
+
+    fn add_to_cells(&mut self, id: ProxyId, range: Option<CellRange>) {
+        let Some(range) = range else {
+            self.oversized.push(id);
+            return;
+        };
+
+        for cell in range.cells() {
+            self.cells.entry(cell).or_default().push(id);
+        }
+    }
+
+    fn remove_from_cells(&mut self, id: ProxyId, range: Option<CellRange>) {
+        let Some(range) = range else {
+            if let Some(pos) = self.oversized.iter().position(|i| *i == id) {
+                self.oversized.swap_remove(pos);
+            }
+            return;
+        };
+
+        for cell in range.cells() {
+            let Some(ids) = self.cells.get_mut(&cell) else {
+                continue;
+            };
+
+            if let Some(pos) = ids.iter().position(|i| *i == id) {
+                ids.swap_remove(pos);
+            }
+            if ids.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+}
+
+// proxies
+// -------
+impl SpatialHash {
+    pub fn insert(&mut self, bounds: AABB2D) -> ProxyId {
+        let cells = self.cell_range(&bounds);
+        let proxy = Proxy { bounds, cells };
+
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.proxies[id.idx()] = Some(proxy);
+                id
+            }
+            None => {
+                self.proxies.push(Some(proxy));
+                ProxyId((self.proxies.len() - 1) as u32)
+            }
+        };
+
+        self.add_to_cells(id, cells);
+        id
+    }
+
+    pub fn remove(&mut self, id: ProxyId) {
+        let Some(proxy) = self.proxies.get_mut(id.idx()).and_then(Option::take) else {
+            return;
+        };
+
+        self.remove_from_cells(id, proxy.cells);
+        self.free.push(id);
+    }
+
+    /// only touches the grid if the proxy moved into different cells
+    pub fn update(&mut self, id: ProxyId, bounds: AABB2D) {
+        let new_cells = self.cell_range(&bounds);
+        let Some(proxy) = self.proxies.get_mut(id.idx()).and_then(Option::as_mut) else {
+            return;
+        };
+
+        proxy.bounds = bounds;
+        let old_cells = proxy.cells;
+        if old_cells == new_cells {
+            return;
+        }
+
+        proxy.cells = new_cells;
+        self.remove_from_cells(id, old_cells);
+        self.add_to_cells(id, new_cells);
+    }
+
+    /// updates the proxy with the world-space bounds of a local collider
+    pub fn update_transformed(&mut self, id: ProxyId, local_bounds: &AABB2D, transform: &Transform) {
+        self.update(id, local_bounds.transform(transform));
+    }
+}
+
+// queries
+// -------
+impl SpatialHash {
+    /// every overlapping pair is reported exactly once, ordered by id
+    pub fn find_pairs(&self, out: &mut Vec<(ProxyId, ProxyId)>) {
+        out.clear();
+
+        for (cell, ids) in &self.cells {
+            for (i, a_id) in ids.iter().enumerate() {
+                let a = self.proxy(*a_id);
+
+                for b_id in &ids[i + 1..] {
+                    let b = self.proxy(*b_id);
+                    if !a.bounds.does_overlap(&b.bounds) {
+                        continue;
+                    }
+
+                    // the pair may share several cells - only report it in the one where their overlap starts
+                    if self.cell_of(a.bounds.min.max(b.bounds.min)) != *cell {
+                        continue;
+                    }
+
+                    out.push(((*a_id).min(*b_id), (*a_id).max(*b_id)));
+                }
+            }
+        }
+
+        for a_id in &self.oversized {
+            let a = self.proxy(*a_id);
+
+            for (b_id, b) in self.live_proxies() {
+                // pairs of two oversized proxies are only reported by the one with the lower id
+                let is_skipped = b.cells.is_none() && b_id <= *a_id;
+                if !is_skipped && a.bounds.does_overlap(&b.bounds) {
+                    out.push(((*a_id).min(b_id), (*a_id).max(b_id)));
+                }
+            }
+        }
+    }
+
+    pub fn pairs(&self) -> Vec<(ProxyId, ProxyId)> {
+        let mut result = Vec::new();
+        self.find_pairs(&mut result);
+        result
+    }
+
+    /// calls `f` once for every proxy that overlaps `bounds`
+    pub fn query(&self, bounds: &AABB2D, mut f: impl FnMut(ProxyId)) {
+        let Some(range) = self.cell_range(bounds) else {
+            self.live_proxies().filter(|(_, p)| p.bounds.does_overlap(bounds)).for_each(|(id, _)| f(id));
+            return;
+        };
+
+        for cell in range.cells() {
+            let Some(ids) = self.cells.get(&cell) else {
+                continue;
+            };
+
+            for id in ids {
+                let proxy = self.proxy(*id);
+                if proxy.bounds.does_overlap(bounds) && self.cell_of(proxy.bounds.min.max(bounds.min)) == cell {
+                    f(*id);
+                }
+            }
+        }
+
+        self.query_oversized(|proxy| proxy.bounds.does_overlap(bounds), f);
+    }
+
+    pub fn query_point(&self, p: Vec2, mut f: impl FnMut(ProxyId)) {
+        if let Some(ids) = self.cells.get(&self.cell_of(p)) {
+            for id in ids {
+                if self.proxy(*id).bounds.contains_point(p) {
+                    f(*id);
+                }
+            }
+        }
+
+        self.query_oversized(|proxy| proxy.bounds.contains_point(p), f);
+    }
+
+    /// walks the cells along the segment `a`-`b` in order
+    ///
+    /// proxies spanning several of those cells are reported once per cell, oversized ones are reported last.
+    /// segments too long for the grid are tested against every proxy.
+    pub fn query_segment(&self, a: Vec2, b: Vec2, mut f: impl FnMut(ProxyId)) {
+        let segment_bounds = AABB2D::new(a.min(b), a.max(b));
+        if self.cell_range(&segment_bounds).is_none() {
+            self.live_proxies().filter(|(_, p)| p.bounds.does_overlap(&segment_bounds)).for_each(|(id, _)| f(id));
+            return;
+        }
+
+        let d = b - a;
+        let end = self.cell_of(b);
+        let mut cell = self.cell_of(a);
+
+        let sign = |v: f32| if v > 0.0 { 1 } else if v < 0.0 { -1 } else { 0 };
+        let step = IVec2::new(sign(d.x), sign(d.y));
+        let boundary = |cell: i32, step: i32| (cell + step.max(0)) as f32 * self.cell_size;
+        let mut t_max = glam::vec2(
+            if step.x != 0 { (boundary(cell.x, step.x) - a.x) / d.x } else { f32::INFINITY },
+            if step.y != 0 { (boundary(cell.y, step.y) - a.y) / d.y } else { f32::INFINITY },
+        );
+        let t_delta = glam::vec2(self.cell_size / d.x.abs(), self.cell_size / d.y.abs());
+
+        let steps = (end - cell).abs();
+        let max_cells = steps.x + steps.y + 1;
+        for _ in 0..max_cells {
+            if let Some(ids) = self.cells.get(&cell) {
+                for id in ids {
+                    if self.proxy(*id).bounds.does_overlap(&segment_bounds) {
+                        f(*id);
+                    }
+                }
+            }
+
+            if cell == end {
+                break;
+            }
+
+            if t_max.x < t_max.y {
+                cell.x += step.x;
+                t_max.x += t_delta.x;
+            } else {
+                cell.y += step.y;
+                t_max.y += t_delta.y;
+            }
+        }
+
+        self.query_oversized(|proxy| proxy.bounds.does_overlap(&segment_bounds), f);
+    }
+
+    fn query_oversized(&self, is_hit: impl Fn(&Proxy) -> bool, mut f: impl FnMut(ProxyId)) {
+        for id in &self.oversized {
+            if is_hit(self.proxy(*id)) {
+                f(*id);
+            }
+        }
+    }
+
+    fn live_proxies(&self) -> impl Iterator<Item = (ProxyId, &Proxy)> {
+        self.proxies.iter().enumerate().filter_map(|(idx, p)| Some((ProxyId(idx as u32), p.as_ref()?)))
+    }
+
+    fn proxy(&self, id: ProxyId) -> &Proxy {
+        self.proxies[id.idx()].as_ref().expect("spatial hash contains a removed proxy")
+    }
+}
+
+impl Default for SpatialHash {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CELL_SIZE)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// brute force
+// ----------------------------------------------------------------------------
+
+/// O(n²) reference, fine for a handful of colliders
+pub fn brute_force_pairs(bounds: &[AABB2D], out: &mut Vec<(usize, usize)>) {
+    out.clear();
+
+    for (i, a) in bounds.iter().enumerate() {
+        for (j, b) in bounds.iter().enumerate().skip(i + 1) {
+            if a.does_overlap(b) {
+                out.push((i, j));
+            }
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::fixtures::random_bounds;
+
+    fn sorted_pairs(hash: &SpatialHash) -> Vec<(usize, usize)> {
+        let mut result: Vec<_> = hash.pairs().into_iter().map(|(a, b)| (a.idx(), b.idx())).collect();
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn test_pairs_match_brute_force() {
+        let mut bounds = random_bounds(300, 7, AABB2D::new(Vec2::splat(-30.0), Vec2::splat(30.0)), 0.2, 3.2);
+        let mut hash = SpatialHash::new(2.0);
+        let ids: Vec<_> = bounds.iter().map(|b| hash.insert(*b)).collect();
+
+        let mut expected = Vec::new();
+        brute_force_pairs(&bounds, &mut expected);
+        assert!(!expected.is_empty());
+        assert_eq!(sorted_pairs(&hash), expected);
+
+        // move everything a bit
+        for (b, id) in bounds.iter_mut().zip(&ids) {
+            *b = AABB2D::new(b.min + glam::vec2(1.3, -0.7), b.max + glam::vec2(1.3, -0.7));
+            hash.update(*id, *b);
+        }
+
+        brute_force_pairs(&bounds, &mut expected);
+        assert_eq!(sorted_pairs(&hash), expected);
+    }
+
+    #[test]
+    fn test_remove_and_reuse() {
+        let mut hash = SpatialHash::default();
+        let a = hash.insert(AABB2D::default());
+        let b = hash.insert(AABB2D::default());
+        assert_eq!(hash.pairs(), vec![(a, b)]);
+
+        hash.remove(a);
+        assert!(hash.pairs().is_empty());
+        assert_eq!(hash.len(), 1);
+
+        let c = hash.insert(AABB2D::from_center_half_extents(glam::vec2(50.0, 0.0), Vec2::ONE));
+        assert_eq!(c, a);
+        assert!(hash.pairs().is_empty());
+    }
+
+    #[test]
+    fn test_query() {
+        let mut hash = SpatialHash::new(1.0);
+        let big = hash.insert(AABB2D::new(glam::vec2(-10.0, -1.0), glam::vec2(10.0, 0.0)));
+        let small = hash.insert(AABB2D::from_center_half_extents(glam::vec2(5.0, 0.5), Vec2::splat(0.25)));
+
+        let mut found = Vec::new();
+        hash.query(&AABB2D::new(glam::vec2(-5.0, -0.5), glam::vec2(6.0, 1.0)), |id| found.push(id));
+        found.sort();
+        assert_eq!(found, vec![big, small]);
+
+        found.clear();
+        hash.query_point(glam::vec2(5.1, 0.6), |id| found.push(id));
+        assert_eq!(found, vec![small]);
+
+        found.clear();
+        hash.query_segment(glam::vec2(5.2, 3.0), glam::vec2(4.8, 0.3), |id| found.push(id));
+        assert_eq!(found, vec![small]);
+
+        found.clear();
+        hash.query_segment(glam::vec2(9.5, 2.0), glam::vec2(-9.5, -2.0), |id| found.push(id));
+        found.sort();
+        found.dedup();
+        assert_eq!(found, vec![big, small]);
+    }
+
+    #[test]
+    fn test_oversized_bounds() {
+        let mut hash = SpatialHash::new(1.0);
+        let small = hash.insert(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)));
+        let huge = hash.insert(AABB2D::new(Vec2::splat(-1e12), Vec2::splat(1e12)));
+        let endless = hash.insert(AABB2D::new(glam::vec2(f32::NEG_INFINITY, 5.0), glam::vec2(f32::INFINITY, 6.0)));
+        let far = hash.insert(AABB2D::from_center_half_extents(glam::vec2(0.0, 50.0), Vec2::splat(0.5)));
+
+        assert_eq!(sorted_pairs(&hash), vec![(0, 1), (1, 2), (1, 3)]);
+
+        let mut found = Vec::new();
+        hash.query(&AABB2D::new(glam::vec2(-1e9, 5.5), glam::vec2(1e9, 5.5)), |id| found.push(id));
+        found.sort();
+        assert_eq!(found, vec![huge, endless]);
+
+        found.clear();
+        hash.query_point(glam::vec2(0.2, 0.2), |id| found.push(id));
+        found.sort();
+        assert_eq!(found, vec![small, huge]);
+
+        found.clear();
+        hash.query_segment(glam::vec2(0.0, -1e10), glam::vec2(0.0, 1e10), |id| found.push(id));
+        found.sort();
+        assert_eq!(found, vec![small, huge, endless, far]);
+
+        // shrinking moves it back into the grid
+        hash.update(huge, AABB2D::from_center_half_extents(glam::vec2(0.0, 50.0), Vec2::ONE));
+        assert_eq!(sorted_pairs(&hash), vec![(1, 3)]);
+
+        hash.remove(endless);
+        hash.update(far, AABB2D::new(Vec2::splat(f32::NAN), Vec2::splat(f32::NAN)));
+        hash.remove(far);
+        assert!(hash.pairs().is_empty());
+    }
+}
@@ -0,0 +1,89 @@
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct CollisionLayers: u32 {
+        const NONE    = 0;
+        const DEFAULT = 1 << 0;
+        const ALL     = u32::MAX;
+    }
+}
+
+impl CollisionLayers {
+    pub const COUNT: u32 = u32::BITS;
+
+    /// games define their own layers: `const ENEMY: CollisionLayers = CollisionLayers::layer(2);`
+    /// panics if `idx` isn't below `COUNT`, at compile time for constants
+    pub const fn layer(idx: u32) -> Self {
+        assert!(idx < Self::COUNT, "collision layer index out of range");
+        Self::from_bits_retain(1 << idx)
+    }
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// two colliders only interact, if each one is part of a layer the other one wants to collide with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionFilter {
+    /// layers this collider is part of
+    pub membership: CollisionLayers,
+    /// layers this collider collides with
+    pub mask: CollisionLayers,
+}
+
+impl CollisionFilter {
+    pub const ALL: Self = Self::new(CollisionLayers::ALL, CollisionLayers::ALL);
+
+    pub const fn new(membership: CollisionLayers, mask: CollisionLayers) -> Self {
+        Self { membership, mask }
+    }
+
+    pub fn can_collide(&self, other: &CollisionFilter) -> bool {
+        self.membership.intersects(other.mask) && other.membership.intersects(self.mask)
+    }
+
+    /// one-sided test used by queries: is a collider with `filter` selected by this mask
+    pub fn selects(&self, filter: &CollisionFilter) -> bool {
+        self.mask.intersects(filter.membership)
+    }
+}
+
+impl Default for CollisionFilter {
+    fn default() -> Self {
+        Self::new(CollisionLayers::DEFAULT, CollisionLayers::ALL)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: CollisionLayers = CollisionLayers::layer(1);
+    const ENEMY:  CollisionLayers = CollisionLayers::layer(2);
+    const PICKUP: CollisionLayers = CollisionLayers::layer(3);
+
+    #[test]
+    fn test_can_collide() {
+        let player = CollisionFilter::new(PLAYER, ENEMY | PICKUP);
+        let enemy = CollisionFilter::new(ENEMY, PLAYER);
+        let pickup = CollisionFilter::new(PICKUP, PLAYER);
+
+        assert!(player.can_collide(&enemy));
+        assert!(player.can_collide(&pickup));
+        assert!(!enemy.can_collide(&pickup));
+        assert!(!enemy.can_collide(&enemy));
+        assert!(CollisionFilter::default().can_collide(&CollisionFilter::default()));
+    }
+    #[test]
+    #[should_panic]
+    fn test_layer_out_of_range() {
+        let idx = std::hint::black_box(CollisionLayers::COUNT);
+        let _ = CollisionLayers::layer(idx);
+    }
+}
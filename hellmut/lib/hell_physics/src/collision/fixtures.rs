@@ -0,0 +1,27 @@
+use glam::Vec2;
+
+use super::AABB2D;
+
+
+
+// ----------------------------------------------------------------------------
+// test fixtures
+// ----------------------------------------------------------------------------
+
+/// deterministic boxes with their centers inside `area` and half extents between `min_half` and `max_half`
+pub fn random_bounds(count: usize, seed: u32, area: AABB2D, min_half: f32, max_half: f32) -> Vec<AABB2D> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (state >> 8) as f32 / (1 << 24) as f32
+    };
+
+    let size = area.max - area.min;
+    (0..count)
+        .map(|_| {
+            let center = area.min + glam::vec2(next() * size.x, next() * size.y);
+            let half = Vec2::splat(min_half) + glam::vec2(next(), next()) * (max_half - min_half);
+            AABB2D::from_center_half_extents(center, half)
+        })
+        .collect()
+}
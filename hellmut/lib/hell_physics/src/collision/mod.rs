@@ -1,2 +1,23 @@
 mod aabb;
 pub use aabb::*;
+
+mod shapes;
+pub use shapes::*;
+
+mod sat;
+pub use sat::{collide, Contact};
+
+mod query;
+pub use query::{cast_ray, cast_shape, contains_point, Ray, RayHit};
+
+mod sweep;
+pub use sweep::*;
+
+mod filter;
+pub use filter::*;
+
+mod broadphase;
+pub use broadphase::*;
+
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures;
@@ -0,0 +1,339 @@
+use glam::Vec2;
+use smallvec::SmallVec;
+
+use super::sat::{self, closest_point_on_segment};
+use super::Shape2D;
+
+
+
+// ----------------------------------------------------------------------------
+// ray
+// ----------------------------------------------------------------------------
+
+/// the segment from `origin` to `origin + direction`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec2,
+    pub direction: Vec2,
+}
+
+impl Ray {
+    pub fn new(origin: Vec2, direction: Vec2) -> Self {
+        Self { origin, direction }
+    }
+
+    /// `direction` doesn't have to be normalized
+    pub fn with_length(origin: Vec2, direction: Vec2, length: f32) -> Self {
+        Self::new(origin, direction.normalize_or_zero() * length)
+    }
+
+    pub fn segment(a: Vec2, b: Vec2) -> Self {
+        Self::new(a, b - a)
+    }
+
+    pub fn at(&self, fraction: f32) -> Vec2 {
+        self.origin + self.direction * fraction
+    }
+
+    pub fn end(&self) -> Vec2 {
+        self.origin + self.direction
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit {
+    /// how far along the ray the hit is, in [0, 1]
+    pub fraction: f32,
+    pub point: Vec2,
+    /// unit vector, points away from the surface that was hit
+    pub normal: Vec2,
+}
+
+
+
+// ----------------------------------------------------------------------------
+// queries
+// ----------------------------------------------------------------------------
+
+const EPSILON: f32 = 1e-6;
+
+/// rays starting inside the shape hit it at fraction 0 with a normal opposing the ray
+pub fn cast_ray(shape: &Shape2D, ray: &Ray) -> Option<RayHit> {
+    shape.with_core(|points, radius| {
+        let (fraction, normal) = cast_ray_core(points, radius, ray, true)?;
+        Some(RayHit { fraction, point: ray.at(fraction), normal })
+    })
+}
+
+/// moves `shape` by `motion` and reports the first contact with `target`
+///
+/// the normal points away from `target`. shapes that already overlap hit at fraction 0, unless `motion` separates them -
+/// that way a body that rests slightly inside the ground can still slide along it.
+pub fn cast_shape(shape: &Shape2D, motion: Vec2, target: &Shape2D) -> Option<RayHit> {
+    if let Some(contact) = sat::collide(shape, target) {
+        let normal = -contact.normal;
+        if motion != Vec2::ZERO && motion.dot(normal) >= 0.0 {
+            return None;
+        }
+        return Some(RayHit { fraction: 0.0, point: shape_support(shape, -normal), normal });
+    }
+
+    // sweeping `shape` against `target` is the same as casting a ray from the origin against their minkowski difference
+    let (fraction, normal) = shape.with_core(|pa, ra| {
+        target.with_core(|pb, rb| {
+            let diff: PointBuf = pb.iter()
+                .flat_map(|b| pa.iter().map(move |a| *b - *a))
+                .collect();
+            // touching shapes are not inside each other, only motion towards the target is blocked
+            cast_ray_core(&convex_hull(diff), ra + rb, &Ray::new(Vec2::ZERO, motion), false)
+        })
+    })?;
+
+    Some(RayHit {
+        fraction,
+        point: shape_support(shape, -normal) + motion * fraction,
+        normal,
+    })
+}
+
+pub fn contains_point(shape: &Shape2D, p: Vec2) -> bool {
+    shape.with_core(|points, radius| core_contains_point(points, radius, p))
+}
+
+/// point of the shape that is furthest in `direction`
+fn shape_support(shape: &Shape2D, direction: Vec2) -> Vec2 {
+    shape.with_core(|points, radius| {
+        let support = points.iter()
+            .copied()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .unwrap_or_default();
+        support + direction.normalize_or_zero() * radius
+    })
+}
+
+
+
+// ----------------------------------------------------------------------------
+// rounded cores
+// ----------------------------------------------------------------------------
+
+/// edges with their outward normals - a segment has two sides, a point none
+fn edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2, Vec2)> + '_ {
+    let n = points.len();
+    let count = match n {
+        0 | 1 => 0,
+        2     => 2,
+        _     => n,
+    };
+
+    (0..count)
+        .map(move |i| (points[i], points[(i + 1) % n]))
+        .map(|(a, b)| {
+            // points are counter-clockwise, so the outside is on the right
+            let normal = -(b - a).perp().normalize_or_zero();
+            (a, b, normal)
+        })
+        .filter(|(_, _, normal)| *normal != Vec2::ZERO)
+}
+
+fn core_contains_point(points: &[Vec2], radius: f32, p: Vec2) -> bool {
+    if points.len() > 2 && edges(points).all(|(a, _, normal)| (p - a).dot(normal) <= 0.0) {
+        return true;
+    }
+
+    let dist_sq = match points.len() {
+        0 => return false,
+        1 => p.distance_squared(points[0]),
+        _ => edges(points)
+            .map(|(a, b, _)| p.distance_squared(closest_point_on_segment(p, a, b)))
+            .fold(f32::MAX, f32::min),
+    };
+
+    dist_sq <= radius * radius
+}
+
+/// the rounded core is the union of the circles around its points and its edges pushed out by the radius,
+/// so the first hit of any of those parts is the first hit of the whole shape
+fn cast_ray_core(points: &[Vec2], radius: f32, ray: &Ray, is_solid: bool) -> Option<(f32, Vec2)> {
+    if is_solid && core_contains_point(points, radius, ray.origin) {
+        return Some((0.0, -ray.direction.normalize_or_zero()));
+    }
+
+    let mut best: Option<(f32, Vec2)> = None;
+    let mut check = |fraction: f32, normal: Vec2| {
+        if best.is_none_or(|(f, _)| fraction < f) {
+            best = Some((fraction, normal));
+        }
+    };
+
+    if radius > 0.0 {
+        for p in points {
+            if let Some(fraction) = ray_circle(ray, *p, radius) {
+                check(fraction, (ray.at(fraction) - *p) / radius);
+            }
+        }
+    }
+
+    for (a, b, normal) in edges(points) {
+        if ray.direction.dot(normal) >= 0.0 {
+            continue;
+        }
+
+        let offset = normal * radius;
+        if let Some(fraction) = ray_segment(ray, a + offset, b + offset) {
+            check(fraction, normal);
+        }
+    }
+
+    best
+}
+
+pub(super) fn ray_circle(ray: &Ray, center: Vec2, radius: f32) -> Option<f32> {
+    let m = ray.origin - center;
+    let a = ray.direction.length_squared();
+    let b = m.dot(ray.direction);
+    let c = m.length_squared() - radius * radius;
+    if a <= EPSILON || (c > 0.0 && b > 0.0) {
+        return None;
+    }
+
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let fraction = (-b - discriminant.sqrt()) / a;
+    (0.0..=1.0).contains(&fraction).then_some(fraction)
+}
+
+fn ray_segment(ray: &Ray, a: Vec2, b: Vec2) -> Option<f32> {
+    let edge = b - a;
+    let denom = ray.direction.perp_dot(edge);
+    if denom.abs() <= EPSILON {
+        return None;
+    }
+
+    let diff = a - ray.origin;
+    let fraction = diff.perp_dot(edge) / denom;
+    let s = diff.perp_dot(ray.direction) / denom;
+
+    ((0.0..=1.0).contains(&fraction) && (0.0..=1.0).contains(&s)).then_some(fraction)
+}
+
+/// points of a minkowski difference - two boxes have 16, so shape casts between the built-in shapes stay on the stack
+type PointBuf = SmallVec<[Vec2; 32]>;
+
+/// counter-clockwise, without collinear points - may collapse to a segment or a single point
+fn convex_hull(mut points: PointBuf) -> PointBuf {
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup_by(|a, b| a.distance_squared(*b) <= EPSILON * EPSILON);
+    if points.len() <= 2 {
+        return points;
+    }
+
+    // lower half, then upper half
+    let mut hull = PointBuf::new();
+    push_hull_half(&mut hull, points.iter().copied());
+    push_hull_half(&mut hull, points.iter().rev().copied());
+    hull
+}
+
+fn push_hull_half(hull: &mut PointBuf, pass: impl Iterator<Item = Vec2>) {
+    let cross = |o: Vec2, a: Vec2, b: Vec2| (a - o).perp_dot(b - o);
+
+    let start = hull.len();
+    for p in pass {
+        while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0.0 {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // the last point is the first one of the other half
+    hull.pop();
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{Capsule, Circle, OrientedBox, AABB2D};
+    use smallvec::smallvec;
+    use std::f32::consts::FRAC_PI_4;
+
+    fn assert_hit(hit: Option<RayHit>, fraction: f32, normal: Vec2) {
+        let hit = hit.expect("expected a hit");
+        assert!((hit.fraction - fraction).abs() < 1e-4, "fraction: {}", hit.fraction);
+        assert!((hit.normal - normal).length() < 1e-4, "normal: {:?}", hit.normal);
+    }
+
+    #[test]
+    fn test_ray_box() {
+        let shape = Shape2D::from(AABB2D::default());
+        let ray = Ray::segment(glam::vec2(-3.0, 0.5), glam::vec2(3.0, 0.5));
+
+        assert_hit(cast_ray(&shape, &ray), 1.0 / 3.0, -Vec2::X);
+        assert!(cast_ray(&shape, &Ray::segment(glam::vec2(-3.0, 1.5), glam::vec2(3.0, 1.5))).is_none());
+        // too short
+        assert!(cast_ray(&shape, &Ray::with_length(glam::vec2(-3.0, 0.0), Vec2::X, 1.5)).is_none());
+        // starts inside
+        assert_hit(cast_ray(&shape, &Ray::new(Vec2::ZERO, Vec2::Y)), 0.0, -Vec2::Y);
+    }
+
+    #[test]
+    fn test_ray_rotated_box() {
+        let shape = Shape2D::from(OrientedBox::new(Vec2::ZERO, Vec2::ONE, FRAC_PI_4));
+        let hit = cast_ray(&shape, &Ray::with_length(glam::vec2(0.0, 5.0), -Vec2::Y, 10.0)).unwrap();
+
+        assert!((hit.point - glam::vec2(0.0, 2.0_f32.sqrt())).length() < 1e-4);
+        assert!(hit.normal.y > 0.0 && hit.normal.x.abs() > 0.5);
+    }
+
+    #[test]
+    fn test_ray_rounded() {
+        let circle = Shape2D::from(Circle::new(glam::vec2(2.0, 0.0), 1.0));
+        assert_hit(cast_ray(&circle, &Ray::new(Vec2::ZERO, glam::vec2(4.0, 0.0))), 0.25, -Vec2::X);
+
+        let capsule = Shape2D::from(Capsule::vertical(Vec2::ZERO, 4.0, 0.5));
+        // side and rounded cap
+        assert_hit(cast_ray(&capsule, &Ray::segment(glam::vec2(-2.0, 1.0), glam::vec2(2.0, 1.0))), 0.375, -Vec2::X);
+        assert_hit(cast_ray(&capsule, &Ray::segment(glam::vec2(0.0, 4.0), Vec2::ZERO)), 0.5, Vec2::Y);
+    }
+
+    #[test]
+    fn test_cast_shape() {
+        let ground = Shape2D::from(AABB2D::new(glam::vec2(-10.0, -1.0), glam::vec2(10.0, 0.0)));
+
+        let circle = Shape2D::from(Circle::new(glam::vec2(0.0, 3.0), 0.5));
+        let hit = cast_shape(&circle, glam::vec2(0.0, -5.0), &ground).unwrap();
+        assert!((hit.fraction - 0.5).abs() < 1e-4);
+        assert!((hit.normal - Vec2::Y).length() < 1e-4);
+        assert!((hit.point - glam::vec2(0.0, 0.0)).length() < 1e-4);
+
+        let capsule = Shape2D::from(Capsule::vertical(glam::vec2(5.0, 2.0), 2.0, 0.5));
+        assert_hit(cast_shape(&capsule, glam::vec2(0.0, -2.0), &ground), 0.5, Vec2::Y);
+        assert!(cast_shape(&capsule, glam::vec2(0.0, 2.0), &ground).is_none());
+
+        // slightly sunk into the ground: sliding and lifting is fine, pushing further in is not
+        let resting = Shape2D::from(AABB2D::from_center_half_extents(glam::vec2(0.0, 0.49), Vec2::splat(0.5)));
+        assert!(cast_shape(&resting, Vec2::X, &ground).is_none());
+        assert!(cast_shape(&resting, Vec2::Y, &ground).is_none());
+        assert_hit(cast_shape(&resting, -Vec2::Y, &ground), 0.0, Vec2::Y);
+
+        // exactly touching
+        let touching = Shape2D::from(AABB2D::from_center_half_extents(glam::vec2(0.0, 0.5), Vec2::splat(0.5)));
+        assert!(cast_shape(&touching, Vec2::X, &ground).is_none());
+        assert_hit(cast_shape(&touching, -Vec2::Y, &ground), 0.0, Vec2::Y);
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let hull = convex_hull(smallvec![Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y, glam::vec2(0.5, 0.5), glam::vec2(0.5, 0.0)]);
+        assert_eq!(hull.as_slice(), [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]);
+
+        let line = convex_hull(smallvec![Vec2::X, Vec2::ZERO, glam::vec2(2.0, 0.0)]);
+        assert_eq!(line.as_slice(), [Vec2::ZERO, glam::vec2(2.0, 0.0)]);
+    }
+}
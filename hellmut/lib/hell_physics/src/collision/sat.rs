@@ -0,0 +1,257 @@
+use glam::Vec2;
+
+use super::Shape2D;
+
+
+
+// ----------------------------------------------------------------------------
+// contact
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// unit vector, points from the first to the second shape
+    pub normal: Vec2,
+    /// moving the second shape by `normal * depth` separates both shapes
+    pub depth: f32,
+}
+
+impl Contact {
+    pub fn flipped(&self) -> Self {
+        Self {
+            normal: -self.normal,
+            depth: self.depth,
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// separating axis test
+// ----------------------------------------------------------------------------
+
+const EPSILON: f32 = 1e-6;
+
+pub fn collide(a: &Shape2D, b: &Shape2D) -> Option<Contact> {
+    a.with_core(|pa, ra| {
+        b.with_core(|pb, rb| collide_cores(pa, ra, pb, rb))
+    })
+}
+
+/// both shapes are convex point sets grown by a radius - their face normals are tested first,
+/// if the cores themselves don't touch, the rounded parts are resolved via their closest points
+fn collide_cores(a: &[Vec2], ra: f32, b: &[Vec2], rb: f32) -> Option<Contact> {
+    let mut best: Option<Contact> = None;
+    let mut has_axes = false;
+    let mut are_cores_separated = false;
+
+    for axis in axes(a).chain(axes(b)) {
+        has_axes = true;
+
+        let (min_a, max_a) = project(a, axis);
+        let (min_b, max_b) = project(b, axis);
+
+        // push b along +axis / -axis
+        let pos = (max_a + ra) - (min_b - rb);
+        let neg = (max_b + rb) - (min_a - ra);
+        if pos <= 0.0 || neg <= 0.0 {
+            return None;
+        }
+
+        are_cores_separated |= max_a <= min_b || max_b <= min_a;
+
+        let (depth, normal) = if pos < neg { (pos, axis) } else { (neg, -axis) };
+        if best.is_none_or(|c| depth < c.depth) {
+            best = Some(Contact { normal, depth });
+        }
+    }
+
+    let radius = ra + rb;
+    if !has_axes {
+        // point vs point
+        are_cores_separated = a[0].distance_squared(b[0]) > EPSILON;
+    }
+
+    if radius > 0.0 && are_cores_separated {
+        let (pa, pb) = closest_points(a, b);
+        let dist = pa.distance(pb);
+        if dist >= radius {
+            return None;
+        }
+
+        // touching cores don't have a direction between their closest points, the sat axis is used instead
+        if dist > EPSILON {
+            return Some(Contact {
+                normal: (pb - pa) / dist,
+                depth: radius - dist,
+            });
+        }
+    }
+
+    // concentric circles don't have any preferred direction
+    best.or_else(|| (radius > 0.0).then_some(Contact { normal: Vec2::Y, depth: radius }))
+}
+
+fn axes(points: &[Vec2]) -> impl Iterator<Item = Vec2> + '_ {
+    let n = points.len();
+    let edges = match n {
+        0 | 1 => 0,
+        _     => n,
+    };
+
+    // a segment also needs its own direction as an axis
+    let extra = (n == 2).then(|| (points[1] - points[0]).normalize_or_zero());
+
+    (0..edges)
+        .filter(move |i| n > 2 || *i == 0)
+        .map(move |i| (points[(i + 1) % n] - points[i]).perp().normalize_or_zero())
+        .chain(extra)
+        .filter(|axis| *axis != Vec2::ZERO)
+}
+
+fn project(points: &[Vec2], axis: Vec2) -> (f32, f32) {
+    points.iter()
+        .map(|p| p.dot(axis))
+        .fold((f32::MAX, f32::MIN), |(min, max), d| (min.min(d), max.max(d)))
+}
+
+fn segments(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    let n = points.len();
+    let count = match n {
+        0     => 0,
+        1 | 2 => 1,
+        _     => n,
+    };
+
+    (0..count).map(move |i| (points[i], points[(i + 1) % n]))
+}
+
+pub(crate) fn closest_point_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let ab = b - a;
+    let len_sq = ab.length_squared();
+    if len_sq <= EPSILON {
+        return a;
+    }
+
+    let t = ((p - a).dot(ab) / len_sq).clamp(0.0, 1.0);
+    a + ab * t
+}
+
+/// closest points of two disjoint convex point sets
+fn closest_points(a: &[Vec2], b: &[Vec2]) -> (Vec2, Vec2) {
+    let mut best = (a[0], b[0]);
+    let mut best_dist = f32::MAX;
+
+    for p in a {
+        for (s0, s1) in segments(b) {
+            let q = closest_point_on_segment(*p, s0, s1);
+            let dist = p.distance_squared(q);
+            if dist < best_dist {
+                best_dist = dist;
+                best = (*p, q);
+            }
+        }
+    }
+
+    for p in b {
+        for (s0, s1) in segments(a) {
+            let q = closest_point_on_segment(*p, s0, s1);
+            let dist = p.distance_squared(q);
+            if dist < best_dist {
+                best_dist = dist;
+                best = (q, *p);
+            }
+        }
+    }
+
+    best
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{Capsule, Circle, ConvexPolygon, OrientedBox, AABB2D};
+    use std::f32::consts::FRAC_PI_4;
+
+    fn assert_contact(contact: Option<Contact>, normal: Vec2, depth: f32) {
+        let contact = contact.expect("expected a contact");
+        assert!((contact.normal - normal).length() < 1e-4, "normal: {:?}", contact.normal);
+        assert!((contact.depth - depth).abs() < 1e-4, "depth: {}", contact.depth);
+    }
+
+    #[test]
+    fn test_circles() {
+        let a = Shape2D::from(Circle::new(Vec2::ZERO, 1.0));
+        let b = Shape2D::from(Circle::new(glam::vec2(1.5, 0.0), 1.0));
+        let c = Shape2D::from(Circle::new(glam::vec2(2.5, 0.0), 0.4));
+
+        assert_contact(a.collide(&b), Vec2::X, 0.5);
+        assert_contact(b.collide(&a), -Vec2::X, 0.5);
+        assert!(!a.does_overlap(&c));
+    }
+
+    #[test]
+    fn test_boxes() {
+        let a = Shape2D::from(AABB2D::default());
+        let b = Shape2D::from(AABB2D::from_center_half_extents(glam::vec2(0.5, 1.8), Vec2::ONE));
+        assert_contact(a.collide(&b), Vec2::Y, 0.2);
+
+        // a box rotated by 45 degrees reaches sqrt(2) to the side
+        let rotated = Shape2D::from(OrientedBox::new(glam::vec2(2.3, 0.0), Vec2::ONE, FRAC_PI_4));
+        assert_contact(a.collide(&rotated), Vec2::X, std::f32::consts::SQRT_2 - 1.3);
+
+        let rotated = Shape2D::from(OrientedBox::new(glam::vec2(2.5, 0.0), Vec2::ONE, FRAC_PI_4));
+        assert!(!a.does_overlap(&rotated));
+
+        // the corner regions of the bounds don't count
+        let corner = Shape2D::from(OrientedBox::new(glam::vec2(1.9, 1.9), Vec2::ONE, FRAC_PI_4));
+        assert!(a.bounds().does_overlap(&corner.bounds()));
+        assert!(!a.does_overlap(&corner));
+    }
+
+    #[test]
+    fn test_circle_box_corner() {
+        let a = Shape2D::from(AABB2D::default());
+        let b = Shape2D::from(Circle::new(glam::vec2(1.5, 1.5), 0.8));
+        let expected_depth = 0.8 - std::f32::consts::SQRT_2 * 0.5;
+        assert_contact(a.collide(&b), Vec2::ONE.normalize(), expected_depth);
+
+        let b = Shape2D::from(Circle::new(glam::vec2(1.5, 1.5), 0.7));
+        assert!(!a.does_overlap(&b));
+    }
+
+    #[test]
+    fn test_circle_touching_box() {
+        let a = Shape2D::from(AABB2D::default());
+        let b = Shape2D::from(Circle::new(glam::vec2(1.0, 0.0), 0.5));
+        let contact = a.collide(&b);
+
+        assert!(contact.is_some_and(|c| c.normal.is_finite()));
+        assert_contact(contact, Vec2::X, 0.5);
+    }
+
+    #[test]
+    fn test_capsule() {
+        let capsule = Shape2D::from(Capsule::vertical(Vec2::ZERO, 4.0, 0.5));
+        let ground = Shape2D::from(AABB2D::new(glam::vec2(-10.0, -3.0), glam::vec2(10.0, -1.8)));
+        assert_contact(capsule.collide(&ground), -Vec2::Y, 0.2);
+
+        let side = Shape2D::from(Circle::new(glam::vec2(0.9, 0.3), 0.5));
+        assert_contact(capsule.collide(&side), Vec2::X, 0.1);
+    }
+
+    #[test]
+    fn test_polygon() {
+        let triangle = Shape2D::from(ConvexPolygon::new(vec![glam::vec2(0.0, 0.0), glam::vec2(2.0, 0.0), glam::vec2(0.0, 2.0)]).unwrap());
+        let inside = Shape2D::from(Circle::new(glam::vec2(1.0, 1.0), 0.2));
+        let outside = Shape2D::from(Circle::new(glam::vec2(1.3, 1.3), 0.2));
+
+        assert!(triangle.does_overlap(&inside));
+        assert!(!triangle.does_overlap(&outside));
+    }
+}
@@ -0,0 +1,398 @@
+use glam::{Mat4, Vec2, Vec3};
+use hell_common::transform::Transform;
+use hell_core::error::{HellError, HellErrorKind, HellResult};
+
+use super::{AABB2D, Contact, Ray, RayHit};
+
+
+
+// ----------------------------------------------------------------------------
+// helpers
+// ----------------------------------------------------------------------------
+
+fn transform_point(m: &Mat4, p: Vec2) -> Vec2 {
+    m.transform_point3(Vec3::from((p, 0.0))).truncate()
+}
+
+fn transform_vector(m: &Mat4, v: Vec2) -> Vec2 {
+    m.transform_vector3(Vec3::from((v, 0.0))).truncate()
+}
+
+/// non-uniform scales would turn circles into ellipses - they are approximated by the larger axis
+fn radius_scale(m: &Mat4) -> f32 {
+    transform_vector(m, Vec2::X).length().max(transform_vector(m, Vec2::Y).length())
+}
+
+/// negative if the transform mirrors the xy-plane
+fn determinant_2d(m: &Mat4) -> f32 {
+    transform_vector(m, Vec2::X).perp_dot(transform_vector(m, Vec2::Y))
+}
+
+
+
+// ----------------------------------------------------------------------------
+// circle
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn transform(&self, m: &Mat4) -> Self {
+        Self::new(transform_point(m, self.center), self.radius * radius_scale(m))
+    }
+
+    pub fn bounds(&self) -> AABB2D {
+        AABB2D::from_center_half_extents(self.center, Vec2::splat(self.radius))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// oriented box
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrientedBox {
+    pub center: Vec2,
+    pub half_extents: Vec2,
+    /// counter-clockwise, in radians
+    pub rotation: f32,
+}
+
+impl OrientedBox {
+    pub fn new(center: Vec2, half_extents: Vec2, rotation: f32) -> Self {
+        Self { center, half_extents: half_extents.abs(), rotation }
+    }
+
+    /// local x- and y-axis
+    pub fn axes(&self) -> [Vec2; 2] {
+        let x = Vec2::from_angle(self.rotation);
+        [x, x.perp()]
+    }
+
+    /// counter-clockwise, starting at the local bottom-left corner
+    pub fn corners(&self) -> [Vec2; 4] {
+        let [x, y] = self.axes();
+        let x = x * self.half_extents.x;
+        let y = y * self.half_extents.y;
+
+        [
+            self.center - x - y,
+            self.center + x - y,
+            self.center + x + y,
+            self.center - x + y,
+        ]
+    }
+
+    /// exact for rotations, translations and scales - a non-uniform scale of an already rotated box would skew it,
+    /// in that case the result is only an approximation (use a `ConvexPolygon` instead)
+    pub fn transform(&self, m: &Mat4) -> Self {
+        let [x, y] = self.axes();
+        let x = transform_vector(m, x * self.half_extents.x);
+        let y = transform_vector(m, y * self.half_extents.y);
+
+        Self::new(
+            transform_point(m, self.center),
+            glam::vec2(x.length(), y.length()),
+            x.y.atan2(x.x),
+        )
+    }
+
+    pub fn bounds(&self) -> AABB2D {
+        AABB2D::from_points(self.corners())
+    }
+}
+
+impl From<AABB2D> for OrientedBox {
+    fn from(val: AABB2D) -> Self {
+        Self::new(val.center(), val.half_extents(), 0.0)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// capsule
+// ----------------------------------------------------------------------------
+
+/// all points within `radius` of the segment `a`-`b`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub radius: f32,
+}
+
+impl Capsule {
+    pub fn new(a: Vec2, b: Vec2, radius: f32) -> Self {
+        Self { a, b, radius }
+    }
+
+    /// upright capsule, `height` includes the rounded caps
+    pub fn vertical(center: Vec2, height: f32, radius: f32) -> Self {
+        let half = Vec2::Y * (height * 0.5 - radius).max(0.0);
+        Self::new(center - half, center + half, radius)
+    }
+
+    pub fn transform(&self, m: &Mat4) -> Self {
+        Self::new(transform_point(m, self.a), transform_point(m, self.b), self.radius * radius_scale(m))
+    }
+
+    pub fn bounds(&self) -> AABB2D {
+        AABB2D::new(self.a, self.b).expand(self.radius)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// convex polygon
+// ----------------------------------------------------------------------------
+
+/// points are stored in counter-clockwise order
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexPolygon {
+    points: Vec<Vec2>,
+}
+
+impl ConvexPolygon {
+    /// `points` have to describe a convex polygon with an area, they are re-ordered to be counter-clockwise if necessary
+    pub fn new(mut points: Vec<Vec2>) -> HellResult<Self> {
+        if points.len() < 3 {
+            return Err(invalid_polygon(format!("a polygon needs at least three points, got {}", points.len())));
+        }
+
+        let area = signed_area(&points);
+        if !area.is_finite() || area.abs() <= f32::EPSILON {
+            return Err(invalid_polygon("polygon without an area"));
+        }
+        if area < 0.0 {
+            points.reverse();
+        }
+        if !is_convex(&points) {
+            return Err(invalid_polygon("polygon is not convex"));
+        }
+
+        Ok(Self { points })
+    }
+
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    pub fn centroid(&self) -> Vec2 {
+        self.points.iter().copied().sum::<Vec2>() / self.points.len() as f32
+    }
+
+    pub fn transform(&self, m: &Mat4) -> Self {
+        let mut points: Vec<_> = self.points.iter().map(|p| transform_point(m, *p)).collect();
+        if determinant_2d(m) < 0.0 {
+            points.reverse();
+        }
+
+        Self { points }
+    }
+
+    pub fn bounds(&self) -> AABB2D {
+        AABB2D::from_points(self.points.iter().copied())
+    }
+}
+
+fn signed_area(points: &[Vec2]) -> f32 {
+    let n = points.len();
+    (0..n).map(|i| points[i].perp_dot(points[(i + 1) % n])).sum::<f32>() * 0.5
+}
+
+fn is_convex(points: &[Vec2]) -> bool {
+    let n = points.len();
+    (0..n).all(|i| {
+        let a = points[i];
+        let b = points[(i + 1) % n];
+        let c = points[(i + 2) % n];
+        (b - a).perp_dot(c - b) >= -f32::EPSILON
+    })
+}
+
+/// bad input of the caller, not a failed resource - so it isn't logged either
+fn invalid_polygon(msg: impl Into<String>) -> HellError {
+    HellError::from_msg(HellErrorKind::GenericError, msg.into())
+}
+
+
+
+// ----------------------------------------------------------------------------
+// shape
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape2D {
+    Circle(Circle),
+    Box(OrientedBox),
+    Capsule(Capsule),
+    Polygon(ConvexPolygon),
+}
+
+impl Default for Shape2D {
+    fn default() -> Self {
+        Self::Box(AABB2D::default().into())
+    }
+}
+
+impl Shape2D {
+    /// local-space shape to world-space
+    pub fn transform(&self, t: &Transform) -> Self {
+        self.transform_mat(&t.create_model_mat())
+    }
+
+    pub fn transform_mat(&self, m: &Mat4) -> Self {
+        match self {
+            Self::Circle(s)  => Self::Circle(s.transform(m)),
+            Self::Box(s)     => Self::Box(s.transform(m)),
+            Self::Capsule(s) => Self::Capsule(s.transform(m)),
+            Self::Polygon(s) => Self::Polygon(s.transform(m)),
+        }
+    }
+
+    pub fn bounds(&self) -> AABB2D {
+        match self {
+            Self::Circle(s)  => s.bounds(),
+            Self::Box(s)     => s.bounds(),
+            Self::Capsule(s) => s.bounds(),
+            Self::Polygon(s) => s.bounds(),
+        }
+    }
+
+    pub fn center(&self) -> Vec2 {
+        match self {
+            Self::Circle(s)  => s.center,
+            Self::Box(s)     => s.center,
+            Self::Capsule(s) => (s.a + s.b) * 0.5,
+            Self::Polygon(s) => s.centroid(),
+        }
+    }
+
+    /// every shape is a convex point set (point, segment or polygon) grown by a radius
+    pub(crate) fn with_core<R>(&self, f: impl FnOnce(&[Vec2], f32) -> R) -> R {
+        match self {
+            Self::Circle(s)  => f(std::slice::from_ref(&s.center), s.radius),
+            Self::Box(s)     => f(&s.corners(), 0.0),
+            Self::Capsule(s) => f(&[s.a, s.b], s.radius),
+            Self::Polygon(s) => f(&s.points, 0.0),
+        }
+    }
+
+    /// both shapes have to be in the same space - the normal of the contact points from `self` to `other`
+    pub fn collide(&self, other: &Shape2D) -> Option<Contact> {
+        super::sat::collide(self, other)
+    }
+
+    pub fn does_overlap(&self, other: &Shape2D) -> bool {
+        self.collide(other).is_some()
+    }
+
+    pub fn cast_ray(&self, ray: &Ray) -> Option<RayHit> {
+        super::query::cast_ray(self, ray)
+    }
+
+    /// sweeps `self` by `motion` against `target`
+    pub fn cast_shape(&self, motion: Vec2, target: &Shape2D) -> Option<RayHit> {
+        super::query::cast_shape(self, motion, target)
+    }
+
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        super::query::contains_point(self, p)
+    }
+}
+
+impl From<Circle> for Shape2D {
+    fn from(val: Circle) -> Self {
+        Self::Circle(val)
+    }
+}
+
+impl From<OrientedBox> for Shape2D {
+    fn from(val: OrientedBox) -> Self {
+        Self::Box(val)
+    }
+}
+
+impl From<AABB2D> for Shape2D {
+    fn from(val: AABB2D) -> Self {
+        Self::Box(val.into())
+    }
+}
+
+impl From<Capsule> for Shape2D {
+    fn from(val: Capsule) -> Self {
+        Self::Capsule(val)
+    }
+}
+
+impl From<ConvexPolygon> for Shape2D {
+    fn from(val: ConvexPolygon) -> Self {
+        Self::Polygon(val)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Quat;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn approx(a: Vec2, b: Vec2) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn test_box_transform() {
+        let t = Transform::new(glam::vec3(5.0, 0.0, 0.0), Quat::from_rotation_z(FRAC_PI_2), glam::vec3(2.0, 1.0, 1.0));
+        let shape = Shape2D::default().transform(&t);
+        let bounds = shape.bounds();
+
+        // rotated by 90 degrees: the scaled x-extent ends up on the y-axis
+        assert!(approx(bounds.min, glam::vec2(4.0, -2.0)));
+        assert!(approx(bounds.max, glam::vec2(6.0, 2.0)));
+    }
+
+    #[test]
+    fn test_circle_transform() {
+        let t = Transform::new(glam::vec3(1.0, 2.0, 0.0), Quat::IDENTITY, glam::vec3(-3.0, 2.0, 1.0));
+        let Shape2D::Circle(c) = Shape2D::from(Circle::new(Vec2::ZERO, 1.0)).transform(&t) else {
+            unreachable!()
+        };
+
+        assert!(approx(c.center, glam::vec2(1.0, 2.0)));
+        assert_eq!(c.radius, 3.0);
+    }
+
+    #[test]
+    fn test_polygon_mirrored() {
+        let poly = ConvexPolygon::new(vec![Vec2::ZERO, Vec2::X, Vec2::Y]).unwrap();
+        let t = Transform::new(Vec3::ZERO, Quat::IDENTITY, glam::vec3(-1.0, 1.0, 1.0));
+        let mirrored = poly.transform(&t.create_model_mat());
+
+        assert!(signed_area(mirrored.points()) > 0.0);
+        assert!(approx(mirrored.bounds().min, glam::vec2(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_polygon_invalid() {
+        assert!(ConvexPolygon::new(vec![Vec2::ZERO, Vec2::X]).is_err());
+        assert!(ConvexPolygon::new(vec![Vec2::ZERO, Vec2::X, Vec2::X * 2.0]).is_err());
+        assert!(ConvexPolygon::new(vec![Vec2::ZERO, glam::vec2(2.0, 0.0), glam::vec2(0.5, 0.5), glam::vec2(0.0, 2.0)]).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use glam::Vec2;
+
+use super::query::ray_circle;
+use super::{Circle, Ray, RayHit, Shape2D, AABB2D};
+
+
+
+// ----------------------------------------------------------------------------
+// swept tests
+// ----------------------------------------------------------------------------
+
+const EPSILON: f32 = 1e-6;
+
+/// moves `moving` by `motion` and reports the first contact with `target`
+///
+/// the normal points away from `target`, boxes that already overlap hit at fraction 0.
+pub fn sweep_aabb(moving: &AABB2D, motion: Vec2, target: &AABB2D) -> Option<RayHit> {
+    // ray from the center of the moving box against the target grown by the moving box
+    let half_extents = moving.half_extents();
+    let expanded = AABB2D::new(target.min - half_extents, target.max + half_extents);
+    let origin = moving.center();
+
+    let mut t_enter = 0.0_f32;
+    let mut t_exit = 1.0_f32;
+    let mut normal = Vec2::ZERO;
+
+    for axis in 0..2 {
+        if motion[axis].abs() <= EPSILON {
+            if origin[axis] < expanded.min[axis] || origin[axis] > expanded.max[axis] {
+                return None;
+            }
+            continue;
+        }
+
+        let t0 = (expanded.min[axis] - origin[axis]) / motion[axis];
+        let t1 = (expanded.max[axis] - origin[axis]) / motion[axis];
+        let (t0, t1) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+
+        if t0 > t_enter {
+            t_enter = t0;
+            normal = Vec2::ZERO;
+            normal[axis] = -motion[axis].signum();
+        }
+        t_exit = t_exit.min(t1);
+
+        if t_enter > t_exit || t_exit <= 0.0 {
+            return None;
+        }
+    }
+
+    if normal == Vec2::ZERO {
+        normal = separation_normal(moving, target);
+    }
+
+    let point = (origin + motion * t_enter).clamp(target.min, target.max);
+    Some(RayHit { fraction: t_enter, point, normal })
+}
+
+pub fn sweep_circle(moving: &Circle, motion: Vec2, target: &Circle) -> Option<RayHit> {
+    let radius = moving.radius + target.radius;
+    let fraction = if moving.center.distance_squared(target.center) < radius * radius {
+        0.0
+    } else {
+        ray_circle(&Ray::new(moving.center, motion), target.center, radius)?
+    };
+
+    let center = moving.center + motion * fraction;
+    let normal = (center - target.center).try_normalize().unwrap_or(-motion.normalize_or_zero());
+    Some(RayHit { fraction, point: target.center + normal * target.radius, normal })
+}
+
+/// first contact of two moving shapes - both move by their motion during the same time span
+///
+/// the fraction of the hit is that time of impact, the point is where both shapes touch at that time.
+pub fn time_of_impact(a: &Shape2D, motion_a: Vec2, b: &Shape2D, motion_b: Vec2) -> Option<RayHit> {
+    let mut hit = super::query::cast_shape(a, motion_a - motion_b, b)?;
+    hit.point += motion_b * hit.fraction;
+    Some(hit)
+}
+
+/// axis of least penetration of two overlapping boxes, pointing towards `moving`
+fn separation_normal(moving: &AABB2D, target: &AABB2D) -> Vec2 {
+    let overlap = moving.max.min(target.max) - moving.min.max(target.min);
+    let dir = moving.center() - target.center();
+
+    if overlap.x < overlap.y {
+        Vec2::X * if dir.x < 0.0 { -1.0 } else { 1.0 }
+    } else {
+        Vec2::Y * if dir.y < 0.0 { -1.0 } else { 1.0 }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_hit(hit: Option<RayHit>, fraction: f32, normal: Vec2) {
+        let hit = hit.expect("expected a hit");
+        assert!((hit.fraction - fraction).abs() < 1e-4, "fraction: {}", hit.fraction);
+        assert!((hit.normal - normal).length() < 1e-4, "normal: {:?}", hit.normal);
+    }
+
+    #[test]
+    fn test_sweep_aabb() {
+        let wall = AABB2D::new(glam::vec2(0.0, -5.0), glam::vec2(0.1, 5.0));
+        let bullet = AABB2D::from_center_half_extents(glam::vec2(-10.0, 0.0), Vec2::splat(0.5));
+
+        // the end position is way behind the wall
+        assert_hit(sweep_aabb(&bullet, glam::vec2(20.0, 0.0), &wall), 0.475, -Vec2::X);
+        assert!(sweep_aabb(&bullet, glam::vec2(5.0, 0.0), &wall).is_none());
+        assert!(sweep_aabb(&bullet, glam::vec2(20.0, 20.0), &wall).is_none());
+        assert!(sweep_aabb(&bullet, glam::vec2(-20.0, 0.0), &wall).is_none());
+
+        let inside = AABB2D::from_center_half_extents(glam::vec2(0.3, 0.0), Vec2::splat(0.5));
+        assert_hit(sweep_aabb(&inside, Vec2::ZERO, &wall), 0.0, Vec2::X);
+
+        // touching and moving away
+        let touching = AABB2D::from_center_half_extents(glam::vec2(-0.5, 0.0), Vec2::splat(0.5));
+        assert!(sweep_aabb(&touching, -Vec2::X, &wall).is_none());
+        assert_hit(sweep_aabb(&touching, Vec2::X, &wall), 0.0, -Vec2::X);
+    }
+
+    #[test]
+    fn test_sweep_circle() {
+        let target = Circle::new(glam::vec2(10.0, 0.0), 1.0);
+        let hit = sweep_circle(&Circle::new(Vec2::ZERO, 1.0), glam::vec2(16.0, 0.0), &target).unwrap();
+
+        assert!((hit.fraction - 0.5).abs() < 1e-4);
+        assert!((hit.point - glam::vec2(9.0, 0.0)).length() < 1e-4);
+        assert!(sweep_circle(&Circle::new(Vec2::ZERO, 1.0), glam::vec2(16.0, 6.0), &target).is_none());
+    }
+
+    #[test]
+    fn test_time_of_impact() {
+        let a = Shape2D::from(AABB2D::from_center_half_extents(glam::vec2(-5.0, 0.0), Vec2::splat(0.5)));
+        let b = Shape2D::from(AABB2D::from_center_half_extents(glam::vec2(5.0, 0.0), Vec2::splat(0.5)));
+
+        // both boxes run into each other and meet in the middle
+        let hit = time_of_impact(&a, glam::vec2(10.0, 0.0), &b, glam::vec2(-10.0, 0.0)).unwrap();
+        assert!((hit.fraction - 0.45).abs() < 1e-4);
+        assert!((hit.point.x - 0.0).abs() < 1e-4);
+        assert!(time_of_impact(&a, glam::vec2(10.0, 0.0), &b, glam::vec2(10.0, 0.0)).is_none());
+    }
+}
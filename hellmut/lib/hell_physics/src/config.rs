@@ -1,19 +1,41 @@
+use glam::Vec2;
+
 pub struct PhysicsConfig {
     pub g_force: f32,
+    /// the world is always advanced in steps of this size
+    pub fixed_time_step: f32,
+    /// upper limit of fixed steps per frame, so that a long frame doesn't stall the following ones
+    pub max_sub_steps: u32,
+    pub velocity_iterations: u32,
+    /// bodies slower than this for `sleep_time` seconds are put to sleep
+    pub sleep_velocity: f32,
+    pub sleep_time: f32,
+    pub broadphase_cell_size: f32,
 }
 
 impl PhysicsConfig {
     pub fn new(g_force: f32) -> Self {
         Self {
             g_force,
+            ..Default::default()
         }
     }
+
+    pub fn gravity(&self) -> Vec2 {
+        Vec2::new(0.0, self.g_force)
+    }
 }
 
 impl Default for PhysicsConfig {
     fn default() -> Self {
         Self {
             g_force: -9.81,
+            fixed_time_step: 1.0 / 60.0,
+            max_sub_steps: 8,
+            velocity_iterations: 6,
+            sleep_velocity: 0.05,
+            sleep_time: 0.5,
+            broadphase_cell_size: 4.0,
         }
     }
 }
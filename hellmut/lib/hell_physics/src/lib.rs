@@ -7,4 +7,6 @@
 mod config;
 pub use config::PhysicsConfig;
 
+pub mod character;
 pub mod collision;
+pub mod world;
@@ -0,0 +1,275 @@
+use glam::{Mat4, Quat, Vec2, Vec3};
+use hell_common::transform::Transform;
+
+use crate::collision::{CollisionFilter, CollisionLayers, ProxyId, Shape2D, AABB2D};
+
+
+
+// ----------------------------------------------------------------------------
+// handle
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BodyHandle {
+    pub(crate) idx: u32,
+    pub(crate) generation: u32,
+}
+
+impl BodyHandle {
+    pub fn idx(&self) -> usize {
+        self.idx as usize
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// body
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyType {
+    /// moved by gravity, forces and contacts
+    Dynamic,
+    /// moved only by its velocity, pushes dynamic bodies but is never pushed itself
+    Kinematic,
+    /// never moves
+    Static,
+}
+
+/// bodies only translate - the rotation is part of the pose but there are no angular dynamics
+#[derive(Debug, Clone)]
+pub struct RigidBody {
+    pub position: Vec2,
+    pub rotation: f32,
+    pub velocity: Vec2,
+    pub gravity_scale: f32,
+    pub linear_damping: f32,
+    pub restitution: f32,
+    pub friction: f32,
+    /// local-space collider
+    pub shape: Shape2D,
+    pub filter: CollisionFilter,
+    /// sensors report contact events but don't push anything
+    pub is_sensor: bool,
+    /// fast bodies are swept from their previous position each step, so they can't tunnel through thin colliders
+    pub is_bullet: bool,
+    /// id of the game entity that owns the body, it is passed along with contact events
+    pub entity: u64,
+
+    /// changing the type changes the mass, see `set_body_type`
+    pub(crate) body_type: BodyType,
+    /// mass of the body while it is dynamic
+    pub(crate) dynamic_mass: f32,
+    pub(crate) inv_mass: f32,
+    pub(crate) force: Vec2,
+    pub(crate) is_sleeping: bool,
+    pub(crate) sleep_timer: f32,
+    pub(crate) world_shape: Shape2D,
+    pub(crate) proxy: Option<ProxyId>,
+}
+
+impl RigidBody {
+    pub fn new(body_type: BodyType, shape: Shape2D) -> Self {
+        Self {
+            position: Vec2::ZERO,
+            rotation: 0.0,
+            velocity: Vec2::ZERO,
+            gravity_scale: 1.0,
+            linear_damping: 0.0,
+            restitution: 0.0,
+            friction: 0.5,
+            world_shape: shape.clone(),
+            shape,
+            filter: CollisionFilter::default(),
+            is_sensor: false,
+            is_bullet: false,
+            entity: 0,
+
+            body_type,
+            dynamic_mass: 1.0,
+            inv_mass: if body_type == BodyType::Dynamic { 1.0 } else { 0.0 },
+            force: Vec2::ZERO,
+            is_sleeping: false,
+            sleep_timer: 0.0,
+            proxy: None,
+        }
+    }
+
+    pub fn dynamic(shape: impl Into<Shape2D>) -> Self {
+        Self::new(BodyType::Dynamic, shape.into())
+    }
+
+    pub fn kinematic(shape: impl Into<Shape2D>) -> Self {
+        Self::new(BodyType::Kinematic, shape.into())
+    }
+
+    pub fn fixed(shape: impl Into<Shape2D>) -> Self {
+        Self::new(BodyType::Static, shape.into())
+    }
+
+    pub fn with_position(mut self, position: Vec2) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_rotation(mut self, rotation: f32) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn with_velocity(mut self, velocity: Vec2) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// only has an effect on dynamic bodies
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.set_mass(mass);
+        self
+    }
+
+    pub fn with_gravity_scale(mut self, gravity_scale: f32) -> Self {
+        self.gravity_scale = gravity_scale;
+        self
+    }
+
+    pub fn with_linear_damping(mut self, linear_damping: f32) -> Self {
+        self.linear_damping = linear_damping;
+        self
+    }
+
+    pub fn with_restitution(mut self, restitution: f32) -> Self {
+        self.restitution = restitution;
+        self
+    }
+
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    pub fn with_filter(mut self, filter: CollisionFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn with_layers(mut self, membership: CollisionLayers, mask: CollisionLayers) -> Self {
+        self.filter = CollisionFilter::new(membership, mask);
+        self
+    }
+
+    pub fn as_sensor(mut self) -> Self {
+        self.is_sensor = true;
+        self
+    }
+
+    pub fn as_bullet(mut self) -> Self {
+        self.is_bullet = true;
+        self
+    }
+
+    pub fn with_entity(mut self, entity: u64) -> Self {
+        self.entity = entity;
+        self
+    }
+
+    /// takes the 2d translation and the rotation around z
+    pub fn with_transform(mut self, t: &Transform) -> Self {
+        self.set_transform(t);
+        self
+    }
+}
+
+impl RigidBody {
+    pub fn body_type(&self) -> BodyType {
+        self.body_type
+    }
+
+    /// recomputes the mass - a body that becomes dynamic gets the mass it was given before, static bodies stop moving
+    pub fn set_body_type(&mut self, body_type: BodyType) {
+        self.body_type = body_type;
+        self.set_mass(self.dynamic_mass);
+        if body_type == BodyType::Static {
+            self.velocity = Vec2::ZERO;
+            self.force = Vec2::ZERO;
+        }
+        self.wake_up();
+    }
+
+    pub fn is_dynamic(&self) -> bool {
+        self.body_type == BodyType::Dynamic
+    }
+
+    pub fn is_sleeping(&self) -> bool {
+        self.is_sleeping
+    }
+
+    pub fn wake_up(&mut self) {
+        self.is_sleeping = false;
+        self.sleep_timer = 0.0;
+    }
+
+    pub fn mass(&self) -> f32 {
+        if self.inv_mass > 0.0 { 1.0 / self.inv_mass } else { f32::INFINITY }
+    }
+
+    /// kept for when the body becomes dynamic, if it isn't yet
+    pub fn set_mass(&mut self, mass: f32) {
+        self.dynamic_mass = mass;
+        self.inv_mass = if self.is_dynamic() && mass > 0.0 { 1.0 / mass } else { 0.0 };
+    }
+
+    pub fn inv_mass(&self) -> f32 {
+        self.inv_mass
+    }
+
+    /// accumulated until the next step
+    pub fn apply_force(&mut self, force: Vec2) {
+        self.force += force;
+        self.wake_up();
+    }
+
+    pub fn apply_impulse(&mut self, impulse: Vec2) {
+        self.velocity += impulse * self.inv_mass;
+        self.wake_up();
+    }
+
+    /// world-space collider
+    pub fn world_shape(&self) -> &Shape2D {
+        &self.world_shape
+    }
+
+    pub fn bounds(&self) -> AABB2D {
+        self.world_shape.bounds()
+    }
+
+    pub fn model_mat(&self) -> Mat4 {
+        self.model_mat_at(self.position)
+    }
+
+    fn model_mat_at(&self, position: Vec2) -> Mat4 {
+        Mat4::from_rotation_translation(Quat::from_rotation_z(self.rotation), Vec3::from((position, 0.0)))
+    }
+
+    pub fn set_transform(&mut self, t: &Transform) {
+        self.position = t.translation.truncate();
+        self.rotation = t.rotation.to_euler(glam::EulerRot::XYZ).2;
+    }
+
+    /// writes the pose back, z-position and scale are kept
+    pub fn apply_to_transform(&self, t: &mut Transform) {
+        t.translation.x = self.position.x;
+        t.translation.y = self.position.y;
+        t.rotation = Quat::from_rotation_z(self.rotation);
+    }
+
+    pub(crate) fn update_world_shape(&mut self) {
+        self.world_shape = self.shape_at(self.position);
+    }
+
+    /// world-space collider, if the body was at `position`
+    pub(crate) fn shape_at(&self, position: Vec2) -> Shape2D {
+        self.shape.transform_mat(&self.model_mat_at(position))
+    }
+}
@@ -0,0 +1,37 @@
+use super::BodyHandle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactEventKind {
+    /// the bodies started touching during this step
+    Enter,
+    /// the bodies were already touching during the last step
+    Stay,
+    /// the bodies stopped touching, or one of them was removed
+    Exit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContactEvent {
+    pub kind: ContactEventKind,
+    pub a: BodyHandle,
+    pub b: BodyHandle,
+    pub entity_a: u64,
+    pub entity_b: u64,
+    /// at least one of the bodies is a sensor
+    pub is_sensor: bool,
+}
+
+impl ContactEvent {
+    pub fn involves(&self, entity: u64) -> bool {
+        self.entity_a == entity || self.entity_b == entity
+    }
+
+    /// the entity on the other side of the contact
+    pub fn other(&self, entity: u64) -> Option<u64> {
+        match entity {
+            e if e == self.entity_a => Some(self.entity_b),
+            e if e == self.entity_b => Some(self.entity_a),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+mod body;
+pub use body::*;
+
+mod events;
+pub use events::*;
+
+mod query;
+pub use query::*;
+
+#[allow(clippy::module_inception)]
+mod world;
+pub use world::*;
@@ -0,0 +1,265 @@
+use glam::Vec2;
+
+use crate::collision::{CollisionLayers, ProxyId, Ray, RayHit, Shape2D};
+
+use super::{BodyHandle, PhysicsWorld, RigidBody};
+
+
+
+// ----------------------------------------------------------------------------
+// filter
+// ----------------------------------------------------------------------------
+
+/// selects the bodies a query can hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueryFilter {
+    /// only bodies that are a member of one of these layers are considered
+    pub mask: CollisionLayers,
+    pub include_sensors: bool,
+    /// usually the body that is doing the query
+    pub exclude: Option<BodyHandle>,
+}
+
+impl QueryFilter {
+    pub fn new(mask: CollisionLayers) -> Self {
+        Self {
+            mask,
+            include_sensors: false,
+            exclude: None,
+        }
+    }
+
+    pub fn with_sensors(mut self) -> Self {
+        self.include_sensors = true;
+        self
+    }
+
+    pub fn excluding(mut self, handle: BodyHandle) -> Self {
+        self.exclude = Some(handle);
+        self
+    }
+
+    pub fn test(&self, handle: BodyHandle, body: &RigidBody) -> bool {
+        self.exclude != Some(handle)
+            && (self.include_sensors || !body.is_sensor)
+            && self.mask.intersects(body.filter.membership)
+    }
+}
+
+impl Default for QueryFilter {
+    fn default() -> Self {
+        Self::new(CollisionLayers::ALL)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// hit
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QueryHit {
+    pub body: BodyHandle,
+    pub entity: u64,
+    /// how far along the ray or motion the hit is, in [0, 1]
+    pub fraction: f32,
+    pub point: Vec2,
+    /// unit vector, points away from the body that was hit
+    pub normal: Vec2,
+}
+
+impl QueryHit {
+    fn new(body: BodyHandle, entity: u64, hit: RayHit) -> Self {
+        Self {
+            body,
+            entity,
+            fraction: hit.fraction,
+            point: hit.point,
+            normal: hit.normal,
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// queries
+// ----------------------------------------------------------------------------
+
+/// queries see the bodies as they were after the last step, changes made through `body_mut` are not picked up yet
+impl PhysicsWorld {
+    /// the fraction of the hit is relative to `max_distance`
+    pub fn cast_ray(&self, origin: Vec2, direction: Vec2, max_distance: f32, filter: &QueryFilter) -> Option<QueryHit> {
+        self.cast(&Ray::with_length(origin, direction, max_distance), filter)
+    }
+
+    pub fn cast_segment(&self, a: Vec2, b: Vec2, filter: &QueryFilter) -> Option<QueryHit> {
+        self.cast(&Ray::segment(a, b), filter)
+    }
+
+    /// first body the world-space `shape` runs into when it is moved by `motion`
+    pub fn cast_shape(&self, shape: &Shape2D, motion: Vec2, filter: &QueryFilter) -> Option<QueryHit> {
+        let bounds = shape.bounds();
+        let swept = bounds.merge(&bounds.translate(motion));
+
+        let mut best: Option<QueryHit> = None;
+        self.broadphase.query(&swept, |proxy| {
+            let Some((handle, body)) = self.proxy_body(proxy, filter) else {
+                return;
+            };
+
+            if let Some(hit) = shape.cast_shape(motion, body.world_shape()) {
+                if best.is_none_or(|b| hit.fraction < b.fraction) {
+                    best = Some(QueryHit::new(handle, body.entity, hit));
+                }
+            }
+        });
+
+        best
+    }
+
+    /// calls `f` for every body that contains `p`
+    pub fn query_point(&self, p: Vec2, filter: &QueryFilter, mut f: impl FnMut(BodyHandle)) {
+        self.broadphase.query_point(p, |proxy| {
+            if let Some((handle, body)) = self.proxy_body(proxy, filter) {
+                if body.world_shape().contains_point(p) {
+                    f(handle);
+                }
+            }
+        });
+    }
+
+    /// calls `f` for every body that overlaps the world-space `shape`
+    pub fn query_shape(&self, shape: &Shape2D, filter: &QueryFilter, mut f: impl FnMut(BodyHandle)) {
+        self.broadphase.query(&shape.bounds(), |proxy| {
+            if let Some((handle, body)) = self.proxy_body(proxy, filter) {
+                if shape.does_overlap(body.world_shape()) {
+                    f(handle);
+                }
+            }
+        });
+    }
+
+    fn cast(&self, ray: &Ray, filter: &QueryFilter) -> Option<QueryHit> {
+        let mut best: Option<QueryHit> = None;
+
+        // proxies spanning several cells are reported multiple times, testing them again doesn't change the result
+        self.broadphase.query_segment(ray.origin, ray.end(), |proxy| {
+            let Some((handle, body)) = self.proxy_body(proxy, filter) else {
+                return;
+            };
+
+            if let Some(hit) = body.world_shape().cast_ray(ray) {
+                if best.is_none_or(|b| hit.fraction < b.fraction) {
+                    best = Some(QueryHit::new(handle, body.entity, hit));
+                }
+            }
+        });
+
+        best
+    }
+
+    fn proxy_body(&self, proxy: ProxyId, filter: &QueryFilter) -> Option<(BodyHandle, &RigidBody)> {
+        let handle = self.proxy_bodies[proxy.idx()]?;
+        let body = self.body(handle)?;
+        filter.test(handle, body).then_some((handle, body))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{Capsule, Circle, OrientedBox, AABB2D};
+    use crate::PhysicsConfig;
+
+    const WALL: CollisionLayers = CollisionLayers::layer(1);
+
+    fn world() -> (PhysicsWorld, BodyHandle, BodyHandle, BodyHandle) {
+        let mut world = PhysicsWorld::new(PhysicsConfig::new(0.0));
+
+        // ground slope, a wall and a coin
+        let slope = world.add_body(
+            RigidBody::fixed(OrientedBox::new(Vec2::ZERO, glam::vec2(20.0, 1.0), 0.3))
+                .with_entity(1)
+        );
+        let wall = world.add_body(
+            RigidBody::fixed(AABB2D::from_center_half_extents(glam::vec2(8.0, 6.0), glam::vec2(0.5, 4.0)))
+                .with_layers(WALL, CollisionLayers::ALL)
+                .with_entity(2)
+        );
+        let coin = world.add_body(
+            RigidBody::fixed(Circle::new(Vec2::ZERO, 0.5))
+                .with_position(glam::vec2(4.0, 6.0))
+                .as_sensor()
+                .with_entity(3)
+        );
+
+        (world, slope, wall, coin)
+    }
+
+    #[test]
+    fn test_ray() {
+        let (world, slope, wall, coin) = world();
+
+        // straight down onto the slope
+        let hit = world.cast_ray(glam::vec2(0.0, 10.0), -Vec2::Y, 100.0, &QueryFilter::default()).unwrap();
+        assert_eq!(hit.body, slope);
+        assert_eq!(hit.entity, 1);
+        assert!((hit.normal - Vec2::from_angle(0.3).perp()).length() < 1e-4);
+        assert!((hit.fraction * 100.0 - (10.0 - hit.point.y)).abs() < 1e-3);
+
+        // sensors are ignored by default
+        let filter = QueryFilter::default();
+        let hit = world.cast_segment(glam::vec2(-5.0, 6.0), glam::vec2(12.0, 6.0), &filter).unwrap();
+        assert_eq!(hit.body, wall);
+        assert!((hit.point.x - 7.5).abs() < 1e-4);
+
+        let hit = world.cast_segment(glam::vec2(-5.0, 6.0), glam::vec2(12.0, 6.0), &filter.with_sensors()).unwrap();
+        assert_eq!(hit.body, coin);
+
+        // layers and excluded bodies
+        let hit = world.cast_segment(glam::vec2(-5.0, 6.0), glam::vec2(12.0, 6.0), &QueryFilter::new(CollisionLayers::DEFAULT));
+        assert!(hit.is_none());
+        let hit = world.cast_segment(glam::vec2(12.0, 6.0), glam::vec2(-5.0, 6.0), &filter.excluding(wall));
+        assert!(hit.is_none());
+    }
+
+    #[test]
+    fn test_shape_cast() {
+        let (world, slope, _, _) = world();
+
+        // is a capsule standing on the slope grounded?
+        let feet = Shape2D::from(Capsule::vertical(glam::vec2(-3.0, 2.0), 2.0, 0.4));
+        let hit = world.cast_shape(&feet, glam::vec2(0.0, -5.0), &QueryFilter::default()).unwrap();
+        assert_eq!(hit.body, slope);
+        assert!(hit.normal.y > 0.9);
+
+        let ground_distance = 5.0 * hit.fraction;
+        let moved = Shape2D::from(Capsule::vertical(glam::vec2(-3.0, 2.0 - ground_distance + 0.01), 2.0, 0.4));
+        let mut touching = Vec::new();
+        world.query_shape(&moved, &QueryFilter::default(), |h| touching.push(h));
+        assert!(touching.is_empty());
+    }
+
+    #[test]
+    fn test_point() {
+        let (world, _, wall, coin) = world();
+
+        let mut found = Vec::new();
+        world.query_point(glam::vec2(8.2, 8.0), &QueryFilter::default(), |h| found.push(h));
+        assert_eq!(found, vec![wall]);
+
+        found.clear();
+        world.query_point(glam::vec2(4.2, 6.2), &QueryFilter::default().with_sensors(), |h| found.push(h));
+        assert_eq!(found, vec![coin]);
+
+        found.clear();
+        world.query_point(glam::vec2(4.4, 6.4), &QueryFilter::default().with_sensors(), |h| found.push(h));
+        assert!(found.is_empty());
+    }
+}
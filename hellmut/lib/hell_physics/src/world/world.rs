@@ -0,0 +1,805 @@
+use glam::Vec2;
+
+use crate::collision::{self, Contact, ProxyId, RayHit, Shape2D, SpatialHash};
+use crate::PhysicsConfig;
+
+use super::{BodyHandle, BodyType, ContactEvent, ContactEventKind, RigidBody};
+
+
+
+// ----------------------------------------------------------------------------
+// contacts
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy)]
+pub struct ContactPair {
+    pub a: BodyHandle,
+    pub b: BodyHandle,
+    /// normal points from `a` to `b`
+    pub contact: Contact,
+}
+
+
+
+// ----------------------------------------------------------------------------
+// world
+// ----------------------------------------------------------------------------
+
+struct BodySlot {
+    generation: u32,
+    body: Option<RigidBody>,
+}
+
+pub struct PhysicsWorld {
+    config: PhysicsConfig,
+    slots: Vec<BodySlot>,
+    free: Vec<u32>,
+    pub(super) broadphase: SpatialHash,
+    /// maps broadphase proxies back to their bodies
+    pub(super) proxy_bodies: Vec<Option<BodyHandle>>,
+    accumulator: f32,
+    /// sorted pairs of bodies that touched during the last step
+    touching: Vec<(BodyHandle, BodyHandle)>,
+    events: Vec<ContactEvent>,
+    /// number of `events` that were emitted by the last `step`, the rest was emitted between steps
+    step_event_count: usize,
+
+    // scratch
+    pairs: Vec<(ProxyId, ProxyId)>,
+    contacts: Vec<ContactPair>,
+    curr_touching: Vec<(BodyHandle, BodyHandle)>,
+}
+
+impl PhysicsWorld {
+    /// contacts slower than this don't bounce, otherwise resting bodies would never come to rest
+    const RESTITUTION_THRESHOLD: f32 = 1.0;
+    /// penetration that is allowed to remain, avoids jitter of resting contacts
+    const PENETRATION_SLOP: f32 = 0.005;
+    const POSITION_CORRECTION: f32 = 0.8;
+
+    pub fn new(config: PhysicsConfig) -> Self {
+        let broadphase = SpatialHash::new(config.broadphase_cell_size);
+
+        Self {
+            config,
+            slots: Vec::new(),
+            free: Vec::new(),
+            broadphase,
+            proxy_bodies: Vec::new(),
+            accumulator: 0.0,
+            touching: Vec::new(),
+            events: Vec::new(),
+            step_event_count: 0,
+
+            pairs: Vec::new(),
+            contacts: Vec::new(),
+            curr_touching: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &PhysicsConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut PhysicsConfig {
+        &mut self.config
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// solid contacts found during the last fixed step, sensors are not included
+    pub fn contacts(&self) -> &[ContactPair] {
+        &self.contacts
+    }
+
+    /// events of all fixed steps of the last `step` and of bodies that were removed after it, until they are drained
+    pub fn events(&self) -> &[ContactEvent] {
+        &self.events
+    }
+
+    pub fn drain_events(&mut self) -> impl Iterator<Item = ContactEvent> + '_ {
+        self.step_event_count = 0;
+        self.events.drain(..)
+    }
+
+    /// how far the simulation is between the last and the next fixed step - useful to interpolate rendered transforms
+    pub fn interpolation_alpha(&self) -> f32 {
+        self.accumulator / self.config.fixed_time_step
+    }
+}
+
+impl Default for PhysicsWorld {
+    fn default() -> Self {
+        Self::new(PhysicsConfig::default())
+    }
+}
+
+// bodies
+// ------
+impl PhysicsWorld {
+    pub fn add_body(&mut self, mut body: RigidBody) -> BodyHandle {
+        body.update_world_shape();
+
+        let handle = match self.free.pop() {
+            Some(idx) => BodyHandle { idx, generation: self.slots[idx as usize].generation },
+            None => {
+                self.slots.push(BodySlot { generation: 0, body: None });
+                BodyHandle { idx: (self.slots.len() - 1) as u32, generation: 0 }
+            }
+        };
+
+        let proxy = self.broadphase.insert(body.bounds());
+        if self.proxy_bodies.len() <= proxy.idx() {
+            self.proxy_bodies.resize(proxy.idx() + 1, None);
+        }
+        self.proxy_bodies[proxy.idx()] = Some(handle);
+        body.proxy = Some(proxy);
+
+        self.slots[handle.idx()].body = Some(body);
+        handle
+    }
+
+    pub fn remove_body(&mut self, handle: BodyHandle) -> Option<RigidBody> {
+        let slot = self.slots.get_mut(handle.idx())?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+
+        let mut body = slot.body.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.idx);
+
+        if let Some(proxy) = body.proxy.take() {
+            self.broadphase.remove(proxy);
+            self.proxy_bodies[proxy.idx()] = None;
+        }
+
+        // whatever the body touched, it doesn't anymore
+        let mut touching = std::mem::take(&mut self.touching);
+        touching.retain(|(a, b)| {
+            if *a != handle && *b != handle {
+                return true;
+            }
+
+            let other = if *a == handle { *b } else { *a };
+            let other_body = self.body_mut(other);
+            let is_sensor = body.is_sensor || other_body.as_ref().is_some_and(|b| b.is_sensor);
+            let other_entity = other_body.as_ref().map_or(0, |b| b.entity);
+
+            // bodies resting on the removed one have to fall again
+            if let Some(other_body) = other_body.filter(|b| b.is_dynamic()) {
+                other_body.wake_up();
+            }
+            let (entity_a, entity_b) = if *a == handle { (body.entity, other_entity) } else { (other_entity, body.entity) };
+
+            self.events.push(ContactEvent { kind: ContactEventKind::Exit, a: *a, b: *b, entity_a, entity_b, is_sensor });
+            false
+        });
+        self.touching = touching;
+
+        Some(body)
+    }
+
+    pub fn body(&self, handle: BodyHandle) -> Option<&RigidBody> {
+        let slot = self.slots.get(handle.idx())?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.body.as_ref()
+    }
+
+    /// changes to the pose or shape are picked up by the next step
+    pub fn body_mut(&mut self, handle: BodyHandle) -> Option<&mut RigidBody> {
+        let slot = self.slots.get_mut(handle.idx())?;
+        if slot.generation != handle.generation {
+            return None;
+        }
+        slot.body.as_mut()
+    }
+
+    pub fn bodies(&self) -> impl Iterator<Item = (BodyHandle, &RigidBody)> {
+        self.slots.iter()
+            .enumerate()
+            .filter_map(|(idx, slot)| {
+                let handle = BodyHandle { idx: idx as u32, generation: slot.generation };
+                slot.body.as_ref().map(|b| (handle, b))
+            })
+    }
+
+    fn bodies_mut(&mut self) -> impl Iterator<Item = &mut RigidBody> {
+        self.slots.iter_mut().filter_map(|s| s.body.as_mut())
+    }
+
+    fn body_pair_mut(&mut self, a: BodyHandle, b: BodyHandle) -> (&mut RigidBody, &mut RigidBody) {
+        debug_assert_ne!(a.idx, b.idx);
+        let (lo, hi) = (a.idx().min(b.idx()), a.idx().max(b.idx()));
+        let (left, right) = self.slots.split_at_mut(hi);
+        let lo_body = left[lo].body.as_mut().expect("invalid body handle");
+        let hi_body = right[0].body.as_mut().expect("invalid body handle");
+
+        if a.idx() < b.idx() { (lo_body, hi_body) } else { (hi_body, lo_body) }
+    }
+}
+
+// simulation
+// ----------
+impl PhysicsWorld {
+    /// advances the world by `delta_time` in fixed steps, returns the number of steps taken
+    pub fn step(&mut self, delta_time: f32) -> u32 {
+        let step = self.config.fixed_time_step;
+        self.accumulator += delta_time;
+        // events of removed bodies are kept for one more step, they happened after the last one
+        self.events.drain(..self.step_event_count);
+
+        let mut count = 0;
+        while self.accumulator >= step && count < self.config.max_sub_steps {
+            self.fixed_step(step);
+            self.accumulator -= step;
+            count += 1;
+        }
+
+        // drop the time we couldn't catch up with
+        if count == self.config.max_sub_steps {
+            self.accumulator = self.accumulator.min(step);
+        }
+
+        self.step_event_count = self.events.len();
+        count
+    }
+
+    pub fn fixed_step(&mut self, h: f32) {
+        self.integrate(h);
+        self.sweep_bullets(h);
+        self.sync_broadphase();
+        self.find_contacts();
+        self.emit_events();
+        self.wake_touched_bodies();
+
+        for _ in 0..self.config.velocity_iterations {
+            for idx in 0..self.contacts.len() {
+                self.resolve_velocity(self.contacts[idx]);
+            }
+        }
+
+        for idx in 0..self.contacts.len() {
+            self.correct_position(self.contacts[idx]);
+        }
+
+        self.update_sleeping(h);
+        self.sync_broadphase();
+    }
+
+    fn integrate(&mut self, h: f32) {
+        let gravity = self.config.gravity();
+
+        for body in self.bodies_mut() {
+            match body.body_type {
+                BodyType::Dynamic if !body.is_sleeping => {
+                    // semi-implicit euler: velocity first, then position with the new velocity
+                    let acceleration = gravity * body.gravity_scale + body.force * body.inv_mass;
+                    body.velocity += acceleration * h;
+                    body.velocity /= 1.0 + body.linear_damping * h;
+                    body.position += body.velocity * h;
+                }
+                BodyType::Kinematic => {
+                    body.position += body.velocity * h;
+                }
+                _ => {}
+            }
+
+            body.force = Vec2::ZERO;
+        }
+    }
+
+    /// moves bullets back to their first time of impact - the contact is then resolved like any other one
+    ///
+    /// the other bodies are swept against as they were at the start of the step.
+    fn sweep_bullets(&mut self, h: f32) {
+        for idx in 0..self.slots.len() {
+            let Some(body) = self.slots[idx].body.as_ref() else {
+                continue;
+            };
+            if !body.is_bullet || !body.is_dynamic() || body.is_sleeping || body.is_sensor {
+                continue;
+            }
+
+            let handle = BodyHandle { idx: idx as u32, generation: self.slots[idx].generation };
+            let motion = body.velocity * h;
+            let start = body.shape_at(body.position - motion);
+
+            // already touching at the start of the step - the discrete contacts take care of that
+            let Some(hit) = self.find_time_of_impact(handle, body, &start, motion).filter(|hit| hit.fraction > 0.0) else {
+                continue;
+            };
+
+            // end up slightly inside the other body, so that the contact is found during this step
+            let body = self.slots[idx].body.as_mut().expect("body was removed while sweeping");
+            body.position += motion * (hit.fraction - 1.0) - hit.normal * Self::PENETRATION_SLOP * 0.5;
+        }
+    }
+
+    fn find_time_of_impact(&self, handle: BodyHandle, body: &RigidBody, start: &Shape2D, motion: Vec2) -> Option<RayHit> {
+        let bounds = start.bounds();
+        let swept = bounds.merge(&bounds.translate(motion));
+
+        let mut best: Option<RayHit> = None;
+        self.broadphase.query(&swept, |proxy| {
+            let Some(other_handle) = self.proxy_bodies[proxy.idx()] else {
+                return;
+            };
+            let Some(other) = self.body(other_handle) else {
+                return;
+            };
+            if other_handle == handle || other.is_sensor || !body.filter.can_collide(&other.filter) {
+                return;
+            }
+
+            if let Some(hit) = start.cast_shape(motion, other.world_shape()) {
+                if best.is_none_or(|b| hit.fraction < b.fraction) {
+                    best = Some(hit);
+                }
+            }
+        });
+
+        best
+    }
+
+    fn sync_broadphase(&mut self) {
+        for slot in &mut self.slots {
+            let Some(body) = slot.body.as_mut() else {
+                continue;
+            };
+
+            body.update_world_shape();
+            if let Some(proxy) = body.proxy {
+                self.broadphase.update(proxy, body.bounds());
+            }
+        }
+    }
+
+    fn find_contacts(&mut self) {
+        self.contacts.clear();
+        self.curr_touching.clear();
+        self.broadphase.find_pairs(&mut self.pairs);
+
+        for (pa, pb) in &self.pairs {
+            let (Some(a), Some(b)) = (self.proxy_bodies[pa.idx()], self.proxy_bodies[pb.idx()]) else {
+                continue;
+            };
+            let (Some(body_a), Some(body_b)) = (self.body(a), self.body(b)) else {
+                continue;
+            };
+
+            if !body_a.filter.can_collide(&body_b.filter) {
+                continue;
+            }
+
+            // sensors also detect kinematic and static bodies, solid contacts need at least one dynamic body
+            let is_sensor = body_a.is_sensor || body_b.is_sensor;
+            let is_solid = !is_sensor && (body_a.is_dynamic() || body_b.is_dynamic());
+            let are_both_static = body_a.body_type == BodyType::Static && body_b.body_type == BodyType::Static;
+            if are_both_static || (!is_sensor && !is_solid) {
+                continue;
+            }
+
+            if let Some(contact) = collision::collide(body_a.world_shape(), body_b.world_shape()) {
+                self.curr_touching.push((a.min(b), a.max(b)));
+                if is_solid {
+                    self.contacts.push(ContactPair { a, b, contact });
+                }
+            }
+        }
+
+        self.curr_touching.sort_unstable();
+    }
+
+    fn emit_events(&mut self) {
+        let curr = std::mem::take(&mut self.curr_touching);
+        let prev = std::mem::replace(&mut self.touching, curr);
+
+        // both lists are sorted, so they can be merged
+        let (mut i, mut j) = (0, 0);
+        while i < self.touching.len() || j < prev.len() {
+            let curr_pair = self.touching.get(i).copied();
+            let prev_pair = prev.get(j).copied();
+
+            let (kind, (a, b)) = match (curr_pair, prev_pair) {
+                (Some(c), Some(p)) if c == p => { i += 1; j += 1; (ContactEventKind::Stay, c) }
+                (Some(c), Some(p)) if c < p  => { i += 1; (ContactEventKind::Enter, c) }
+                (Some(c), None)              => { i += 1; (ContactEventKind::Enter, c) }
+                (_, Some(p))                 => { j += 1; (ContactEventKind::Exit, p) }
+                (None, None)                 => unreachable!(),
+            };
+
+            let body_a = self.body(a);
+            let body_b = self.body(b);
+            self.events.push(ContactEvent {
+                kind,
+                a,
+                b,
+                entity_a: body_a.map_or(0, |b| b.entity),
+                entity_b: body_b.map_or(0, |b| b.entity),
+                is_sensor: body_a.is_some_and(|b| b.is_sensor) || body_b.is_some_and(|b| b.is_sensor),
+            });
+        }
+
+        self.curr_touching = prev;
+    }
+
+    fn wake_touched_bodies(&mut self) {
+        for idx in 0..self.contacts.len() {
+            let ContactPair { a, b, .. } = self.contacts[idx];
+            let (body_a, body_b) = self.body_pair_mut(a, b);
+
+            let a_is_awake = !body_a.is_sleeping && body_a.body_type != BodyType::Static;
+            let b_is_awake = !body_b.is_sleeping && body_b.body_type != BodyType::Static;
+            if a_is_awake && body_b.is_dynamic() {
+                body_b.wake_up();
+            }
+            if b_is_awake && body_a.is_dynamic() {
+                body_a.wake_up();
+            }
+        }
+    }
+
+    fn resolve_velocity(&mut self, pair: ContactPair) {
+        let (a, b) = self.body_pair_mut(pair.a, pair.b);
+        let inv_mass_sum = a.inv_mass + b.inv_mass;
+        if inv_mass_sum <= 0.0 {
+            return;
+        }
+
+        let normal = pair.contact.normal;
+        let relative = b.velocity - a.velocity;
+        let normal_speed = relative.dot(normal);
+        if normal_speed >= 0.0 {
+            return;
+        }
+
+        // normal impulse
+        let restitution = if -normal_speed > Self::RESTITUTION_THRESHOLD { a.restitution.max(b.restitution) } else { 0.0 };
+        let j = -(1.0 + restitution) * normal_speed / inv_mass_sum;
+        a.velocity -= normal * j * a.inv_mass;
+        b.velocity += normal * j * b.inv_mass;
+
+        // friction impulse, clamped to the coulomb cone
+        let relative = b.velocity - a.velocity;
+        let tangent = (relative - normal * relative.dot(normal)).normalize_or_zero();
+        if tangent == Vec2::ZERO {
+            return;
+        }
+
+        let friction = (a.friction * b.friction).sqrt();
+        let jt = (-relative.dot(tangent) / inv_mass_sum).clamp(-j * friction, j * friction);
+        a.velocity -= tangent * jt * a.inv_mass;
+        b.velocity += tangent * jt * b.inv_mass;
+    }
+
+    fn correct_position(&mut self, pair: ContactPair) {
+        let (a, b) = self.body_pair_mut(pair.a, pair.b);
+        let inv_mass_sum = a.inv_mass + b.inv_mass;
+        if inv_mass_sum <= 0.0 {
+            return;
+        }
+
+        let depth = (pair.contact.depth - Self::PENETRATION_SLOP).max(0.0);
+        let correction = pair.contact.normal * (depth / inv_mass_sum * Self::POSITION_CORRECTION);
+        a.position -= correction * a.inv_mass;
+        b.position += correction * b.inv_mass;
+    }
+
+    fn update_sleeping(&mut self, h: f32) {
+        let sleep_velocity_sq = self.config.sleep_velocity * self.config.sleep_velocity;
+        let sleep_time = self.config.sleep_time;
+
+        for body in self.bodies_mut() {
+            if !body.is_dynamic() || body.is_sleeping {
+                continue;
+            }
+
+            if body.velocity.length_squared() > sleep_velocity_sq {
+                body.sleep_timer = 0.0;
+                continue;
+            }
+
+            body.sleep_timer += h;
+            if body.sleep_timer >= sleep_time {
+                body.is_sleeping = true;
+                body.velocity = Vec2::ZERO;
+            }
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::{Circle, AABB2D};
+
+    fn world_with_ground() -> PhysicsWorld {
+        let mut world = PhysicsWorld::default();
+        world.add_body(RigidBody::fixed(AABB2D::new(glam::vec2(-50.0, -1.0), glam::vec2(50.0, 0.0))));
+        world
+    }
+
+    fn simulate(world: &mut PhysicsWorld, seconds: f32) {
+        let steps = (seconds / world.config().fixed_time_step).round() as usize;
+        for _ in 0..steps {
+            world.fixed_step(world.config().fixed_time_step);
+        }
+    }
+
+    #[test]
+    fn test_falls_and_rests_on_ground() {
+        let mut world = world_with_ground();
+        let body = world.add_body(
+            RigidBody::dynamic(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)))
+                .with_position(glam::vec2(0.0, 5.0))
+        );
+
+        simulate(&mut world, 3.0);
+
+        let body = world.body(body).unwrap();
+        assert!((body.position.y - 0.5).abs() < 0.02, "y: {}", body.position.y);
+        assert!(body.is_sleeping());
+    }
+
+    #[test]
+    fn test_change_body_type() {
+        let mut world = world_with_ground();
+        let crate_box = world.add_body(
+            RigidBody::fixed(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)))
+                .with_position(glam::vec2(0.0, 5.0))
+                .with_mass(4.0)
+        );
+        assert_eq!(world.body(crate_box).unwrap().inv_mass(), 0.0);
+
+        // released boxes fall with the mass they were given
+        world.body_mut(crate_box).unwrap().set_body_type(BodyType::Dynamic);
+        assert_eq!(world.body(crate_box).unwrap().inv_mass(), 0.25);
+        simulate(&mut world, 0.5);
+        assert!(world.body(crate_box).unwrap().position.y < 5.0);
+
+        let body = world.body_mut(crate_box).unwrap();
+        body.set_body_type(BodyType::Static);
+        assert_eq!((body.inv_mass(), body.velocity), (0.0, Vec2::ZERO));
+    }
+
+    #[test]
+    fn test_remove_wakes_touching_bodies() {
+        let mut world = PhysicsWorld::default();
+        let ground = world.add_body(RigidBody::fixed(AABB2D::new(glam::vec2(-50.0, -1.0), glam::vec2(50.0, 0.0))));
+        let body = world.add_body(
+            RigidBody::dynamic(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)))
+                .with_position(glam::vec2(0.0, 0.5))
+        );
+
+        simulate(&mut world, 2.0);
+        assert!(world.body(body).unwrap().is_sleeping());
+
+        world.remove_body(ground);
+        simulate(&mut world, 0.5);
+
+        let body = world.body(body).unwrap();
+        assert!(!body.is_sleeping());
+        assert!(body.position.y < 0.0, "y: {}", body.position.y);
+    }
+
+    #[test]
+    fn test_restitution() {
+        let mut world = world_with_ground();
+        let ball = world.add_body(
+            RigidBody::dynamic(Circle::new(Vec2::ZERO, 0.5))
+                .with_position(glam::vec2(0.0, 3.0))
+                .with_restitution(0.8)
+        );
+
+        let mut max_upwards = 0.0_f32;
+        for _ in 0..120 {
+            world.fixed_step(1.0 / 60.0);
+            max_upwards = max_upwards.max(world.body(ball).unwrap().velocity.y);
+        }
+
+        assert!(max_upwards > 4.0, "max upwards velocity: {}", max_upwards);
+    }
+
+    #[test]
+    fn test_friction() {
+        let slide = |friction: f32| {
+            let mut world = world_with_ground();
+            let body = world.add_body(
+                RigidBody::dynamic(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)))
+                    .with_position(glam::vec2(0.0, 0.5))
+                    .with_velocity(glam::vec2(5.0, 0.0))
+                    .with_friction(friction)
+            );
+            simulate(&mut world, 1.0);
+            world.body(body).unwrap().velocity.x
+        };
+
+        assert!(slide(0.0) > 4.9);
+        assert!(slide(1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_kinematic_pushes_dynamic() {
+        let mut world = world_with_ground();
+        let pusher = world.add_body(
+            RigidBody::kinematic(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)))
+                .with_position(glam::vec2(-2.0, 0.5))
+                .with_velocity(glam::vec2(2.0, 0.0))
+        );
+        let crate_body = world.add_body(
+            RigidBody::dynamic(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(0.5)))
+                .with_position(glam::vec2(0.0, 0.5))
+                .with_friction(0.0)
+        );
+
+        simulate(&mut world, 2.0);
+
+        let pusher = world.body(pusher).unwrap();
+        let crate_body = world.body(crate_body).unwrap();
+        assert!((pusher.position.x - 2.0).abs() < 0.01);
+        assert!(crate_body.position.x > pusher.position.x + 0.9);
+    }
+
+    #[test]
+    fn test_fixed_steps() {
+        let mut world = PhysicsWorld::default();
+        assert_eq!(world.step(1.0 / 30.0 + 0.001), 2);
+        assert_eq!(world.step(0.0), 0);
+        // huge frames are capped
+        assert_eq!(world.step(10.0), world.config().max_sub_steps);
+        assert!(world.interpolation_alpha() <= 1.0);
+    }
+
+    #[test]
+    fn test_stale_handles() {
+        let mut world = PhysicsWorld::default();
+        let a = world.add_body(RigidBody::fixed(Circle::new(Vec2::ZERO, 1.0)));
+        assert!(world.remove_body(a).is_some());
+
+        let b = world.add_body(RigidBody::fixed(Circle::new(Vec2::ZERO, 1.0)));
+        assert_eq!(a.idx(), b.idx());
+        assert!(world.body(a).is_none());
+        assert!(world.body(b).is_some());
+        assert_eq!(world.len(), 1);
+    }
+
+    #[test]
+    fn test_sensor_events() {
+        let mut world = PhysicsWorld::new(PhysicsConfig::new(0.0));
+        world.add_body(
+            RigidBody::fixed(AABB2D::from_center_half_extents(Vec2::ZERO, Vec2::splat(1.0)))
+                .as_sensor()
+                .with_entity(1)
+        );
+        let body = world.add_body(
+            RigidBody::dynamic(Circle::new(Vec2::ZERO, 0.25))
+                .with_position(glam::vec2(-2.0, 0.0))
+                .with_velocity(glam::vec2(3.0, 0.0))
+                .with_entity(2)
+        );
+
+        let mut kinds = Vec::new();
+        for _ in 0..120 {
+            world.step(1.0 / 60.0);
+            for event in world.drain_events() {
+                assert!(event.is_sensor);
+                assert!(event.involves(1) && event.involves(2));
+                assert_eq!(event.other(1), Some(2));
+                if kinds.last() != Some(&event.kind) {
+                    kinds.push(event.kind);
+                }
+            }
+        }
+
+        assert_eq!(kinds, vec![ContactEventKind::Enter, ContactEventKind::Stay, ContactEventKind::Exit]);
+        // the sensor didn't slow the body down
+        assert!((world.body(body).unwrap().velocity.x - 3.0).abs() < 1e-4);
+        assert!(world.contacts().is_empty());
+    }
+
+    #[test]
+    fn test_layers() {
+        use crate::collision::CollisionLayers;
+
+        const PLAYER: CollisionLayers = CollisionLayers::layer(1);
+        const GHOST:  CollisionLayers = CollisionLayers::layer(2);
+
+        let mut world = world_with_ground();
+        let ghost = world.add_body(
+            RigidBody::dynamic(Circle::new(Vec2::ZERO, 0.5))
+                .with_position(glam::vec2(0.0, 2.0))
+                .with_layers(GHOST, CollisionLayers::ALL - CollisionLayers::DEFAULT)
+        );
+        let player = world.add_body(
+            RigidBody::dynamic(Circle::new(Vec2::ZERO, 0.5))
+                .with_position(glam::vec2(3.0, 2.0))
+                .with_layers(PLAYER, CollisionLayers::ALL)
+        );
+
+        simulate(&mut world, 1.0);
+
+        // the ghost ignores the ground, the player lands on it
+        assert!(world.body(ghost).unwrap().position.y < -1.0);
+        assert!((world.body(player).unwrap().position.y - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_remove_emits_exit() {
+        let mut world = PhysicsWorld::new(PhysicsConfig::new(0.0));
+        let a = world.add_body(RigidBody::fixed(Circle::new(Vec2::ZERO, 1.0)).as_sensor().with_entity(7));
+        let b = world.add_body(RigidBody::dynamic(Circle::new(Vec2::ZERO, 0.5)).with_entity(8));
+
+        world.step(1.0 / 60.0);
+        assert_eq!(world.events().len(), 1);
+        assert_eq!(world.events()[0].kind, ContactEventKind::Enter);
+
+        world.remove_body(b);
+        let exit = world.events().last().unwrap();
+        assert_eq!(exit.kind, ContactEventKind::Exit);
+        assert_eq!(exit.other(7), Some(8));
+        assert_eq!(exit.a.min(exit.b), a);
+
+        // the exit happened between steps and is still visible after the next one
+        world.step(1.0 / 60.0);
+        assert_eq!(world.events().len(), 1);
+        assert_eq!(world.events()[0].kind, ContactEventKind::Exit);
+
+        world.step(1.0 / 60.0);
+        assert!(world.events().is_empty());
+    }
+
+    #[test]
+    fn test_bullets_dont_tunnel() {
+        let shoot = |is_bullet: bool| {
+            let mut world = PhysicsWorld::new(PhysicsConfig::new(0.0));
+            world.add_body(RigidBody::fixed(AABB2D::new(glam::vec2(0.0, -5.0), glam::vec2(0.1, 5.0))));
+
+            // moves 10 units per step
+            let mut body = RigidBody::dynamic(Circle::new(Vec2::ZERO, 0.1))
+                .with_position(glam::vec2(-5.0, 0.0))
+                .with_velocity(glam::vec2(600.0, 0.0));
+            if is_bullet {
+                body = body.as_bullet();
+            }
+
+            let body = world.add_body(body);
+            simulate(&mut world, 0.1);
+            world.body(body).unwrap().position.x
+        };
+
+        assert!(shoot(false) > 10.0);
+        assert!(shoot(true) < 0.0);
+    }
+
+    #[test]
+    fn test_bullet_bounces() {
+        let mut world = PhysicsWorld::new(PhysicsConfig::new(0.0));
+        world.add_body(RigidBody::fixed(AABB2D::new(glam::vec2(0.0, -5.0), glam::vec2(0.1, 5.0))));
+        let body = world.add_body(
+            RigidBody::dynamic(Circle::new(Vec2::ZERO, 0.1))
+                .with_position(glam::vec2(-5.0, 0.0))
+                .with_velocity(glam::vec2(600.0, 0.0))
+                .with_restitution(1.0)
+                .as_bullet()
+        );
+
+        simulate(&mut world, 0.05);
+
+        let body = world.body(body).unwrap();
+        assert!(body.velocity.x < -590.0, "velocity: {}", body.velocity.x);
+        assert!(body.position.x < 0.0);
+    }
+}
@@ -31,6 +31,28 @@ impl HellCamera {
 
         let view_proj = view * proj;
 
+        Self {
+            view,
+            proj,
+            view_proj,
+        }
+    }
+    /// maps physical pixels to the screen - the origin is at the top left and y goes up, so the ui lies below the x axis
+    pub fn new_ui(width: f32, height: f32) -> Self {
+        let view = glam::Mat4::IDENTITY;
+
+        let mut proj = glam::Mat4::orthographic_lh(
+            0.0,
+            width.max(1.0),
+            -height.max(1.0),
+            0.0,
+            0.0,
+            100.0
+        );
+        proj.y_axis.y *= -1.0;
+
+        let view_proj = view * proj;
+
         Self {
             view,
             proj,
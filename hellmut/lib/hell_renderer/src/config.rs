@@ -1,4 +1,6 @@
 use ash::vk;
+use hell_core::hell_name;
+use hell_core::name::HellName;
 
 
 pub const APP_NAME: &str = "hellengine";
@@ -36,10 +38,10 @@ pub const CLEAR_COLOR: [f32; 4] = [0.3, 0.2, 0.8, 1.0];
 pub const IMG_FLIP_V: bool = false;
 pub const IMG_FLIP_H: bool = false;
 
-pub const SPRITE_SHADER_KEY:  &str = "sprite";
+pub const SPRITE_SHADER_KEY:  HellName = hell_name!("sprite");
 pub const SPRITE_SHADER_PATH: &str = "shaders/sprite";
-pub const TEST_SHADER_KEY:    &str = "test";
-pub const TEST_SHADER_PATH:   &str = "shaders/test";
+// the ui uses the sprite shader stages with its own pipeline
+pub const UI_SHADER_KEY:      HellName = hell_name!("ui");
 
 
 // guaranteed by the spec -> 128 Bytes for push constants
@@ -63,6 +65,8 @@ pub const MAX_DESCRIPTOR_SET_COUNT: usize = 1024;
 
 pub const VULKAN_MAX_MATERIAL_COUNT: usize = 1024;
 pub const VULKAN_MAX_SAMPLERS_PER_SHADER: usize = 16;
+// number of draws a shader can hold in its local storage
+pub const VULKAN_MAX_LOCAL_ENTRIES: usize = 10000;
 
 pub const VULKAN_SHADER_MAX_STAGES: usize =  8;
 pub const VULKAN_SHADER_MAX_GLOBAL_TEXTURES: usize =  31;
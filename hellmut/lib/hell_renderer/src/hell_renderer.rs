@@ -2,11 +2,13 @@ use std::sync::Arc;
 
 use hell_common::window::{HellSurfaceInfo, HellWindowExtent};
 use hell_core::error::HellResult;
+use hell_core::profile_scope;
 
 use crate::camera::HellCamera;
 use crate::render_types::RenderPackage;
 use crate::resources::{TextureManager, MaterialManager, ShaderManager, ResourceHandle};
-use crate::vulkan::primitives::VulkanSwapchain;
+use crate::vulkan::primitives::{BultinRenderPassType, VulkanSwapchain};
+use crate::vulkan::shader_program::ShaderScope;
 use crate::vulkan::{VulkanBackend, VulkanContext};
 
 
@@ -59,38 +61,35 @@ impl HellRenderer {
         self.backend.wait_idle()
     }
 
+    pub fn window_extent(&self) -> HellWindowExtent {
+        self.info.window_extent
+    }
+
     pub fn handle_window_changed(&mut self, window_extent: HellWindowExtent) -> HellResult<()> {
         self.info.window_extent = window_extent;
         self.backend.on_window_changed(self.info.window_extent)
     }
 
-    #[allow(unused)]
+    /// creates the shaders, materials that were acquired before get their instance entries now
     pub fn prepare_renderer(&mut self) -> HellResult<()> {
-        let sprite_handle = self.acquire_shader("sprite", true)?;
-        let sprite_shader = self.sha_man.shader_mut(sprite_handle);
-        let player_tex = self.tex_man.acquire_textuer(&self.backend, "player_tex".to_string(), Some("assets/characters/player_char.png".to_string()),   false, false)?;
-        let enemy_tex  = self.tex_man.acquire_textuer(&self.backend, "enemy_tex".to_string(),  Some("assets/characters/enemy_t1_char.png".to_string()), false, false)?;
-        let ground_tex = self.tex_man.acquire_textuer(&self.backend, "enemy_tex".to_string(),  Some("assets/environment/ground_v1.png".to_string()),    false, false)?;
-        let _ = sprite_shader.acquire_instance_resource(&[enemy_tex])?;
-        let _ = sprite_shader.acquire_instance_resource(&[player_tex])?;
-        let _ = sprite_shader.acquire_instance_resource(&[ground_tex])?;
-        // TODO: local instances
-        let _ = sprite_shader.acquire_local_resource(&[])?;
-
-        let handle = self.acquire_shader("test", false)?;
-        let shader = self.sha_man.shader_mut(handle);
-        let tex_1 = self.tex_man.acquire_textuer(&self.backend, "instance_tex_1".to_string(), Some("assets/characters/enemy_t1_char.png".to_string()), false, false)?;
-        let tex_2 = self.tex_man.acquire_textuer(&self.backend, "instance_tex_2".to_string(), Some("assets/characters/player_char.png".to_string()), false, false)?;
-        let _ = shader.acquire_shared_resource(&[])?;
-        let _ = shader.acquire_instance_resource(&[tex_1])?;
-        let _ = shader.acquire_instance_resource(&[tex_2])?;
+        self.acquire_shader("sprite", BultinRenderPassType::World)?;
+        self.acquire_shader("ui", BultinRenderPassType::Ui)?;
+
+        for idx in 0..self.mat_man.len() {
+            self.register_material(ResourceHandle::new(idx))?;
+        }
+
         // TODO: local instances
-        let _ = shader.acquire_local_resource(&[])?;
+        for shader in self.sha_man.shaders_mut() {
+            let _ = shader.acquire_local_resource(&[])?;
+        }
 
         Ok(())
     }
 
     pub fn draw_frame(&mut self, delta_time: f32, render_pkg: &RenderPackage) -> HellResult<bool> {
+        profile_scope!("draw_frame");
+
         self.backend.begin_frame()?;
         self.backend.draw_frame(delta_time, render_pkg, &mut self.sha_man, &self.tex_man, &self.camera)?;
         let is_resized = self.backend.end_frame()?;
@@ -100,13 +99,36 @@ impl HellRenderer {
 
 impl HellRenderer {
     // TODO: this sux
-    pub fn acquire_shader(&mut self, key: &str, is_sprite_shader: bool) -> HellResult<ResourceHandle> {
-        let tex = self.tex_man.acquire_textuer(&self.backend, "test_global".to_string(), None, false, false)?;
-        let shader = self.sha_man.create_shader(&self.backend, key, tex, is_sprite_shader)?;
+    pub fn acquire_shader(&mut self, key: &str, pass: BultinRenderPassType) -> HellResult<ResourceHandle> {
+        let tex = self.default_texture()?;
+        let shader = self.sha_man.create_shader(&self.backend, key, tex, pass)?;
         Ok(shader)
     }
 
     pub fn acquire_material(&mut self, path: impl Into<String>) -> HellResult<ResourceHandle> {
-        self.mat_man.acquire_from_file(&self.backend, &mut self.tex_man, path.into())
+        let material = self.mat_man.acquire_from_file(&self.backend, &mut self.tex_man, path.into())?;
+        self.register_material(material)?;
+        Ok(material)
+    }
+
+    /// adds an instance entry with the main texture of the material to every shader, the entry has the index of the material.
+    /// does nothing for materials that already have their entry.
+    fn register_material(&mut self, material: ResourceHandle) -> HellResult<()> {
+        let tex = match self.mat_man.texture(material, MaterialManager::MAIN_TEX) {
+            Some(tex) => tex,
+            None      => self.default_texture()?,
+        };
+
+        for shader in self.sha_man.shaders_mut() {
+            if shader.scope_entry_count(ShaderScope::Instance) == material.idx {
+                let _ = shader.acquire_instance_resource(&[tex])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn default_texture(&mut self) -> HellResult<ResourceHandle> {
+        self.tex_man.acquire_textuer(&self.backend, "default_tex".to_string(), None, false, false)
     }
 }
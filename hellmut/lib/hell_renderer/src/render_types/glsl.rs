@@ -75,11 +75,7 @@ impl GlslType {
 
 impl GlslType {
     pub fn is_sampler(&self) -> bool {
-        match self {
-            GlslType::Sampler2d |
-            GlslType::Sampler2dArray => { true }
-            _ => { false }
-        }
+        matches!(self, GlslType::Sampler2d | GlslType::Sampler2dArray)
     }
 }
 
@@ -26,29 +26,101 @@ pub struct RenderPackage {
     pub ui: RenderData,
 }
 
+impl RenderPackage {
+    /// has to be called after the transforms were changed and before the package is drawn
+    pub fn update_transforms(&mut self) {
+        self.world.transforms.update();
+        self.ui.transforms.update();
+    }
+}
+
 
 // -----------------------------------------------
 
+use glam::{Mat4, Vec2, Vec4};
+use hell_common::hierarchy::TransformHierarchy;
 use hell_common::transform::Transform;
 
 use crate::{resources::ResourceHandle, config};
 
 pub struct RenderDataChunk<'a> {
     pub mesh_idx: usize,
+    pub node: usize,
     pub transform: &'a Transform,
+    pub model_mat: &'a Mat4,
     pub material: ResourceHandle,
+    pub uv_rect: Vec4,
+    pub tint: Vec4,
+    pub sdf_style: Option<SdfStyle>,
+}
+
+/// the alpha channel of the texture is used as a signed distance field, e.g. for the atlas of an `SdfFont`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SdfStyle {
+    /// in distance field units, 0.5 reaches to the end of the spread of the atlas
+    pub outline_width: f32,
+    pub outline_color: Vec4,
+    /// in texture pixels - the shadow is cut off at the border of the glyph, so it has to stay within the spread
+    pub shadow_offset: Vec2,
+    pub shadow_color: Vec4,
+}
+
+impl Default for SdfStyle {
+    fn default() -> Self {
+        Self {
+            outline_width: 0.0,
+            outline_color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+            shadow_offset: Vec2::ZERO,
+            shadow_color: Vec4::ZERO,
+        }
+    }
+}
+
+/// per-draw entry of the local storage buffer - has to match `LocalUbo` in the shaders
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LocalData {
+    pub model: Mat4,
+    /// offset (xy) and size (zw) of the sampled texture region, in uv coordinates
+    pub uv_rect: Vec4,
+    /// multiplied with the sampled color
+    pub tint: Vec4,
+    /// x: 1 for distance fields, y: outline width, zw: shadow offset
+    pub sdf_params: Vec4,
+    pub outline_color: Vec4,
+    pub shadow_color: Vec4,
+}
+
+impl LocalData {
+    fn new(model: Mat4, uv_rect: Vec4, tint: Vec4, sdf_style: Option<SdfStyle>) -> Self {
+        let (sdf_params, outline_color, shadow_color) = match sdf_style {
+            Some(s) => (Vec4::new(1.0, s.outline_width, s.shadow_offset.x, s.shadow_offset.y), s.outline_color, s.shadow_color),
+            None    => (Vec4::ZERO, Vec4::ZERO, Vec4::ZERO),
+        };
+
+        Self { model, uv_rect, tint, sdf_params, outline_color, shadow_color }
+    }
 }
 
 // -----------------------------------------------
 
+/// every draw references a node in `transforms`, nodes without a draw can be used as parents
 #[derive(Debug, Default)]
 pub struct RenderData {
     pub meshes: Vec<usize>,
-    pub transforms: Vec<Transform>,
+    pub nodes: Vec<usize>,
     pub materials: Vec<ResourceHandle>,
+    pub uv_rects: Vec<Vec4>,
+    pub tints: Vec<Vec4>,
+    pub sdf_styles: Vec<Option<SdfStyle>>,
+    pub transforms: TransformHierarchy,
 }
 
 impl RenderData {
+    /// samples the whole texture
+    pub const FULL_UV_RECT: Vec4 = Vec4::new(0.0, 0.0, 1.0, 1.0);
+    pub const NO_TINT: Vec4 = Vec4::ONE;
+
     pub fn len(&self) -> usize {
         self.meshes.len()
     }
@@ -57,25 +129,95 @@ impl RenderData {
         self.len() == 0
     }
 
+    /// removes all draws and transforms
+    pub fn clear(&mut self) {
+        self.meshes.clear();
+        self.nodes.clear();
+        self.materials.clear();
+        self.uv_rects.clear();
+        self.tints.clear();
+        self.sdf_styles.clear();
+        self.transforms.clear();
+    }
+
+    /// returns the node of the new transform
     pub fn add_data(&mut self, mesh_idx: usize, material: ResourceHandle, trans: Transform) -> usize {
-        self.meshes.push(mesh_idx);
-        self.transforms.push(trans);
-        self.materials.push(material);
+        let node = self.transforms.add(trans);
+        self.add_draw(mesh_idx, material, node);
+        node
+    }
 
-        self.len()
+    /// `trans` is relative to `parent`, returns the node of the new transform
+    pub fn add_child_data(&mut self, mesh_idx: usize, material: ResourceHandle, parent: usize, trans: Transform) -> usize {
+        let node = self.transforms.add_child(parent, trans);
+        self.add_draw(mesh_idx, material, node);
+        node
     }
 
-    pub fn data_at(&self, idx: usize) -> RenderDataChunk {
+    /// adds a transform that isn't drawn
+    pub fn add_node(&mut self, trans: Transform, parent: Option<usize>) -> usize {
+        match parent {
+            Some(p) => self.transforms.add_child(p, trans),
+            None    => self.transforms.add(trans),
+        }
+    }
+
+    pub fn data_at(&self, idx: usize) -> RenderDataChunk<'_> {
+        let node = self.nodes[idx];
+
         RenderDataChunk {
             mesh_idx: self.meshes[idx],
-            transform: &self.transforms[idx],
-            material: self.materials[idx]
+            node,
+            transform: self.transforms.local(node),
+            model_mat: self.transforms.world_mat(node),
+            material: self.materials[idx],
+            uv_rect: self.uv_rects[idx],
+            tint: self.tints[idx],
+            sdf_style: self.sdf_styles[idx],
         }
     }
+
+    /// `draw` is the index of the draw, i.e. `len()` before it was added
+    pub fn set_uv_rect(&mut self, draw: usize, uv_rect: Vec4) {
+        self.uv_rects[draw] = uv_rect;
+    }
+
+    pub fn set_tint(&mut self, draw: usize, tint: Vec4) {
+        self.tints[draw] = tint;
+    }
+
+    /// `None` samples the texture as usual
+    pub fn set_sdf_style(&mut self, draw: usize, style: Option<SdfStyle>) {
+        self.sdf_styles[draw] = style;
+    }
+
+    /// world matrices of all draws, in draw order
+    pub fn model_mats(&self) -> impl ExactSizeIterator<Item = Mat4> + '_ {
+        self.nodes.iter().map(|n| *self.transforms.world_mat(*n))
+    }
+
+    /// local storage entries of all draws, in draw order
+    pub fn local_data(&self) -> impl ExactSizeIterator<Item = LocalData> + '_ {
+        (0..self.len()).map(|idx| LocalData::new(
+            *self.transforms.world_mat(self.nodes[idx]),
+            self.uv_rects[idx],
+            self.tints[idx],
+            self.sdf_styles[idx],
+        ))
+    }
+
+    fn add_draw(&mut self, mesh_idx: usize, material: ResourceHandle, node: usize) {
+        self.meshes.push(mesh_idx);
+        self.nodes.push(node);
+        self.materials.push(material);
+        self.uv_rects.push(Self::FULL_UV_RECT);
+        self.tints.push(Self::NO_TINT);
+        self.sdf_styles.push(None);
+    }
 }
 
 impl RenderData {
-    pub fn iter(&self) -> RenderDataIter {
+    pub fn iter(&self) -> RenderDataIter<'_> {
         self.into_iter()
     }
 }
@@ -180,5 +322,36 @@ where T: Default
 
 pub type MemRange = ValueRange<usize>;
 
+
+
 // ----------------------------------------------------------------------------
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_data() {
+        // the shaders read the entries with a std140 layout
+        assert_eq!(std::mem::size_of::<LocalData>(), 144);
+
+        let mut data = RenderData::default();
+        let parent = data.add_data(0, ResourceHandle::new(0), Transform::default());
+        data.add_child_data(0, ResourceHandle::new(1), parent, Transform::default());
+        data.set_uv_rect(1, glam::vec4(0.5, 0.25, 0.5, 0.25));
+        data.set_tint(1, glam::vec4(1.0, 0.0, 0.0, 0.5));
+        data.set_sdf_style(1, Some(SdfStyle { outline_width: 0.1, shadow_offset: glam::vec2(2.0, -2.0), ..Default::default() }));
+        data.transforms.update();
+
+        let locals: Vec<_> = data.local_data().collect();
+        assert_eq!(locals.len(), 2);
+        assert_eq!(locals[0].uv_rect, RenderData::FULL_UV_RECT);
+        assert_eq!(locals[0].tint, RenderData::NO_TINT);
+        assert_eq!(locals[1].uv_rect, glam::vec4(0.5, 0.25, 0.5, 0.25));
+        assert_eq!(data.data_at(1).tint, glam::vec4(1.0, 0.0, 0.0, 0.5));
+
+        assert_eq!(locals[0].sdf_params, Vec4::ZERO);
+        assert_eq!(locals[1].sdf_params, glam::vec4(1.0, 0.1, 2.0, -2.0));
+        assert_eq!(locals[1].outline_color, SdfStyle::default().outline_color);
+    }
+}
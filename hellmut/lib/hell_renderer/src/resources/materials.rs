@@ -1,6 +1,10 @@
-use std::{collections::HashMap, path::Path, fs};
+use std::{path::Path, fs};
 
 use hell_core::error::HellResult;
+use hell_core::hell_name;
+use hell_core::logging::target;
+use hell_core::name::{HellName, HellNameMap};
+use tracing::debug;
 
 use crate::vulkan::RenderBackend;
 
@@ -18,8 +22,8 @@ pub struct MaterialFile {
 #[derive(Debug, serde::Deserialize)]
 pub struct MaterialInfo {
     pub name: String,
-    pub shader: String,
-    pub textures: HashMap<String, MaterialTextureInfo>,
+    pub shader: HellName,
+    pub textures: HellNameMap<MaterialTextureInfo>,
 }
 
 #[derive(Debug, serde::Deserialize)]
@@ -31,13 +35,13 @@ pub struct MaterialTextureInfo {
 
 #[derive(Default)]
 pub struct MaterialManager {
-    handles: HashMap<String, ResourceHandle>,
-    shader: Vec<String>,
-    textures: Vec<HashMap<String, ResourceHandle>>,
+    handles: HellNameMap<ResourceHandle>,
+    shader: Vec<HellName>,
+    textures: Vec<HellNameMap<ResourceHandle>>,
 }
 
 impl MaterialManager {
-    pub const MAIN_TEX: &'static str = "main_tex";
+    pub const MAIN_TEX: HellName = hell_name!("main_tex");
 }
 
 impl MaterialManager {
@@ -57,26 +61,30 @@ impl MaterialManager {
         self.len() == 0
     }
 
-    pub fn handle(&self, path: &str) -> Option<ResourceHandle> {
-        self.handles.get(path).copied()
+    pub fn handle(&self, key: HellName) -> Option<ResourceHandle> {
+        self.handles.get(&key).copied()
+    }
+
+    pub fn texture(&self, material: ResourceHandle, name: HellName) -> Option<ResourceHandle> {
+        self.textures.get(material.idx)?.get(&name).copied()
     }
 
     pub fn acquire(&mut self, backend: &RenderBackend, tex_man: &mut TextureManager, path: String, info: MaterialInfo) -> HellResult<ResourceHandle> {
-        if let Some(handle) = self.handle(&path) {
+        let name = HellName::new(&path);
+        if let Some(handle) = self.handle(name) {
             return Ok(handle);
         }
 
+        debug!(target: target::RESOURCES, "acquire material '{}' ({})", info.name, path);
         let handle = ResourceHandle::new(self.len());
 
-        let textures: HellResult<HashMap<_, _>> = info.textures.into_iter()
-            .map(|(k, v)| {
-                let handle = tex_man.acquire_textuer(backend, v.path.clone(), Some(v.path), false, false)?;
-                Ok((k, handle))
-            })
-            .collect();
-        let textures = textures?;
+        let (names, paths): (Vec<_>, Vec<_>) = info.textures.into_iter()
+            .map(|(k, v)| (k, v.path))
+            .unzip();
+        let handles = tex_man.acquire_textures(backend, &paths, false, false)?;
+        let textures: HellNameMap<_> = names.into_iter().zip(handles).collect();
 
-        self.handles.insert(path, handle);
+        self.handles.insert(name, handle);
         self.shader.push(info.shader);
         self.textures.push(textures);
 
@@ -1,17 +1,19 @@
 pub mod config;
 
 
-use std::collections::HashMap;
-
 use hell_core::error::{HellResult, OptToHellErr};
+use hell_core::logging::target;
+use hell_core::name::{HellName, HellNameMap};
+use tracing::debug;
 
+use crate::vulkan::primitives::BultinRenderPassType;
 use crate::vulkan::{shader_program::ShaderProgram, RenderBackend};
 
 use super::ResourceHandle;
 
 #[derive(Default)]
 pub struct ShaderManager {
-    handles:  HashMap<String, ResourceHandle>,
+    handles:  HellNameMap<ResourceHandle>,
     // TODO: abstract vulkan specific details
     shaders: Vec<ShaderProgram>,
 }
@@ -19,27 +21,28 @@ pub struct ShaderManager {
 impl ShaderManager {
     pub fn new() -> Self {
         Self {
-            handles: HashMap::new(),
+            handles: HellNameMap::default(),
             shaders: Vec::new(),
         }
     }
 
-    pub fn handle(&self, key: &str) -> Option<ResourceHandle> {
-        self.handles.get(key).copied()
+    pub fn handle(&self, key: HellName) -> Option<ResourceHandle> {
+        self.handles.get(&key).copied()
     }
 
-    pub fn handle_res(&self, key: &str) -> HellResult<ResourceHandle> {
-        self.handles.get(key).copied().ok_or_render_herr("failed to get shader handle")
+    pub fn handle_res(&self, key: HellName) -> HellResult<ResourceHandle> {
+        self.handles.get(&key).copied().ok_or_render_herr("failed to get shader handle")
     }
 
-    pub fn create_shader(&mut self, backend: &RenderBackend, key: &str, global_tex: ResourceHandle, is_sprite_shader: bool) -> HellResult<ResourceHandle> {
-        if let Some(handle) = self.handle(key) {
+    pub fn create_shader(&mut self, backend: &RenderBackend, key: &str, global_tex: ResourceHandle, pass: BultinRenderPassType) -> HellResult<ResourceHandle> {
+        let name = HellName::new(key);
+        if let Some(handle) = self.handle(name) {
             Ok(handle)
         } else {
-            println!("create shader '{}'", key);
+            debug!(target: target::RESOURCES, "create shader '{}'", key);
             let handle = ResourceHandle::new(self.shaders.len());
-            self.handles.insert(key.to_string(), handle);
-            let shader = if is_sprite_shader { backend.create_sprite_shader(global_tex)? } else { backend.create_test_shader(global_tex)? };
+            self.handles.insert(name, handle);
+            let shader = backend.create_sprite_shader(global_tex, pass)?;
             self.shaders.push(shader);
             Ok(handle)
         }
@@ -52,4 +55,8 @@ impl ShaderManager {
     pub fn shader_mut(&mut self, handle: ResourceHandle) -> &mut ShaderProgram {
         self.shaders.get_mut(handle.idx).unwrap()
     }
+
+    pub fn shaders_mut(&mut self) -> impl Iterator<Item = &mut ShaderProgram> {
+        self.shaders.iter_mut()
+    }
 }
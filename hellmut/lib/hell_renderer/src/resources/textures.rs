@@ -1,7 +1,11 @@
-use std::{path::Path, collections::HashMap};
+use std::path::Path;
 
 use hell_core::error::{HellResult, HellErrorHelper};
+use hell_core::jobs::{self, JobHandle, JobSystem};
+use hell_core::logging::target;
+use hell_core::name::{HellName, HellNameMap};
 use image::{RgbaImage, DynamicImage};
+use tracing::debug;
 
 use crate::vulkan::{RenderTexture, RenderBackend};
 
@@ -11,7 +15,7 @@ use super::ResourceHandle;
 
 
 pub struct TextureManager {
-    handles:  HashMap<String, ResourceHandle>,
+    handles:  HellNameMap<ResourceHandle>,
     images:   Vec<Option<RgbaImage>>,
     textures: Vec<RenderTexture>,
 }
@@ -25,25 +29,59 @@ impl Default for TextureManager {
 impl TextureManager {
     pub fn new() -> Self {
         Self {
-            handles: HashMap::new(),
+            handles: HellNameMap::default(),
             images:  Vec::new(),
             textures: Vec::new(),
         }
     }
 
     pub fn acquire_textuer(&mut self, backend: &RenderBackend, key: String, path: Option<String>, flipv: bool, fliph: bool) -> HellResult<ResourceHandle> {
-        if let Some(handle) = self.handle(&key) {
+        let name = HellName::new(&key);
+        if let Some(handle) = self.handle(name) {
             return Ok(handle);
         }
 
-        let (img, internal) = if let Some(path) = path {
-            let img = Self::load_img(&path, flipv, fliph)?;
-            let data = img.as_raw().as_slice();
-            let internal = backend.texture_create(data, img.width() as usize, img.height() as usize)?;
-            (Some(img), internal)
-        } else {
-            let internal = backend.texture_create_default()?;
-            (None, internal)
+        debug!(target: target::RESOURCES, "acquire texture '{}' from {:?}", key, path);
+
+        let img = match path {
+            Some(path) => Some(Self::load_img(&path, flipv, fliph)?),
+            None => None,
+        };
+
+        self.insert_texture(backend, name, img)
+    }
+
+    /// decodes all images in parallel on the job system and uploads them in the given order, the paths are used as keys
+    pub fn acquire_textures(&mut self, backend: &RenderBackend, paths: &[String], flipv: bool, fliph: bool) -> HellResult<Vec<ResourceHandle>> {
+        let jobs = jobs::global();
+        let decoding: Vec<_> = paths.iter()
+            .map(|path| {
+                let name = HellName::new(path);
+                let job = self.handle(name)
+                    .is_none()
+                    .then(|| Self::load_img_async(jobs, path.clone(), flipv, fliph));
+                (name, job)
+            })
+            .collect();
+
+        decoding.into_iter()
+            .map(|(name, job)| {
+                // the same path may be requested more than once
+                if let Some(handle) = self.handle(name) {
+                    return Ok(handle);
+                }
+
+                debug!(target: target::RESOURCES, "acquire texture '{}'", name);
+                let img = job.map(JobHandle::wait).transpose()?;
+                self.insert_texture(backend, name, img)
+            })
+            .collect()
+    }
+
+    fn insert_texture(&mut self, backend: &RenderBackend, key: HellName, img: Option<RgbaImage>) -> HellResult<ResourceHandle> {
+        let internal = match &img {
+            Some(img) => backend.texture_create(img.as_raw().as_slice(), img.width() as usize, img.height() as usize)?,
+            None => backend.texture_create_default()?,
         };
 
         let handle = ResourceHandle::new(self.textures.len());
@@ -54,8 +92,8 @@ impl TextureManager {
         Ok(handle)
     }
 
-    pub fn handle(&self, path: &str) -> Option<ResourceHandle> {
-        self.handles.get(path).copied()
+    pub fn handle(&self, key: HellName) -> Option<ResourceHandle> {
+        self.handles.get(&key).copied()
     }
 
     pub fn textures(&self) -> &[RenderTexture] {
@@ -74,6 +112,10 @@ impl TextureManager {
 }
 
 impl TextureManager {
+    pub fn load_img_async(jobs: &JobSystem, path: String, flipv: bool, fliph: bool) -> JobHandle<HellResult<RgbaImage>> {
+        jobs.spawn(move || Self::load_img(&path, flipv, fliph))
+    }
+
     fn load_img(path: &str, flipv: bool, fliph: bool) -> HellResult<RgbaImage> {
         let dyn_img = {
             let i = image::open(Path::new(path))?;
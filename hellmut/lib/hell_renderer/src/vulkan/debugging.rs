@@ -1,4 +1,6 @@
 use ash::vk;
+use hell_core::logging::target;
+use tracing::{debug, error, info, trace, warn};
 use std::os::raw;
 use std::{ptr, ffi};
 
@@ -27,7 +29,7 @@ impl VulkanDebugData {
 impl Drop for VulkanDebugData {
     fn drop(&mut self) {
         unsafe {
-            println!("> dropping DebugData");
+            trace!(target: target::RENDER, "dropping DebugData");
 
             self.debug_utils_loader.destroy_debug_utils_messenger(self.debug_messenger, None);
         }
@@ -63,14 +65,6 @@ unsafe extern "system" fn vulkan_debug_utils_callback(
     _p_user_data: *mut raw::c_void,
 ) -> vk::Bool32 {
 
-    let severity = match message_severity {
-        vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE => "[VERBOSE]",
-        vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => "[WARNING]",
-        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR => "[ERROR  ]",
-        vk::DebugUtilsMessageSeverityFlagsEXT::INFO => "[INFO   ]",
-        _ => "[UNKNOWN]",
-    };
-
     let types = match message_type {
         vk::DebugUtilsMessageTypeFlagsEXT::GENERAL => "[GENERAL    ]",
         vk::DebugUtilsMessageTypeFlagsEXT::PERFORMANCE => "[PERFORMANCE]",
@@ -80,7 +74,13 @@ unsafe extern "system" fn vulkan_debug_utils_callback(
 
     let message = ffi::CStr::from_ptr((*p_callback_data).p_message);
 
-    println!("[DEBUG]{severity}{types}{message:?}");
+    match message_severity {
+        vk::DebugUtilsMessageSeverityFlagsEXT::VERBOSE => trace!(target: target::RENDER, "{types}{message:?}"),
+        vk::DebugUtilsMessageSeverityFlagsEXT::INFO    => debug!(target: target::RENDER, "{types}{message:?}"),
+        vk::DebugUtilsMessageSeverityFlagsEXT::WARNING => warn!(target: target::RENDER, "{types}{message:?}"),
+        vk::DebugUtilsMessageSeverityFlagsEXT::ERROR   => error!(target: target::RENDER, "{types}{message:?}"),
+        _ => info!(target: target::RENDER, "{types}{message:?}"),
+    };
 
     if message_severity == vk::DebugUtilsMessageSeverityFlagsEXT::ERROR ||
        message_severity == vk::DebugUtilsMessageSeverityFlagsEXT::WARNING
@@ -85,7 +85,7 @@ impl VulkanFrame {
         &self.render_finished_sem[self.frame_idx]
     }
 
-    pub fn gfx_cmd_buffer(&self) -> VulkanCommandBuffer {
+    pub fn gfx_cmd_buffer(&self) -> VulkanCommandBuffer<'_> {
         self.gfx_cmd_pools[self.frame_idx]
             .get_buffer(0)
     }
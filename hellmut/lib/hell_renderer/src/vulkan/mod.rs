@@ -17,6 +17,7 @@ mod vulkan_backend;
 pub use vulkan_backend::*;
 
 mod vulkan_data;
+#[allow(unused_imports)] // the module is still empty, newer toolchains flag the glob
 pub use vulkan_data::*;
 
 pub mod shader_program;
@@ -1,5 +1,7 @@
 mod shader;
 use hell_core::{error::{HellResult, HellErrorKind, HellError}, collections::stack_array::StackArray};
+use hell_core::logging::target;
+use tracing::trace;
 pub use shader::*;
 pub mod shader_data;
 
@@ -27,7 +29,7 @@ pub struct VulkanPipeline {
 
 impl Drop for VulkanPipeline {
     fn drop(&mut self) {
-        println!("> dropping GraphicsPipeline...");
+        trace!(target: target::RENDER, "dropping GraphicsPipeline...");
 
         unsafe {
             let device = &self.ctx.device.handle;
@@ -1,7 +1,8 @@
 use ash::vk;
 use hell_core::error::{HellResult, ErrToHellErr};
+use hell_core::logging::target;
+use tracing::debug;
 
-use std::io::Read;
 use std::path::Path;
 use std::{fs, ffi};
 
@@ -17,7 +18,7 @@ pub struct VulkanShader {
 
 impl VulkanShader {
     pub fn from_file(ctx: &VulkanContextRef, path: &str) -> HellResult<Self> {
-        println!("create vulkan shader from path: '{}'", path);
+        debug!(target: target::RENDER, "create vulkan shader from path: '{}'", path);
         let vert_path = format!("{}.vert.spv", path);
         let frag_path = format!("{}.frag.spv", path);
 
@@ -83,12 +84,7 @@ impl VulkanShaderModule {
 }
 
 fn read_shader_code(path: &Path) -> HellResult<Vec<u8>> {
-    let file = fs::File::open(path).to_render_hell_err()?;
-    Ok(
-        file.bytes()
-            .filter_map(|b| b.ok())
-            .collect()
-    )
+    fs::read(path).to_render_hell_err()
 }
 
 fn create_shader_module(device: &ash::Device, code: &[u8]) -> HellResult<vk::ShaderModule> {
@@ -3,19 +3,19 @@ use hell_core::error::HellResult;
 use crate::vulkan::{VulkanContextRef, primitives::{VulkanBuffer, VulkanCommands}, Vertex3D, Vertex2D};
 
 
-/// Vulkan:
-///      -1
-///      |
-/// -1 ----- +1
-///      |
-///      +1
-
-/// Hell:
-///      +1
-///      |
-/// -1 ----- +1
-///      |
-///      -1
+// Vulkan:
+//      -1
+//      |
+// -1 ----- +1
+//      |
+//      +1
+
+// Hell:
+//      +1
+//      |
+// -1 ----- +1
+//      |
+//      -1
 
 static QUAD_VERTS_4D: &[Vertex3D] = &[
     // Top-Left
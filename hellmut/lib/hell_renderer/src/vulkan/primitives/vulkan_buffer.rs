@@ -1,5 +1,7 @@
 use ash::vk;
 use hell_core::error::HellResult;
+use hell_core::logging::target;
+use tracing::trace;
 use crate::vulkan::{VulkanContextRef, Vertex3D};
 
 use super::{VulkanCommands, VulkanCommandPool, VulkanDeviceMemory};
@@ -22,7 +24,7 @@ pub struct VulkanBuffer {
 
 impl Drop for VulkanBuffer {
     fn drop(&mut self) {
-        println!("> dropping VulkanBuffer...");
+        trace!(target: target::RENDER, "dropping VulkanBuffer...");
 
         unsafe {
             let device = &self.ctx.device.handle;
@@ -69,7 +71,7 @@ impl VulkanBuffer {
         let device = &ctx.device.handle;
 
         let buffer_size = std::mem::size_of_val(vertices);
-        println!("VERT-SIZE: {}", buffer_size);
+        trace!(target: target::RENDER, "vertex-buffer size: {}", buffer_size);
 
         let mut staging_buffer = VulkanBuffer::new(
             ctx,
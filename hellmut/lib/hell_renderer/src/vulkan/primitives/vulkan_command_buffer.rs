@@ -1,6 +1,8 @@
 use std::ptr;
 use ash::vk;
 use hell_core::error::{HellResult, ErrToHellErr};
+use hell_core::logging::target;
+use tracing::trace;
 use crate::{vulkan::VulkanContextRef, config};
 
 
@@ -114,7 +116,7 @@ impl<'a> VulkanCommandBuffer<'a> {
         unsafe {
             let push_const_bytes = std::slice::from_raw_parts(
                 constants.as_ptr() as *const u8,
-                std::mem::size_of::<T>() * constants.len()
+                std::mem::size_of_val(constants)
             );
 
             ctx.device.handle.cmd_push_constants(self.handle(), layout, stage_flags, offset as u32, push_const_bytes);
@@ -139,7 +141,7 @@ pub struct VulkanCommandPool {
 
 impl Drop for VulkanCommandPool {
     fn drop(&mut self) {
-        println!("> dropping CommandPool...");
+        trace!(target: target::RENDER, "dropping CommandPool...");
 
         unsafe {
             let device = &self.ctx.device.handle;
@@ -169,7 +171,7 @@ impl VulkanCommandPool {
         VulkanCommandPool::new(ctx, ctx.device.queues.transfer.family_idx, vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER | vk::CommandPoolCreateFlags::TRANSIENT)
     }
 
-    pub fn get_buffer(&self, idx: usize) -> VulkanCommandBuffer {
+    pub fn get_buffer(&self, idx: usize) -> VulkanCommandBuffer<'_> {
         VulkanCommandBuffer::new(
             &self.frame_buffers[idx]
         )
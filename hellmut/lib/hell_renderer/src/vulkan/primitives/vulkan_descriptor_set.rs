@@ -4,6 +4,8 @@ use std::array;
 use ash::prelude::VkResult;
 use ash::vk;
 use hell_core::error::HellResult;
+use hell_core::logging::target;
+use tracing::trace;
 use crate::render_types::PerFrame;
 use crate::vulkan::VulkanContextRef;
 
@@ -24,7 +26,7 @@ pub struct VulkanDescriptorSetGroup {
 
 impl Drop for VulkanDescriptorSetGroup {
     fn drop(&mut self) {
-        println!("> dropping VulkanDescriptorSetLayoutGroup...");
+        trace!(target: target::RENDER, "dropping VulkanDescriptorSetLayoutGroup...");
 
         unsafe {
             let device = &self.ctx.device.handle;
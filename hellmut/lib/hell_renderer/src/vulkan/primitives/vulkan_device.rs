@@ -3,6 +3,8 @@ use std::{ptr, ffi};
 
 use ash::vk;
 use hell_core::error::{HellResult, ErrToHellErr};
+use hell_core::logging::target;
+use tracing::trace;
 use hell_utils::conversion;
 
 use crate::config;
@@ -82,7 +84,7 @@ impl VulkanLogicDevice {
 
 impl Drop for VulkanLogicDevice {
     fn drop(&mut self) {
-        println!("> dropping LogicDevice...");
+        trace!(target: target::RENDER, "dropping LogicDevice...");
 
         unsafe {
             // cleans up device queues
@@ -1,5 +1,7 @@
 use ash::vk;
 use hell_core::error::HellResult;
+use hell_core::logging::target;
+use tracing::trace;
 use crate::{vulkan::VulkanContextRef, config};
 
 use super::{VulkanSwapchain, VulkanImage, VulkanRenderPass};
@@ -12,7 +14,7 @@ pub struct VulkanFramebuffer {
 
 impl Drop for VulkanFramebuffer {
     fn drop(&mut self) {
-        println!("> dropping Framebuffer...");
+        trace!(target: target::RENDER, "dropping Framebuffer...");
 
         unsafe {
             let device = &self.ctx.device.handle;
@@ -1,5 +1,7 @@
 use ash::vk;
 use hell_core::error::HellResult;
+use hell_core::logging::target;
+use tracing::{debug, trace};
 use std::ptr;
 
 
@@ -21,7 +23,7 @@ pub struct VulkanImage {
 
 impl Drop for VulkanImage {
     fn drop(&mut self) {
-        println!("> dropping Image...");
+        trace!(target: target::RENDER, "dropping Image...");
 
         unsafe {
             let device = &self.ctx.device.handle;
@@ -283,7 +285,7 @@ impl VulkanImage {
         const SIZE: usize = WIDTH * HEIGHT;
         const BYTE_SIZE: usize = SIZE * 4;
 
-        println!("creating default tex with size '{}'", BYTE_SIZE);
+        debug!(target: target::RENDER, "creating default tex with size '{}'", BYTE_SIZE);
 
         const MOD:  u32 = 64;
         const STEP: u32 = MOD / 2;
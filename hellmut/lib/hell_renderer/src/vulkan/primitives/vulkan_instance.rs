@@ -3,6 +3,8 @@ use std::os::raw;
 
 use ash::vk;
 use hell_core::error::{HellResult, ErrToHellErr};
+use hell_core::logging::target;
+use tracing::trace;
 use crate::{vulkan::{validation_layers, platforms, debugging}, config};
 
 
@@ -79,7 +81,7 @@ impl VulkanInstance {
 
 impl Drop for VulkanInstance {
     fn drop(&mut self) {
-        println!("> dropping Instance...");
+        trace!(target: target::RENDER, "dropping Instance...");
 
         unsafe {
             self.instance.destroy_instance(None);
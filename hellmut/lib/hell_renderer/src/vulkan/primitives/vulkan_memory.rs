@@ -178,8 +178,7 @@ impl<T> VulkanMemoryMap<T> {
 
     // NOTE: theoretically does not need to be &mut self - but its probably a good idea
     pub fn copy_from_nonoverlapping<V>(&mut self, src: &[V], offset: isize) {
-        let align = std::mem::size_of::<V>();
-        let count = src.len() * align;
+        let count = std::mem::size_of_val(src);
         let src_ptr = src.as_ptr() as *const T;
 
         unsafe {
@@ -196,7 +195,6 @@ impl<T> VulkanMemoryMap<T> {
         let val = (&val) as *const V as *const T;
 
         (0..size)
-            .into_iter()
             .step_by(align)
             .for_each(|off| { unsafe {
                 self.data_ptr
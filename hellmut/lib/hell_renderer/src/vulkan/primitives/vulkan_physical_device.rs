@@ -1,8 +1,10 @@
 use ash::vk;
 use hell_core::error::{HellResult, ErrToHellErr};
+use hell_core::logging::target;
 use hell_utils::conversion::c_str_from_char_slice;
 use std::ffi::CStr;
 use std::fmt;
+use tracing::{debug, info, trace, warn};
 
 use crate::config;
 
@@ -45,7 +47,7 @@ impl VulkanPhysDevice {
             Some(d) => d,
         };
 
-        println!("physical device picked: {:?}", device);
+        info!(target: target::RENDER, "physical device picked: {:?}", device);
 
         Ok(device)
     }
@@ -63,7 +65,7 @@ impl VulkanPhysDevice {
         let mut _score = 0;
 
         let device_name = unsafe { CStr::from_ptr(device_props.device_name.as_ptr()) };
-        println!("rate device: {:?}", device_name);
+        debug!(target: target::RENDER, "rate device: {:?}", device_name);
 
         // api version
         // -----------
@@ -71,14 +73,11 @@ impl VulkanPhysDevice {
         let minor_version = vk::api_version_minor(device_props.api_version);
         let patch_version = vk::api_version_patch(device_props.api_version);
 
-        println!(
-            "\t> API Version: {}.{}.{}",
-            major_version, minor_version, patch_version
-        );
+        debug!(target: target::RENDER, "api-version: {}.{}.{}", major_version, minor_version, patch_version);
 
         // device-type
         // -----------
-        println!("\t> device-type: {:?}", device_props.device_type);
+        debug!(target: target::RENDER, "device-type: {:?}", device_props.device_type);
         match device_props.device_type {
             vk::PhysicalDeviceType::DISCRETE_GPU => _score += 1000,
             _ => _score += 100,
@@ -87,26 +86,23 @@ impl VulkanPhysDevice {
         // shaders
         // -------
         if features.geometry_shader == vk::TRUE {
-            println!("\t> geometry-shader is supported");
+            debug!(target: target::RENDER, "geometry-shader is supported");
         } else {
             _score = 0;
-            eprintln!("\t> geometry-shader is NOT supported");
+            warn!(target: target::RENDER, "geometry-shader is NOT supported");
         }
 
         if features2_phys_device_features_11.shader_draw_parameters == vk::TRUE {
-            println!("\t> shader-draw-parameters are supported");
+            debug!(target: target::RENDER, "shader-draw-parameters are supported");
         } else {
             _score = 0;
-            eprintln!("\t> shader-draw-parameters are NOT supported");
+            warn!(target: target::RENDER, "shader-draw-parameters are NOT supported");
         }
 
 
         // sampler
         // -------
-        println!(
-            "\t> sampler-anisotropy is supported: {:?}",
-            features.sampler_anisotropy
-        );
+        debug!(target: target::RENDER, "sampler-anisotropy is supported: {:?}", features.sampler_anisotropy);
         if features.sampler_anisotropy == vk::TRUE {
             _score += 20;
         }
@@ -118,9 +114,9 @@ impl VulkanPhysDevice {
         let queue_support = VulkanQueueSupport::new(instance, phys_device, surface).ok()?;
         if !queue_support.is_complete() {
             _score = 0;
-            println!("> no suitable queues were found!");
+            warn!(target: target::RENDER, "no suitable queues were found");
         } else {
-            println!("queue-families found: {:?}", queue_support);
+            debug!(target: target::RENDER, "queue-families found: {:?}", queue_support);
         }
 
         // extensions
@@ -128,7 +124,7 @@ impl VulkanPhysDevice {
         let swapchain_support = {
             if !check_device_extension_support(instance, phys_device, extension_names).ok()? {
                 _score = 0;
-                println!("> not all device extensions are supported!");
+                warn!(target: target::RENDER, "not all device extensions are supported");
                 return None;
             } else {
                 // swap-chains
@@ -136,7 +132,7 @@ impl VulkanPhysDevice {
                 let swapchain_support = VulkanSwapchainSupport::new(phys_device, surface).ok()?;
                 if !swapchain_support.is_suitable() {
                     _score = 0;
-                    println!("> no suitable swap-chain found!");
+                    warn!(target: target::RENDER, "no suitable swap-chain found");
                 }
                 swapchain_support
             }
@@ -176,22 +172,19 @@ fn check_device_extension_support(instance: &ash::Instance, phys_device: vk::Phy
     };
     let mut remaining_extensions = extension_names.to_owned();
 
-    println!("checking extension support...");
-    println!("\t> supported extensions: ");
-    println!("\t\thidden");
+    debug!(target: target::RENDER, "checking extension support...");
 
     for prop in extension_props {
         let ext = c_str_from_char_slice(&prop.extension_name)
             .to_str()
             .to_render_hell_err()?;
-        // println!("\t\t> {:?}", ext);
+        trace!(target: target::RENDER, "supported extension: {:?}", ext);
 
         remaining_extensions.retain(|e| *e != ext);
     }
 
-    println!("\t> un-supported extensions: ");
     for ext in &remaining_extensions {
-        println!("\t\t> {:?}", ext);
+        warn!(target: target::RENDER, "un-supported extension: {:?}", ext);
     }
 
     Ok(remaining_extensions.is_empty())
@@ -208,15 +201,11 @@ pub fn find_supported_format(
         let props = unsafe { instance.get_physical_device_format_properties(phys_device, *format) };
 
         match tiling {
-            vk::ImageTiling::LINEAR => {
-                if (props.linear_tiling_features & features) == features {
-                    return *format;
-                };
+            vk::ImageTiling::LINEAR if (props.linear_tiling_features & features) == features => {
+                return *format;
             }
-            vk::ImageTiling::OPTIMAL => {
-                if (props.optimal_tiling_features & features) == features {
-                    return *format;
-                };
+            vk::ImageTiling::OPTIMAL if (props.optimal_tiling_features & features) == features => {
+                return *format;
             }
             _ => {}
         }
@@ -1,6 +1,8 @@
 use std::collections::HashSet;
 use ash::vk;
 use hell_core::error::{HellResult, OptToHellErr};
+use hell_core::logging::target;
+use tracing::debug;
 use super::VulkanSurface;
 
 
@@ -150,12 +152,15 @@ impl VulkanQueueSupport {
         let props = unsafe { instance.get_physical_device_queue_family_properties(device) };
 
         for (idx, prop) in props.iter().enumerate() {
-            println!("\t> queue: {}", idx);
-
-            if prop.queue_flags.contains(vk::QueueFlags::GRAPHICS) { println!("\t\t> GRAPHICS-QUEUE"); }
-            if prop.queue_flags.contains(vk::QueueFlags::COMPUTE) { println!("\t\t> COMPUTE-QUEUE"); }
-            if prop.queue_flags.contains(vk::QueueFlags::TRANSFER) { println!("\t\t> TRANSFER-QUEUE"); }
-            if prop.queue_flags.contains(vk::QueueFlags::SPARSE_BINDING) { println!("\t\t> SPARSE-BINDING-QUEUE"); }
+            debug!(
+                target: target::RENDER,
+                "queue {}: graphics={} compute={} transfer={} sparse-binding={}",
+                idx,
+                prop.queue_flags.contains(vk::QueueFlags::GRAPHICS),
+                prop.queue_flags.contains(vk::QueueFlags::COMPUTE),
+                prop.queue_flags.contains(vk::QueueFlags::TRANSFER),
+                prop.queue_flags.contains(vk::QueueFlags::SPARSE_BINDING),
+            );
         }
     }
 }
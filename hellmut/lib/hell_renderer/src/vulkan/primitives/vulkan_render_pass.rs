@@ -1,6 +1,8 @@
 use ash::vk;
 use hell_core::collections::stack_array::StackArray;
 use hell_core::error::HellResult;
+use hell_core::logging::target;
+use tracing::trace;
 
 use crate::vulkan::VulkanContextRef;
 
@@ -18,6 +20,7 @@ bitflags::bitflags! {
 }
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BultinRenderPassType {
     World,
     Ui,
@@ -37,7 +40,7 @@ pub struct VulkanRenderPass {
 
 impl Drop for VulkanRenderPass {
     fn drop(&mut self) {
-        println!("> dropping RenderPass...");
+        trace!(target: target::RENDER, "dropping RenderPass...");
 
         unsafe {
             let device = &self.ctx.device.handle;
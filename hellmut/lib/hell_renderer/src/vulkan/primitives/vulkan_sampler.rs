@@ -1,4 +1,6 @@
 use ash::prelude::VkResult;
+use hell_core::logging::target;
+use tracing::trace;
 pub use ash::vk;
 
 use crate::vulkan::VulkanContextRef;
@@ -13,7 +15,7 @@ pub struct VulkanSampler {
 
 impl Drop for VulkanSampler {
     fn drop(&mut self) {
-        println!("> dropping VulkanTextureSampler...");
+        trace!(target: target::RENDER, "dropping VulkanTextureSampler...");
 
         unsafe {
             let device = &self.ctx.device.handle;
@@ -1,6 +1,8 @@
 use ash::vk;
 use hell_common::window::HellSurfaceInfo;
 use hell_core::error::{HellResult, ErrToHellErr};
+use hell_core::logging::target;
+use tracing::trace;
 
 
 pub struct VulkanSurface {
@@ -23,7 +25,7 @@ impl VulkanSurface {
 
 impl Drop for VulkanSurface {
     fn drop(&mut self) {
-        println!("> dropping Surface...");
+        trace!(target: target::RENDER, "dropping Surface...");
 
         unsafe {
             self.surface_loader.destroy_surface(self.surface, None);
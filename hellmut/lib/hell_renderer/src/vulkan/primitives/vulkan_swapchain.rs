@@ -1,6 +1,8 @@
 use ash::vk;
 use hell_common::window::HellWindowExtent;
 use hell_core::error::{HellResult, OptToHellErr};
+use hell_core::logging::target;
+use tracing::{debug, trace};
 use crate::{vulkan::{primitives::VulkanImage,  VulkanContextRef}, config};
 
 use super::{VulkanSemaphore, VulkanSurface};
@@ -144,7 +146,7 @@ impl Drop for VulkanSwapchain {
 
 impl VulkanSwapchain {
     pub fn drop_manual(&mut self) {
-        println!("> dropping Swapchain...");
+        trace!(target: target::RENDER, "dropping Swapchain...");
 
         unsafe {
             let device = &self.ctx.device.handle;
@@ -213,7 +215,7 @@ impl VulkanSwapchain {
             }
         ];
 
-        println!("swapchain created with {} images...", imgs.len());
+        debug!(target: target::RENDER, "swapchain created with {} images", imgs.len());
 
         Ok(VulkanSwapchain {
             ctx: ctx.clone(),
@@ -1,10 +1,14 @@
 #![allow(dead_code)]
 #![allow(unused)]
 
-use std::{array, collections::HashMap, mem::{self, size_of}};
+use std::{array, mem::{self, size_of}};
 
 use ash::vk::{self, WriteDescriptorSet};
 use hell_core::{collections::stack_array::StackArray, error::{HellResult, OptToHellErr, HellErrorHelper}};
+use hell_core::hell_name;
+use hell_core::logging::target;
+use hell_core::name::{HellName, HellNameMap};
+use tracing::trace;
 use crate::{vulkan::{VulkanContextRef, primitives::{VulkanDescriptorSetGroup, VulkanSwapchain,  VulkanRenderPass, VulkanImage, VulkanBuffer, VulkanMemoryMap, VulkanCommands, VulkanSampler, VulkanTexture, VulkanCommandBuffer}, pipeline::{VulkanShader, VulkanPipeline}, VulkanFrame}, resources::{ResourceHandle, TextureManager}, render_types::{PerFrame, ValueRange, MemRange, NumberFormat}, config};
 
 
@@ -171,14 +175,14 @@ pub struct ShaderProgramBuilder {
     use_set: PerScope<bool>,
     sampler_counts: PerScope<usize>,
     uniforms: PerScope<Vec<UniformInfo>>,
-    uniform_lookups: HashMap<String, UniformHandle>,
+    uniform_lookups: HellNameMap<UniformHandle>,
     scope_sizes: PerScope<usize>,
     scope_entry_count: PerScope<usize>,
     global_tex: Vec<ResourceHandle>,
 
     push_constant_size: usize,
     push_constants: Vec<PushConstantInfo>,
-    push_constant_lookups: HashMap<String, ResourceHandle>,
+    push_constant_lookups: HellNameMap<ResourceHandle>,
 }
 
 impl ShaderProgramBuilder {
@@ -207,7 +211,7 @@ impl ShaderProgramBuilder {
             is_wireframe: false,
             shader_path: shader_path.into(),
             attributes: StackArray::default(),
-            uniform_lookups: HashMap::new(),
+            uniform_lookups: HellNameMap::default(),
             use_set: Default::default(),
             uniforms: Default::default(),
             sampler_counts: Default::default(),
@@ -218,7 +222,7 @@ impl ShaderProgramBuilder {
             push_constant_size: 0,
             // push_constant_ranges: Vec::new(),
             push_constants: Vec::new(),
-            push_constant_lookups: HashMap::new(),
+            push_constant_lookups: HellNameMap::default(),
         }
     }
 
@@ -260,13 +264,14 @@ impl ShaderProgramBuilder {
         let range = MemRange::new(offset, size);
         let info = UniformInfo::new_uniform(&name, scope, idx, range);
 
-        println!("PUSH-UNIFORM: {:?}", info);
+        trace!(target: target::RENDER, "push-uniform: {:?}", info);
         // NOTE: use final size stored in info struct
         self.scope_sizes[scope as usize] += info.range.range;
         uniforms.push(info);
-        self.uniform_lookups.insert(name, UniformHandle::new(scope, idx));
+        self.uniform_lookups.insert(HellName::new(&name), UniformHandle::new(scope, idx));
 
-        if scope == ShaderScope::Local {
+        // all local uniforms share the same index into the storage buffer
+        if scope == ShaderScope::Local && idx == 0 {
             self.add_push_constant::<u32>("local_idx");
         }
 
@@ -311,9 +316,9 @@ impl ShaderProgramBuilder {
             handle,
             range,
         });
-        self.push_constant_lookups.insert(name, handle);
+        self.push_constant_lookups.insert(HellName::new(&name), handle);
 
-        println!("with-push-constant: '{:?}'", range);
+        trace!(target: target::RENDER, "with-push-constant: '{:?}'", range);
     }
 
     // ------------------------------------------
@@ -409,10 +414,10 @@ impl ShaderProgramBuilder {
         for (idx, use_set) in self.use_set.iter().enumerate() {
             // sets have to be contigous -> there can't be a set 3 when there is no set 2
             if !use_set {
-                println!("skipping layoutfor set '{}'", idx);
+                trace!(target: target::RENDER, "skipping layout for set '{}'", idx);
                 continue;
             }
-            println!("creating layoutfor set '{}'", idx);
+            trace!(target: target::RENDER, "creating layout for set '{}'", idx);
 
             // scope-set-mapping
             // -----------------
@@ -484,8 +489,10 @@ impl ShaderProgramBuilder {
         // allocate main-buffer
         // --------------------
         // TODO: do somewhere elese
-        scope_strides[ShaderScope::Local as usize] = std::mem::size_of::<glam::Mat4>() * 10000;
-        let local_buffer_size = std::mem::size_of::<glam::Mat4>() * 10000;
+        // std140 rounds the size of the struct up to a multiple of 16
+        let local_entry_size = self.scope_sizes[ShaderScope::Local as usize].next_multiple_of(16).max(16);
+        let local_buffer_size = local_entry_size * config::VULKAN_MAX_LOCAL_ENTRIES;
+        scope_strides[ShaderScope::Local as usize] = local_buffer_size;
         let mut local_buffer = VulkanBuffer::from_storage(ctx, local_buffer_size)?;
         local_buffer.mem.map_memory(0, local_buffer_size, vk::MemoryMapFlags::empty())?;
 
@@ -562,7 +569,7 @@ pub struct ShaderProgram {
     ctx: VulkanContextRef,
     pub pipeline: VulkanPipeline,
     desc_pool: vk::DescriptorPool,
-    uniform_lookups: HashMap<String, UniformHandle>,
+    uniform_lookups: HellNameMap<UniformHandle>,
     uniforms: PerScope<Vec<UniformInfo>>,
     main_buffer: VulkanBuffer,
     local_buffer: VulkanBuffer,
@@ -579,7 +586,7 @@ pub struct ShaderProgram {
     global_entry: ResourceHandle,
 
     push_constants: Vec<PushConstantInfo>,
-    push_constant_lookups: HashMap<String, ResourceHandle>,
+    push_constant_lookups: HellNameMap<ResourceHandle>,
 }
 
 impl Drop for ShaderProgram {
@@ -596,19 +603,19 @@ impl Drop for ShaderProgram {
 }
 
 impl ShaderProgram {
-    pub fn uniform_handle(&self, name: &str) -> Option<UniformHandle> {
-        self.uniform_lookups.get(name).copied()
+    pub fn uniform_handle(&self, name: HellName) -> Option<UniformHandle> {
+        self.uniform_lookups.get(&name).copied()
     }
 
-    pub fn uniform_handle_res(&self, name: &str) -> HellResult<UniformHandle> {
+    pub fn uniform_handle_res(&self, name: HellName) -> HellResult<UniformHandle> {
         self.uniform_handle(name).ok_or_render_herr("failed to get uniform")
     }
 
-    pub fn push_constant_handle(&self, name: &str) -> Option<ResourceHandle> {
-        self.push_constant_lookups.get(name).copied()
+    pub fn push_constant_handle(&self, name: HellName) -> Option<ResourceHandle> {
+        self.push_constant_lookups.get(&name).copied()
     }
 
-    pub fn push_constant_handle_res(&self, name: &str) -> HellResult<ResourceHandle> {
+    pub fn push_constant_handle_res(&self, name: HellName) -> HellResult<ResourceHandle> {
         self.push_constant_handle(name).ok_or_render_herr("failed to get push-constant-handle")
     }
 
@@ -654,7 +661,10 @@ impl ShaderProgram {
     }
 
     pub fn set_local_storage<T>(&mut self, value: &[T]) -> HellResult<()> {
-        let buff_size = std::mem::size_of::<T>() * value.len();
+        let buff_size = std::mem::size_of_val(value);
+        if buff_size > self.local_buffer.size {
+            return Err(HellErrorHelper::render_msg_err("local storage exceeds the size of the local buffer"));
+        }
 
         self.local_buffer.mem
             .mapped_memory_mut()?
@@ -673,7 +683,7 @@ impl ShaderProgram {
 
     // TODO: improve
     pub fn set_local_idx(&mut self, frame: &VulkanFrame, idx: u32) -> HellResult<()> {
-        let handle = self.push_constant_handle_res("local_idx")?;
+        let handle = self.push_constant_handle_res(hell_name!("local_idx"))?;
         self.set_push_constant(handle, &[idx], frame)
     }
 
@@ -727,7 +737,17 @@ impl ShaderProgram {
 
     // ------------------------------------------------------------------------
 
+    pub fn scope_entry_count(&self, scope: ShaderScope) -> usize {
+        self.scope_entry_states[scope as usize].len()
+    }
+
     pub fn apply_main_scope_intern(&self, scope: ShaderScope, frame: &VulkanFrame, tex_man: &TextureManager, entry: ResourceHandle) -> HellResult<()> {
+        let desc_set = self.update_scope_entry(scope, frame, tex_man, entry)?;
+        self.bind_scope_desc_set(scope, frame, desc_set)
+    }
+
+    /// writes the buffer and textures of the entry into its descriptor set of this frame, without binding it
+    fn update_scope_entry(&self, scope: ShaderScope, frame: &VulkanFrame, tex_man: &TextureManager, entry: ResourceHandle) -> HellResult<vk::DescriptorSet> {
         let state = self.scope_entry_states[scope as usize].get(entry.idx).ok_or_render_herr("failed to get scope state")?;
         let desc_set = state.buffer_desc_set(frame.idx());
         let tex_handles = state.textures();
@@ -791,19 +811,17 @@ impl ShaderProgram {
 
         // update descriptor sets
         // ----------------------
-        // unsafe { self.ctx.device.handle.update_descriptor_sets(write_desc.as_slice(), &[]); }
-        //
-        // let cmd_buff = frame.gfx_cmd_buffer();
-        // let first_set = self.scope_set_mapping[scope as usize].ok_or_render_herr("failed to get scope mapping")? as u32;
-        // cmd_buff.cmd_bind_descriptor_sets(&self.ctx, vk::PipelineBindPoint::GRAPHICS, self.pipeline.layout, first_set, &[desc_set], &[]);
-        self.bind_and_udpate_scope(scope, frame, write_desc.as_slice(), desc_set)?;
+        unsafe { self.ctx.device.handle.update_descriptor_sets(write_desc.as_slice(), &[]); }
 
-        Ok(())
+        Ok(desc_set)
     }
 
     fn bind_and_udpate_scope(&self, scope: ShaderScope, frame: &VulkanFrame, write_desc: &[WriteDescriptorSet], desc_set: vk::DescriptorSet) -> HellResult<()> {
         unsafe { self.ctx.device.handle.update_descriptor_sets(write_desc, &[]); }
+        self.bind_scope_desc_set(scope, frame, desc_set)
+    }
 
+    fn bind_scope_desc_set(&self, scope: ShaderScope, frame: &VulkanFrame, desc_set: vk::DescriptorSet) -> HellResult<()> {
         let cmd_buff = frame.gfx_cmd_buffer();
         let first_set = self.scope_set_mapping[scope as usize].ok_or_render_herr("failed to get scope mapping")? as u32;
         cmd_buff.cmd_bind_descriptor_sets(&self.ctx, vk::PipelineBindPoint::GRAPHICS, self.pipeline.layout, first_set, &[desc_set], &[]);
@@ -823,6 +841,21 @@ impl ShaderProgram {
         self.apply_main_scope_intern(ShaderScope::Instance, frame, tex_man, entry)
     }
 
+    /// updates the descriptor sets of all instance entries, they can be bound with `bind_instance_entry` afterwards
+    pub fn update_instance_entries(&self, frame: &VulkanFrame, tex_man: &TextureManager) -> HellResult<()> {
+        for idx in 0..self.scope_entry_count(ShaderScope::Instance) {
+            self.update_scope_entry(ShaderScope::Instance, frame, tex_man, ResourceHandle::new(idx))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn bind_instance_entry(&self, frame: &VulkanFrame, entry: ResourceHandle) -> HellResult<()> {
+        let scope = ShaderScope::Instance;
+        let state = self.scope_entry_states[scope as usize].get(entry.idx).ok_or_render_herr("failed to get scope state")?;
+        self.bind_scope_desc_set(scope, frame, state.buffer_desc_set(frame.idx()))
+    }
+
     // pub fn apply_local_scope(&self, frame: &VulkanFrame, tex_man: &TextureManager, entry: ResourceHandle) -> HellResult<()> {
     pub fn apply_local_scope(&self, frame: &VulkanFrame) -> HellResult<()> {
         let scope = ShaderScope::Local;
@@ -1,4 +1,6 @@
 use hell_core::error::{ErrToHellErr, HellResult};
+use hell_core::logging::target;
+use tracing::{debug, warn};
 use hell_utils::conversion;
 
 
@@ -18,9 +20,9 @@ pub fn check_validation_layer_support(entry: &ash::Entry, required_layers: &[&st
             });
 
         if res.is_some()  {
-            println!("validation-layer: {layer} is supported!");
+            debug!(target: target::RENDER, "validation-layer: {layer} is supported");
         } else {
-            eprintln!("validation-layer: {layer} is not supported!");
+            warn!(target: target::RENDER, "validation-layer: {layer} is not supported");
             return Ok(false);
         }
     }
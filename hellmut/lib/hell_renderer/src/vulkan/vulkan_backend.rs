@@ -7,8 +7,14 @@ use std::cell::RefCell;
 use ash::vk;
 use hell_common::transform::Transform;
 use hell_common::window::HellWindowExtent;
+use hell_core::collections::arena::FrameAllocator;
 use hell_core::collections::stack_array::StackArray;
 use hell_core::error::{HellResult, HellError, HellErrorKind, OptToHellErr, ErrToHellErr};
+use hell_core::hell_name;
+use hell_core::logging::target;
+use hell_core::name::HellName;
+use hell_core::profile_scope;
+use tracing::{debug, trace};
 use crate::camera::HellCamera;
 use crate::config;
 use crate::render_types::{RenderData, RenderPackage, NumberFormat};
@@ -40,6 +46,8 @@ pub struct VulkanBackend {
     pub swapchain: VulkanSwapchain,
     pub swap_idx: usize,
     pub render_pass_data: VulkanRenderPassData,
+    /// scratch memory for data that is only needed while recording a frame
+    pub frame_alloc: FrameAllocator,
     pub ctx: VulkanContextRef,
 }
 
@@ -60,6 +68,7 @@ impl VulkanBackend {
             swapchain,
             swap_idx: usize::MAX,
             render_pass_data,
+            frame_alloc: FrameAllocator::new(config::FRAMES_IN_FLIGHT),
             cmds,
             // world_shader,
 
@@ -70,7 +79,7 @@ impl VulkanBackend {
 
 impl VulkanBackend {
     pub fn recreate_swapchain(&mut self, window_extent: HellWindowExtent) -> HellResult<()> {
-        println!("> recreating swapchain...");
+        debug!(target: target::RENDER, "recreating swapchain...");
 
         self.swapchain.drop_manual();
         self.swapchain = VulkanSwapchain::new(&self.ctx, window_extent)?;
@@ -147,10 +156,12 @@ impl VulkanBackend {
     }
 
     pub fn begin_frame(&mut self) -> HellResult<()> {
+        profile_scope!("begin_frame");
         self.frame.begin_frame();
 
         let in_flight_fence = self.frame.in_flight_fence();
         in_flight_fence.wait_for_fence(u64::MAX)?;
+        self.frame_alloc.begin_frame(self.frame.idx());
 
         let img_available_sem = self.frame.img_available_sem();
         let (curr_swap_idx, _is_suboptimal) = self.swapchain.aquire_next_image(img_available_sem)?;
@@ -173,21 +184,25 @@ impl VulkanBackend {
         cmd_buffer.cmd_set_scissor(ctx, 0, &self.swapchain.sissor);
 
         // world render pass
-        self.update_sprite_shader(sha_man, tex_man, camera, &render_pkg.world)?;
+        self.update_sprite_shader(sha_man, tex_man, config::SPRITE_SHADER_KEY, camera, &render_pkg.world)?;
         self.begin_render_pass(BultinRenderPassType::World, &cmd_buffer);
-        self.record_generic_cmd_buffer(&cmd_buffer, &render_pkg.world, sha_man, "sprite")?;
+        self.record_generic_cmd_buffer(&cmd_buffer, &render_pkg.world, sha_man, config::SPRITE_SHADER_KEY)?;
         self.end_renderpass(&cmd_buffer);
 
         // ui render pass
-        self.update_test_shader(sha_man, tex_man, &render_pkg.ui)?;
+        // the ui has its own coordinate system, independent of the aspect ratio
+        let extent = self.swapchain.extent;
+        let ui_camera = HellCamera::new_ui(extent.width as f32, extent.height as f32);
+        self.update_sprite_shader(sha_man, tex_man, config::UI_SHADER_KEY, &ui_camera, &render_pkg.ui)?;
         self.begin_render_pass(BultinRenderPassType::Ui, &cmd_buffer);
-        self.record_generic_cmd_buffer(&cmd_buffer, &render_pkg.ui, sha_man, "test")?;
+        self.record_generic_cmd_buffer(&cmd_buffer, &render_pkg.ui, sha_man, config::UI_SHADER_KEY)?;
         self.end_renderpass(&cmd_buffer);
 
         Ok(())
     }
 
     pub fn end_frame(&mut self) -> HellResult<bool> {
+        profile_scope!("end_frame");
         let ctx = &self.ctx;
 
         // end cmd-buffer
@@ -245,7 +260,9 @@ impl VulkanBackend {
         Ok(is_resized)
     }
 
-    fn record_generic_cmd_buffer(&self, cmd_buffer: &VulkanCommandBuffer, render_data: &RenderData, sha_man: &mut ShaderManager, shader: &str) -> HellResult<()> {
+    fn record_generic_cmd_buffer(&self, cmd_buffer: &VulkanCommandBuffer, render_data: &RenderData, sha_man: &mut ShaderManager, shader: HellName) -> HellResult<()> {
+        profile_scope!("record_generic_cmd_buffer");
+
         // bind vertex data
         // ----------------
         let mesh = &self.world_meshes[0];
@@ -256,7 +273,13 @@ impl VulkanBackend {
         cmd_buffer.cmd_bind_pipeline(&self.ctx, vk::PipelineBindPoint::GRAPHICS, shader.pipeline.pipeline);
 
         // draw each object
+        // the instance entries of a shader have the same indices as the materials
+        let mut bound_material = None;
         for (idx, rd) in render_data.iter().enumerate() {
+            if bound_material != Some(rd.material) {
+                shader.bind_instance_entry(&self.frame, rd.material)?;
+                bound_material = Some(rd.material);
+            }
             shader.set_local_idx(&self.frame, idx as u32)?;
 
             // value of 'first_instance' is used in the vertex shader to index into the object storage
@@ -268,96 +291,41 @@ impl VulkanBackend {
 }
 
 impl VulkanBackend {
-    pub fn update_sprite_shader(&self, sha_man: &mut ShaderManager, tex_man: &TextureManager, camera: &HellCamera, render_data: &RenderData) -> HellResult<()> {
-        let shader = sha_man.shader_mut(sha_man.handle_res("sprite")?);
+    /// used by the world and the ui pass, `key` selects the pipeline
+    pub fn update_sprite_shader(&self, sha_man: &mut ShaderManager, tex_man: &TextureManager, key: HellName, camera: &HellCamera, render_data: &RenderData) -> HellResult<()> {
+        profile_scope!("update_sprite_shader");
+
+        let shader = sha_man.shader_mut(sha_man.handle_res(key)?);
 
         // global
         // --------
         shader.bind_global();
 
         shader.set_uniform(
-            shader.uniform_handle_res("view")?,
+            shader.uniform_handle_res(hell_name!("view"))?,
             &[camera.view]
         )?;
         shader.set_uniform(
-            shader.uniform_handle_res("proj")?,
+            shader.uniform_handle_res(hell_name!("proj"))?,
             &[camera.proj]
         )?;
         shader.set_uniform(
-            shader.uniform_handle_res("view_proj")?,
+            shader.uniform_handle_res(hell_name!("view_proj"))?,
             &[camera.view_proj]
         )?;
         shader.apply_global_scope(&self.frame, tex_man)?;
 
         // instance
         // --------
-        let dummy = shader.uniform_handle_res("dummy")?;
-        shader.bind_instance(0);
-        shader.set_uniform(dummy, &[glam::vec4(0.0, 1.0, 0.0, 1.0)])?;
-        const TMP_HANDLE: ResourceHandle = ResourceHandle::new(1);
-        shader.apply_instance_scope(&self.frame, tex_man, TMP_HANDLE);
+        // one entry per material, they are bound per draw
+        shader.update_instance_entries(&self.frame, tex_man)?;
 
         // local
         // -----
         shader.bind_local(0);
-        let local_val: Vec<_> = render_data.transforms.iter().map(|t| t.create_model_mat()).collect();
-        shader.set_local_storage(&local_val);
-        shader.apply_local_scope(&self.frame);
-
-        Ok(())
-    }
-
-    #[allow(unused)]
-    pub fn update_test_shader(&self, sha_man: &mut ShaderManager, tex_man: &TextureManager, render_data: &RenderData) -> HellResult<()> {
-        let cam = HellCamera::new(self.swapchain.aspect_ratio());
-
-        let mut shader = sha_man.shader_mut(sha_man.handle("test").unwrap());
-
-        // --------------------------------------
-
-        shader.bind_global();
-
-        if let Some(mut uni) = shader.uniform_handle("view") {
-            shader.set_uniform(uni, &[cam.view])?;
-        }
-
-        if let Some(mut uni) = shader.uniform_handle("proj") {
-            shader.set_uniform(uni, &[cam.proj])?;
-        }
-
-        if let Some(mut uni) = shader.uniform_handle("view_proj") {
-            shader.set_uniform(uni, &[cam.view_proj])?;
-        }
-
-        shader.apply_global_scope(&self.frame, tex_man)?;
-
-        // --------------------------------------
-
-        if let Some(mut uni) = shader.uniform_handle("shared_color") {
-            const ENTYR_0: ResourceHandle = ResourceHandle::new(0);
-            shader.bind_shared(0);
-            shader.set_uniform(uni, &[glam::vec4(1.0, 0.0, 0.0, 1.0)])?;
-            shader.apply_shared_scope(&self.frame, tex_man, ENTYR_0);
-        }
-
-        // --------------------------------------
-
-        if let Some(mut uni) = shader.uniform_handle("instance_color") {
-            const ENTRY_0: ResourceHandle = ResourceHandle::new(0);
-            const ENTRY_1: ResourceHandle = ResourceHandle::new(1);
-            shader.bind_instance(0);
-            shader.set_uniform(uni, &[glam::vec4(0.0, 1.0, 0.0, 1.0)])?;
-            shader.bind_instance(1);
-            shader.set_uniform(uni, &[glam::vec4(1.0, 0.2, 1.0, 1.0)])?;
-            shader.apply_instance_scope(&self.frame, tex_man, ENTRY_0);
-        }
-
-        // --------------------------------------
-
-        shader.bind_local(0);
-        let local_val: Vec<_> = render_data.transforms.iter().map(|t| t.create_model_mat()).collect();
-        shader.set_local_storage(&local_val);
-        shader.apply_local_scope(&self.frame);
+        let local_val = self.frame_alloc.arena().alloc_slice_fill_iter(render_data.local_data());
+        shader.set_local_storage(local_val)?;
+        shader.apply_local_scope(&self.frame)?;
 
         Ok(())
     }
@@ -372,9 +340,18 @@ impl VulkanBackend {
         VulkanTexture::new_default(&self.ctx, &self.cmds)
     }
 
-    pub fn create_sprite_shader(&self, global_tex: ResourceHandle) -> HellResult<ShaderProgram> {
-        let shader = ShaderProgramBuilder::new(&self.ctx, config::SPRITE_SHADER_PATH)
-            .with_depth_test()
+    /// the world pass tests depth, the ui is drawn in order on top of it
+    pub fn create_sprite_shader(&self, global_tex: ResourceHandle, pass: BultinRenderPassType) -> HellResult<ShaderProgram> {
+        let mut builder = ShaderProgramBuilder::new(&self.ctx, config::SPRITE_SHADER_PATH);
+        let render_pass = match pass {
+            BultinRenderPassType::World => {
+                builder = builder.with_depth_test();
+                &self.render_pass_data.world_render_pass
+            }
+            BultinRenderPassType::Ui => &self.render_pass_data.ui_render_pass,
+        };
+
+        let shader = builder
             .with_attribute(NumberFormat::R32G32B32_SFLOAT)
             .with_attribute(NumberFormat::R32G32_SFLOAT)
             .with_global_uniform::<glam::Mat4>("view")
@@ -383,28 +360,14 @@ impl VulkanBackend {
             .with_instance_uniform::<glam::Mat4>("dummy")
             .with_instance_sampler("instance_tex_0")?
             .with_local_uniform::<glam::Mat4>("model")
-            .build(&self.swapchain, &self.render_pass_data.world_render_pass)?;
-
-        println!("create sprite shader: \n{:#?}", shader);
-
-        Ok(shader)
-    }
-
-    pub fn create_test_shader(&self, global_tex: ResourceHandle) -> HellResult<ShaderProgram> {
-        let shader = ShaderProgramBuilder::new(&self.ctx, config::TEST_SHADER_PATH)
-            .with_attribute(NumberFormat::R32G32B32_SFLOAT)
-            .with_attribute(NumberFormat::R32G32_SFLOAT)
-            .with_global_uniform::<glam::Mat4>("view")
-            .with_global_uniform::<glam::Mat4>("proj")
-            .with_global_uniform::<glam::Mat4>("view_proj")
-            .with_global_sampler("global_tex", global_tex)?
-            .with_shared_uniform::<glam::Vec4>("shared_color")
-            .with_instance_uniform::<glam::Vec4>("instance_color")
-            .with_instance_sampler("instance_tex")?
-            .with_local_uniform::<glam::Mat4>("model")
-            .build(&self.swapchain, &self.render_pass_data.ui_render_pass)?;
-
-        println!("create test shader: \n{:#?}", shader);
+            .with_local_uniform::<glam::Vec4>("uv_rect")
+            .with_local_uniform::<glam::Vec4>("tint")
+            .with_local_uniform::<glam::Vec4>("sdf_params")
+            .with_local_uniform::<glam::Vec4>("outline_color")
+            .with_local_uniform::<glam::Vec4>("shadow_color")
+            .build(&self.swapchain, render_pass)?;
+
+        trace!(target: target::RENDER, "create sprite shader ({:?}): \n{:#?}", pass, shader);
 
         Ok(shader)
     }
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 use hell_common::window::HellSurfaceInfo;
 use hell_core::error::HellResult;
+use hell_core::logging::target;
+use tracing::trace;
 use crate::config;
 
 use super::debugging::VulkanDebugData;
@@ -45,9 +47,9 @@ impl VulkanContext {
     }
 
     pub fn wait_device_idle(&self) -> HellResult<()> {
-        println!("> waiting for the device to be idle...");
+        trace!(target: target::RENDER, "waiting for the device to be idle...");
         self.device.wait_idle()?;
-        println!("> done waiting for the device to be idle...");
+        trace!(target: target::RENDER, "done waiting for the device to be idle");
 
         Ok(())
     }
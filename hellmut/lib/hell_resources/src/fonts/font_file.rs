@@ -1,11 +1,62 @@
-use std::fs::File;
-use std::io::{BufReader, BufRead};
+use std::collections::HashMap;
 use std::path::Path;
+use std::str::FromStr;
 
-use hell_core::error::HellResult;
+use hell_core::error::{HellError, HellErrorHelper, HellResult};
 
+
+
+// ----------------------------------------------------------------------------
+// rows
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FntFileInfo {
+    pub face: String,
+    /// negative if the size matches the cell height instead of the character height
+    pub size: i32,
+    pub bold: bool,
+    pub italic: bool,
+    pub charset: String,
+    pub unicode: bool,
+    /// height stretch in percent
+    pub stretch_h: i32,
+    pub smooth: bool,
+    /// supersampling level, 1 means no supersampling
+    pub aa: i32,
+    /// up, right, down, left
+    pub padding: [i32; 4],
+    /// horizontal, vertical
+    pub spacing: [i32; 2],
+    pub outline: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FntFileCommon {
+    pub line_height: i32,
+    /// distance from the top of a line to the baseline
+    pub base: i32,
+    pub scale_w: i32,
+    pub scale_h: i32,
+    pub pages: i32,
+    /// each color channel holds a different set of characters
+    pub packed: bool,
+    pub alpha_chnl: i32,
+    pub red_chnl: i32,
+    pub green_chnl: i32,
+    pub blue_chnl: i32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FntFilePage {
+    pub id: u32,
+    /// relative to the font file
+    pub file: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct FntFileCharRow {
-    pub id: u64,
+    pub id: u32,
     pub x: i32,
     pub y: i32,
     pub width: i32,
@@ -13,31 +64,698 @@ pub struct FntFileCharRow {
     pub xoffset: i32,
     pub yoffset: i32,
     pub xadvance: i32,
-    pub yadvance: i32,
+    pub page: u32,
+    /// bitmask of the texture channels the glyph is stored in, 15 means all of them
+    pub chnl: u32,
 }
 
-#[allow(dead_code)]
-pub struct FntFile {
-    chars: Vec<FntFileCharRow>
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FntFileKerning {
+    pub first: u32,
+    pub second: u32,
+    pub amount: i32,
 }
 
 
 
+// ----------------------------------------------------------------------------
+// fnt file
+// ----------------------------------------------------------------------------
+
+/// BMFont description of a bitmap font, loaded from the text, xml or binary format
+#[derive(Debug, Default, Clone)]
+pub struct FntFile {
+    pub info: FntFileInfo,
+    pub common: FntFileCommon,
+    pub pages: Vec<FntFilePage>,
+    pub chars: Vec<FntFileCharRow>,
+    pub kernings: Vec<FntFileKerning>,
+
+    char_lookup: HashMap<u32, usize>,
+    kerning_lookup: HashMap<(u32, u32), i32>,
+}
+
 impl FntFile {
-    #[allow(unused)]
+    const BINARY_MAGIC: &'static [u8] = b"BMF";
+
+    /// the format is detected from the content of the file
     pub fn from_file(path: &Path) -> HellResult<Self> {
-        let chars = Vec::new();
+        let data = std::fs::read(path)?;
+
+        if data.starts_with(Self::BINARY_MAGIC) {
+            return Self::from_binary(&data);
+        }
+
+        let txt = std::str::from_utf8(&data)?;
+        if txt.trim_start().starts_with('<') {
+            Self::from_xml(txt)
+        } else {
+            Self::from_text(txt)
+        }
+    }
+
+    pub fn new(info: FntFileInfo, common: FntFileCommon, pages: Vec<FntFilePage>, chars: Vec<FntFileCharRow>, kernings: Vec<FntFileKerning>) -> Self {
+        let file = Self { info, common, pages, chars, kernings, ..Default::default() };
+        file.finish(true, true).expect("the blocks are always present")
+    }
+
+    pub fn char(&self, id: u32) -> Option<&FntFileCharRow> {
+        self.char_lookup.get(&id).map(|idx| &self.chars[*idx])
+    }
+
+    /// additional horizontal advance when `second` follows `first`
+    pub fn kerning(&self, first: u32, second: u32) -> i32 {
+        self.kerning_lookup.get(&(first, second)).copied().unwrap_or(0)
+    }
+
+    fn finish(mut self, has_info: bool, has_common: bool) -> HellResult<Self> {
+        if !has_info {
+            return Err(HellErrorHelper::resource_msg_err("fnt file is missing the 'info' block"));
+        }
+        if !has_common {
+            return Err(HellErrorHelper::resource_msg_err("fnt file is missing the 'common' block"));
+        }
+
+        self.char_lookup = self.chars.iter().enumerate().map(|(idx, c)| (c.id, idx)).collect();
+        self.kerning_lookup = self.kernings.iter().map(|k| ((k.first, k.second), k.amount)).collect();
+        Ok(self)
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// text and xml format
+// ----------------------------------------------------------------------------
+
+impl FntFile {
+    /// one block per line, e.g. `char id=32 x=0 y=0 ...`
+    pub fn from_text(txt: &str) -> HellResult<Self> {
+        let mut builder = FntBuilder::default();
+
+        for (idx, line) in txt.lines().enumerate() {
+            let line_nr = idx + 1;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let (tag, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let attrs = Attributes::parse(rest, line_nr)?;
+            if !builder.add(tag, &attrs)? {
+                return Err(attrs.err(format!("unknown block '{tag}'")));
+            }
+        }
+
+        builder.finish()
+    }
+
+    /// same attributes as the text format, stored in self-closing tags like `<char id="32" ... />`
+    pub fn from_xml(txt: &str) -> HellResult<Self> {
+        let mut builder = FntBuilder::default();
+        let mut rest = txt;
+
+        while let Some(start) = rest.find('<') {
+            let line_nr = txt[..txt.len() - rest.len() + start].matches('\n').count() + 1;
+            rest = &rest[start + 1..];
+
+            // declarations and comments
+            if let Some(comment) = rest.strip_prefix("!--") {
+                let end = comment.find("-->").ok_or_else(|| line_err(line_nr, "unterminated comment"))?;
+                rest = &comment[end + 3..];
+                continue;
+            }
+
+            let end = rest.find('>').ok_or_else(|| line_err(line_nr, "unterminated tag"))?;
+            let tag = rest[..end].trim();
+            rest = &rest[end + 1..];
+
+            if tag.starts_with('?') || tag.starts_with('!') || tag.starts_with('/') {
+                continue;
+            }
+
+            let tag = tag.trim_end_matches('/').trim_end();
+            let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            let attrs = Attributes::parse(attrs, line_nr)?;
+
+            // containers don't hold any data
+            if !builder.add(name, &attrs)? && !matches!(name, "font" | "pages" | "kernings") {
+                return Err(attrs.err(format!("unknown tag '{name}'")));
+            }
+        }
+
+        builder.finish()
+    }
+}
+
+impl FntFile {
+    /// writes the text format, which can be read by `from_text`
+    pub fn to_text(&self) -> String {
+        let flag = |v: bool| v as i32;
+        let info = &self.info;
+        let common = &self.common;
+        let [pad_up, pad_right, pad_down, pad_left] = info.padding;
+        let [spacing_h, spacing_v] = info.spacing;
+
+        let mut txt = format!(
+            "info face=\"{}\" size={} bold={} italic={} charset=\"{}\" unicode={} stretchH={} smooth={} aa={} padding={pad_up},{pad_right},{pad_down},{pad_left} spacing={spacing_h},{spacing_v} outline={}\n",
+            info.face, info.size, flag(info.bold), flag(info.italic), info.charset, flag(info.unicode), info.stretch_h, flag(info.smooth), info.aa, info.outline,
+        );
+        txt += &format!(
+            "common lineHeight={} base={} scaleW={} scaleH={} pages={} packed={} alphaChnl={} redChnl={} greenChnl={} blueChnl={}\n",
+            common.line_height, common.base, common.scale_w, common.scale_h, common.pages, flag(common.packed), common.alpha_chnl, common.red_chnl, common.green_chnl, common.blue_chnl,
+        );
+
+        for page in &self.pages {
+            txt += &format!("page id={} file=\"{}\"\n", page.id, page.file);
+        }
+
+        txt += &format!("chars count={}\n", self.chars.len());
+        for c in &self.chars {
+            txt += &format!(
+                "char id={} x={} y={} width={} height={} xoffset={} yoffset={} xadvance={} page={} chnl={}\n",
+                c.id, c.x, c.y, c.width, c.height, c.xoffset, c.yoffset, c.xadvance, c.page, c.chnl,
+            );
+        }
+
+        if !self.kernings.is_empty() {
+            txt += &format!("kernings count={}\n", self.kernings.len());
+            for k in &self.kernings {
+                txt += &format!("kerning first={} second={} amount={}\n", k.first, k.second, k.amount);
+            }
+        }
+
+        txt
+    }
+}
+
+#[derive(Default)]
+struct FntBuilder {
+    file: FntFile,
+    has_info: bool,
+    has_common: bool,
+}
+
+impl FntBuilder {
+    /// returns false for unknown tags
+    fn add(&mut self, tag: &str, attrs: &Attributes) -> HellResult<bool> {
+        let file = &mut self.file;
+
+        match tag {
+            "info" => {
+                let padding = attrs.list("padding")?;
+                let spacing = attrs.list("spacing")?;
+
+                self.has_info = true;
+                file.info = FntFileInfo {
+                    face: attrs.get_or("face", String::new())?,
+                    size: attrs.get_or("size", 0)?,
+                    bold: attrs.flag("bold")?,
+                    italic: attrs.flag("italic")?,
+                    charset: attrs.get_or("charset", String::new())?,
+                    unicode: attrs.flag("unicode")?,
+                    stretch_h: attrs.get_or("stretchH", 100)?,
+                    smooth: attrs.flag("smooth")?,
+                    aa: attrs.get_or("aa", 1)?,
+                    padding: padding.unwrap_or_default(),
+                    spacing: spacing.unwrap_or_default(),
+                    outline: attrs.get_or("outline", 0)?,
+                };
+            }
+            "common" => {
+                self.has_common = true;
+                file.common = FntFileCommon {
+                    line_height: attrs.get("lineHeight")?,
+                    base: attrs.get("base")?,
+                    scale_w: attrs.get("scaleW")?,
+                    scale_h: attrs.get("scaleH")?,
+                    pages: attrs.get_or("pages", 1)?,
+                    packed: attrs.flag("packed")?,
+                    alpha_chnl: attrs.get_or("alphaChnl", 0)?,
+                    red_chnl: attrs.get_or("redChnl", 0)?,
+                    green_chnl: attrs.get_or("greenChnl", 0)?,
+                    blue_chnl: attrs.get_or("blueChnl", 0)?,
+                };
+            }
+            "page" => {
+                file.pages.push(FntFilePage {
+                    id: attrs.get("id")?,
+                    file: attrs.get("file")?,
+                });
+            }
+            "char" => {
+                file.chars.push(FntFileCharRow {
+                    id: attrs.get("id")?,
+                    x: attrs.get("x")?,
+                    y: attrs.get("y")?,
+                    width: attrs.get("width")?,
+                    height: attrs.get("height")?,
+                    xoffset: attrs.get_or("xoffset", 0)?,
+                    yoffset: attrs.get_or("yoffset", 0)?,
+                    xadvance: attrs.get("xadvance")?,
+                    page: attrs.get_or("page", 0)?,
+                    chnl: attrs.get_or("chnl", 15)?,
+                });
+            }
+            "kerning" => {
+                file.kernings.push(FntFileKerning {
+                    first: attrs.get("first")?,
+                    second: attrs.get("second")?,
+                    amount: attrs.get("amount")?,
+                });
+            }
+            // the counts are implied by the rows
+            "chars" | "kernings" => {
+                let _ = attrs.get_or("count", 0_usize)?;
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn finish(self) -> HellResult<FntFile> {
+        self.file.finish(self.has_info, self.has_common)
+    }
+}
+
+// attributes
+// ----------
+struct Attributes<'a> {
+    line: usize,
+    pairs: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Attributes<'a> {
+    /// `key=value` pairs separated by whitespace, values can be quoted
+    fn parse(txt: &'a str, line: usize) -> HellResult<Self> {
+        let mut pairs = Vec::new();
+        let mut rest = txt.trim_start();
+
+        while !rest.is_empty() {
+            let (key, after_key) = rest.split_once('=').ok_or_else(|| line_err(line, format!("expected 'key=value' but got '{rest}'")))?;
+            let key = key.trim();
+            if key.is_empty() || key.contains(char::is_whitespace) {
+                return Err(line_err(line, format!("invalid key '{key}'")));
+            }
+
+            let after_key = after_key.trim_start();
+            let (value, after_value) = if let Some(quoted) = after_key.strip_prefix('"') {
+                let end = quoted.find('"').ok_or_else(|| line_err(line, format!("unterminated string for '{key}'")))?;
+                (&quoted[..end], &quoted[end + 1..])
+            } else {
+                after_key.split_at(after_key.find(char::is_whitespace).unwrap_or(after_key.len()))
+            };
+
+            pairs.push((key, value));
+            rest = after_value.trim_start();
+        }
+
+        Ok(Self { line, pairs })
+    }
+
+    fn value(&self, key: &str) -> Option<&'a str> {
+        self.pairs.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+
+    fn get<T: FromStr>(&self, key: &str) -> HellResult<T> {
+        let val = self.value(key).ok_or_else(|| self.err(format!("missing '{key}'")))?;
+        self.convert(key, val)
+    }
 
-        // let file = std::fs::read_to_string(path)?;
-        let buff_reader = BufReader::new(File::open(path)?);
+    fn get_or<T: FromStr>(&self, key: &str, default: T) -> HellResult<T> {
+        match self.value(key) {
+            Some(val) => self.convert(key, val),
+            None      => Ok(default),
+        }
+    }
+
+    fn flag(&self, key: &str) -> HellResult<bool> {
+        Ok(self.get_or(key, 0_i32)? != 0)
+    }
+
+    fn list<const N: usize>(&self, key: &str) -> HellResult<Option<[i32; N]>> {
+        let Some(val) = self.value(key) else {
+            return Ok(None);
+        };
+
+        let items = val.split(',').map(|v| self.convert(key, v.trim())).collect::<HellResult<Vec<i32>>>()?;
+        let items: [i32; N] = items.try_into().map_err(|_| self.err(format!("'{key}' needs {N} values but got '{val}'")))?;
+        Ok(Some(items))
+    }
+
+    fn convert<T: FromStr>(&self, key: &str, val: &str) -> HellResult<T> {
+        val.parse().map_err(|_| self.err(format!("invalid value '{val}' for '{key}'")))
+    }
+
+    fn err(&self, msg: impl AsRef<str>) -> HellError {
+        line_err(self.line, msg)
+    }
+}
 
-        // TODO:
-        for line in buff_reader.lines().flatten() {
-            // println!("ROW: '{}'", line);
+fn line_err(line: usize, msg: impl AsRef<str>) -> HellError {
+    HellErrorHelper::resource_msg_err(format!("fnt line {line}: {}", msg.as_ref()))
+}
+
+
+
+// ----------------------------------------------------------------------------
+// binary format
+// ----------------------------------------------------------------------------
+
+impl FntFile {
+    const BINARY_VERSION: u8 = 3;
+    const CHAR_SIZE: usize = 20;
+    const KERNING_SIZE: usize = 10;
+
+    /// version 3 of the binary format
+    pub fn from_binary(data: &[u8]) -> HellResult<Self> {
+        let mut reader = BinaryReader { data, pos: 0 };
+
+        if reader.bytes(3)? != Self::BINARY_MAGIC {
+            return Err(reader.err("missing 'BMF' header"));
+        }
+        let version = reader.u8()?;
+        if version != Self::BINARY_VERSION {
+            return Err(reader.err(format!("unsupported version {version}")));
         }
 
-        Ok(Self {
-            chars
+        let mut file = FntFile::default();
+        let mut has_info = false;
+        let mut has_common = false;
+
+        while !reader.is_done() {
+            let block_type = reader.u8()?;
+            let block_size = reader.u32()? as usize;
+            let block_end = reader.pos + block_size;
+            let mut block = BinaryReader { data: &data[..block_end.min(data.len())], pos: reader.pos };
+
+            match block_type {
+                1 => {
+                    has_info = true;
+                    file.info = Self::read_info(&mut block)?;
+                }
+                2 => {
+                    has_common = true;
+                    file.common = Self::read_common(&mut block)?;
+                }
+                3 => {
+                    let mut id = 0;
+                    while !block.is_done() {
+                        file.pages.push(FntFilePage { id, file: block.string()? });
+                        id += 1;
+                    }
+                }
+                4 => {
+                    if !block_size.is_multiple_of(Self::CHAR_SIZE) {
+                        return Err(block.err(format!("chars block size {block_size} is not a multiple of {}", Self::CHAR_SIZE)));
+                    }
+                    while !block.is_done() {
+                        file.chars.push(FntFileCharRow {
+                            id: block.u32()?,
+                            x: block.u16()? as i32,
+                            y: block.u16()? as i32,
+                            width: block.u16()? as i32,
+                            height: block.u16()? as i32,
+                            xoffset: block.i16()? as i32,
+                            yoffset: block.i16()? as i32,
+                            xadvance: block.i16()? as i32,
+                            page: block.u8()? as u32,
+                            chnl: block.u8()? as u32,
+                        });
+                    }
+                }
+                5 => {
+                    if !block_size.is_multiple_of(Self::KERNING_SIZE) {
+                        return Err(block.err(format!("kerning block size {block_size} is not a multiple of {}", Self::KERNING_SIZE)));
+                    }
+                    while !block.is_done() {
+                        file.kernings.push(FntFileKerning {
+                            first: block.u32()?,
+                            second: block.u32()?,
+                            amount: block.i16()? as i32,
+                        });
+                    }
+                }
+                _ => return Err(reader.err(format!("unknown block type {block_type}"))),
+            }
+
+            if block_end > data.len() {
+                return Err(reader.err(format!("block of type {block_type} runs past the end of the file")));
+            }
+            reader.pos = block_end;
+        }
+
+        file.finish(has_info, has_common)
+    }
+
+    fn read_info(block: &mut BinaryReader) -> HellResult<FntFileInfo> {
+        let size = block.i16()? as i32;
+        let bits = block.u8()?;
+        let charset = block.u8()?;
+
+        Ok(FntFileInfo {
+            size,
+            smooth: bits & 0x80 != 0,
+            unicode: bits & 0x40 != 0,
+            italic: bits & 0x20 != 0,
+            bold: bits & 0x10 != 0,
+            charset: if bits & 0x40 != 0 { String::new() } else { charset.to_string() },
+            stretch_h: block.u16()? as i32,
+            aa: block.u8()? as i32,
+            padding: [block.u8()? as i32, block.u8()? as i32, block.u8()? as i32, block.u8()? as i32],
+            spacing: [block.u8()? as i32, block.u8()? as i32],
+            outline: block.u8()? as i32,
+            face: block.string()?,
         })
     }
+
+    fn read_common(block: &mut BinaryReader) -> HellResult<FntFileCommon> {
+        Ok(FntFileCommon {
+            line_height: block.u16()? as i32,
+            base: block.u16()? as i32,
+            scale_w: block.u16()? as i32,
+            scale_h: block.u16()? as i32,
+            pages: block.u16()? as i32,
+            packed: block.u8()? & 0x01 != 0,
+            alpha_chnl: block.u8()? as i32,
+            red_chnl: block.u8()? as i32,
+            green_chnl: block.u8()? as i32,
+            blue_chnl: block.u8()? as i32,
+        })
+    }
+}
+
+/// little endian
+struct BinaryReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    fn is_done(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn bytes(&mut self, count: usize) -> HellResult<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos + count).ok_or_else(|| self.err("unexpected end of data"))?;
+        self.pos += count;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> HellResult<[u8; N]> {
+        Ok(self.bytes(N)?.try_into().unwrap_or([0; N]))
+    }
+
+    fn u8(&mut self) -> HellResult<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> HellResult<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i16(&mut self) -> HellResult<i16> {
+        self.array().map(i16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> HellResult<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    /// null terminated
+    fn string(&mut self) -> HellResult<String> {
+        let rest = &self.data[self.pos.min(self.data.len())..];
+        let len = rest.iter().position(|b| *b == 0).ok_or_else(|| self.err("unterminated string"))?;
+        let val = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(val)
+    }
+
+    fn err(&self, msg: impl AsRef<str>) -> HellError {
+        HellErrorHelper::resource_msg_err(format!("fnt byte {}: {}", self.pos, msg.as_ref()))
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FONT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../assets/fonts/font_bm_fira_code.fnt");
+    const SDF_FONT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../assets/fonts/font_sdf_dejavu_sans.fnt");
+
+    fn font() -> FntFile {
+        FntFile::from_file(Path::new(FONT_PATH)).unwrap()
+    }
+
+    #[test]
+    fn test_bundled_font() {
+        let font = font();
+
+        assert_eq!(font.info.face, "FiraCodeNerdFontComplete-Bold");
+        assert_eq!(font.info.size, 72);
+        assert!(font.info.unicode && font.info.smooth && !font.info.bold);
+        assert_eq!(font.info.padding, [1, 1, 1, 1]);
+        assert_eq!(font.info.spacing, [1, 1]);
+
+        assert_eq!(font.common, FntFileCommon { line_height: 72, base: 66, scale_w: 449, scale_h: 449, pages: 1, ..Default::default() });
+        assert_eq!(font.pages, vec![FntFilePage { id: 0, file: "Unnamed.png".into() }]);
+        assert_eq!(font.chars.len(), 91);
+        assert!(font.kernings.is_empty());
+
+        assert_eq!(font.char(33), Some(&FntFileCharRow {
+            id: 33, x: 432, y: 0, width: 16, height: 54, xoffset: 14, yoffset: 15, xadvance: 45, page: 0, chnl: 15,
+        }));
+        assert_eq!(font.char(8470).unwrap().xoffset, -1);
+        assert!(font.char(1).is_none());
+    }
+
+    #[test]
+    fn test_bundled_sdf_font() {
+        let font = FntFile::from_file(Path::new(SDF_FONT_PATH)).unwrap();
+        let page = Path::new(SDF_FONT_PATH).with_file_name(&font.pages[0].file);
+
+        assert_eq!(font.info.face, "DejaVuSans");
+        assert_eq!(font.info.padding, [6, 6, 6, 6]);
+        assert!(page.is_file());
+        // everything the hud and the menus show in english and german
+        assert!(" 0123456789:azAZäöüÄÖÜß".chars().all(|c| font.char(c as u32).is_some()));
+    }
+
+    #[test]
+    fn test_text_errors() {
+        let err = |txt: &str| format!("{:?}", FntFile::from_text(txt).unwrap_err());
+
+        let common = "info face=\"a b\" size=10\ncommon lineHeight=10 base=8 scaleW=64 scaleH=64\n";
+        assert!(FntFile::from_text(common).is_ok());
+        assert!(err(&format!("{common}char id=1 x=0 y=0 width=1 height=two xadvance=1")).contains("fnt line 3: invalid value 'two' for 'height'"));
+        assert!(err(&format!("{common}\nchar id=1 x=0 y=0 width=1 height=1")).contains("fnt line 4: missing 'xadvance'"));
+        assert!(err(&format!("{common}glyph id=1")).contains("fnt line 3: unknown block 'glyph'"));
+        assert!(err("info face=\"a").contains("fnt line 1: unterminated string"));
+        assert!(err("info padding=1,1").contains("'padding' needs 4 values"));
+        assert!(err("info size=10").contains("missing the 'common' block"));
+    }
+
+    #[test]
+    fn test_to_text() {
+        let font = font();
+        let copy = FntFile::from_text(&font.to_text()).unwrap();
+
+        assert_eq!(copy.info, font.info);
+        assert_eq!(copy.common, font.common);
+        assert_eq!(copy.pages, font.pages);
+        assert_eq!(copy.chars, font.chars);
+
+        let kernings = vec![FntFileKerning { first: 65, second: 86, amount: -2 }];
+        let copy = FntFile::new(font.info.clone(), font.common.clone(), Vec::new(), Vec::new(), kernings);
+        let copy = FntFile::from_text(&copy.to_text()).unwrap();
+        assert_eq!(copy.kerning(65, 86), -2);
+        assert!(copy.chars.is_empty());
+    }
+
+    #[test]
+    fn test_kerning() {
+        let txt = "info face=a\ncommon lineHeight=10 base=8 scaleW=64 scaleH=64\nkernings count=1\nkerning first=65 second=86 amount=-2\n";
+        let font = FntFile::from_text(txt).unwrap();
+
+        assert_eq!(font.kerning(65, 86), -2);
+        assert_eq!(font.kerning(86, 65), 0);
+    }
+
+    #[test]
+    fn test_xml() {
+        let xml = r#"<?xml version="1.0"?>
+            <font>
+              <info face="Arial" size="32" bold="1" padding="0,0,0,0" spacing="1,1"/>
+              <common lineHeight="32" base="26" scaleW="256" scaleH="256" pages="1" packed="0"/>
+              <pages>
+                <page id="0" file="arial.png" />
+              </pages>
+              <!-- <char id="1"/> -->
+              <chars count="1">
+                <char id="65" x="1" y="2" width="3" height="4" xoffset="5" yoffset="6" xadvance="7" page="0" chnl="15" />
+              </chars>
+              <kernings count="1">
+                <kerning first="65" second="65" amount="1" />
+              </kernings>
+            </font>"#;
+
+        let font = FntFile::from_xml(xml).unwrap();
+        assert_eq!(font.info.face, "Arial");
+        assert!(font.info.bold);
+        assert_eq!(font.pages[0].file, "arial.png");
+        assert_eq!(font.char(65).unwrap().xadvance, 7);
+        assert!(font.char(1).is_none());
+        assert_eq!(font.kerning(65, 65), 1);
+
+        let err = FntFile::from_xml("<font>\n<info face=\"a\"/>\n<common lineHeight=\"x\"/>").unwrap_err();
+        assert!(format!("{err:?}").contains("fnt line 3: invalid value 'x' for 'lineHeight'"));
+    }
+
+    #[test]
+    fn test_binary() {
+        let mut data = b"BMF\x03".to_vec();
+        let mut block = |ty: u8, content: &[u8]| {
+            data.push(ty);
+            data.extend((content.len() as u32).to_le_bytes());
+            data.extend(content);
+        };
+
+        // info: size 32, unicode + bold, stretch 100, aa 1, padding, spacing, outline, name
+        block(1, &[32, 0, 0x50, 0, 100, 0, 1, 1, 2, 3, 4, 1, 1, 0, b'F', b'o', b'o', 0]);
+        // common
+        block(2, &[32, 0, 26, 0, 0, 1, 0, 1, 1, 0, 0, 0, 4, 4, 4]);
+        block(3, b"foo_0.png\0");
+        let mut chars = Vec::new();
+        chars.extend(65_u32.to_le_bytes());
+        for v in [10_u16, 20, 8, 9] {
+            chars.extend(v.to_le_bytes());
+        }
+        for v in [-1_i16, 3, 9] {
+            chars.extend(v.to_le_bytes());
+        }
+        chars.extend([0, 15]);
+        block(4, &chars);
+        block(5, &[65, 0, 0, 0, 65, 0, 0, 0, 0xfe, 0xff]);
+
+        let font = FntFile::from_binary(&data).unwrap();
+        assert_eq!(font.info.face, "Foo");
+        assert!(font.info.unicode && font.info.bold && !font.info.italic);
+        assert_eq!(font.info.padding, [1, 2, 3, 4]);
+        assert_eq!(font.common.scale_w, 256);
+        assert_eq!(font.common.blue_chnl, 4);
+        assert_eq!(font.pages[0].file, "foo_0.png");
+        assert_eq!(font.char(65), Some(&FntFileCharRow {
+            id: 65, x: 10, y: 20, width: 8, height: 9, xoffset: -1, yoffset: 3, xadvance: 9, page: 0, chnl: 15,
+        }));
+        assert_eq!(font.kerning(65, 65), -2);
+
+        let err = FntFile::from_binary(&data[..data.len() - 3]).unwrap_err();
+        assert!(format!("{err:?}").contains("fnt byte"));
+        assert!(FntFile::from_binary(b"BMF\x02").is_err());
+    }
 }
@@ -1,2 +1,4 @@
 mod font_file;
+mod sdf_font;
 pub use font_file::*;
+pub use sdf_font::*;
@@ -0,0 +1,420 @@
+use std::path::Path;
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use hell_core::error::{HellErrorHelper, HellResult};
+
+use super::{FntFile, FntFileCharRow, FntFileCommon, FntFileInfo, FntFileKerning, FntFilePage};
+
+
+
+// ----------------------------------------------------------------------------
+// config
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SdfFontConfig {
+    /// written to the metrics file
+    pub face: String,
+    /// line height in atlas pixels
+    pub size: u32,
+    /// how far the distance field reaches outside and inside of the glyphs, in atlas pixels
+    pub spread: u32,
+    /// glyphs are rasterized at `size * supersample` before the distances are measured
+    pub supersample: u32,
+    pub chars: Vec<char>,
+    pub atlas_width: u32,
+    /// name of the atlas, relative to the metrics file
+    pub page_file: String,
+}
+
+impl Default for SdfFontConfig {
+    fn default() -> Self {
+        Self {
+            face: String::new(),
+            size: 48,
+            spread: 6,
+            supersample: 4,
+            chars: (' '..='~').collect(),
+            atlas_width: 512,
+            page_file: "font_sdf.png".to_owned(),
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// sdf font
+// ----------------------------------------------------------------------------
+
+/// single channel signed distance field atlas with BMFont metrics
+///
+/// the distance is stored in the alpha channel of a white image: 0.5 is the outline of the glyph, larger values are inside of it
+/// and the value changes by 0.5 over `spread` pixels.
+#[derive(Debug, Clone)]
+pub struct SdfFont {
+    pub fnt: FntFile,
+    pub atlas: image::RgbaImage,
+}
+
+impl SdfFont {
+    /// rasterizes a ttf or otf file
+    pub fn from_file(path: &Path, config: &SdfFontConfig) -> HellResult<Self> {
+        let data = std::fs::read(path)?;
+        Self::from_data(data, config)
+    }
+
+    pub fn from_data(data: Vec<u8>, config: &SdfFontConfig) -> HellResult<Self> {
+        let font = FontVec::try_from_vec(data).map_err(|e| HellErrorHelper::resource_msg_err(format!("failed to parse font: {e}")))?;
+        Self::generate(&font, config)
+    }
+
+    pub fn generate(font: &impl Font, config: &SdfFontConfig) -> HellResult<Self> {
+        if config.size == 0 || config.supersample == 0 || config.atlas_width == 0 {
+            return Err(HellErrorHelper::resource_msg_err("sdf font: size, supersample and atlas width have to be positive"));
+        }
+
+        let ss = config.supersample as i32;
+        let spread = config.spread as i32;
+        let scaled = font.as_scaled(PxScale::from((config.size * config.supersample) as f32));
+        let ascent = scaled.ascent();
+
+        // rasterize
+        // ---------
+        let mut glyphs: Vec<(FntFileCharRow, Option<Bitmap>)> = Vec::new();
+        let mut ids = Vec::new();
+        for c in &config.chars {
+            let id = scaled.glyph_id(*c);
+            // missing characters map to the 'notdef' glyph
+            if id.0 == 0 || ids.iter().any(|(prev, _)| *prev == *c) {
+                continue;
+            }
+            ids.push((*c, id));
+
+            let mut row = FntFileCharRow {
+                id: *c as u32,
+                xadvance: (scaled.h_advance(id) / ss as f32).round() as i32,
+                chnl: 15,
+                ..Default::default()
+            };
+
+            let glyph = id.with_scale_and_position(scaled.scale(), ab_glyph::point(0.0, ascent));
+            let bitmap = scaled.outline_glyph(glyph).map(|outline| {
+                let bounds = outline.px_bounds();
+                // the cell is aligned to the atlas pixels and has room for the spread on every side
+                let min_x = (bounds.min.x as i32).div_euclid(ss) - spread;
+                let min_y = (bounds.min.y as i32).div_euclid(ss) - spread;
+                let max_x = (bounds.max.x.ceil() as i32 + ss - 1).div_euclid(ss) + spread;
+                let max_y = (bounds.max.y.ceil() as i32 + ss - 1).div_euclid(ss) + spread;
+
+                let mut coverage = Coverage::new(((max_x - min_x) * ss) as usize, ((max_y - min_y) * ss) as usize);
+                let off_x = bounds.min.x as i32 - min_x * ss;
+                let off_y = bounds.min.y as i32 - min_y * ss;
+                outline.draw(|x, y, c| coverage.set(x as i32 + off_x, y as i32 + off_y, c));
+
+                row.xoffset = min_x;
+                row.yoffset = min_y;
+                row.width = max_x - min_x;
+                row.height = max_y - min_y;
+                coverage.to_sdf(config.supersample as usize, config.spread as f32)
+            });
+
+            glyphs.push((row, bitmap));
+        }
+
+        let kernings = ids.iter()
+            .flat_map(|(first, a)| ids.iter().map(move |(second, b)| (*first, *a, *second, *b)))
+            .filter_map(|(first, a, second, b)| {
+                let amount = (scaled.kern(a, b) / ss as f32).round() as i32;
+                (amount != 0).then_some(FntFileKerning { first: first as u32, second: second as u32, amount })
+            })
+            .collect();
+
+        // pack
+        // ----
+        let (scale_w, scale_h) = pack(&mut glyphs, config.atlas_width as i32)?;
+        let mut atlas = image::RgbaImage::from_pixel(scale_w as u32, scale_h as u32, image::Rgba([255, 255, 255, 0]));
+        for (row, bitmap) in &glyphs {
+            let Some(bitmap) = bitmap else {
+                continue;
+            };
+
+            for (idx, val) in bitmap.data.iter().enumerate() {
+                let x = row.x as u32 + (idx % bitmap.width) as u32;
+                let y = row.y as u32 + (idx / bitmap.width) as u32;
+                atlas.put_pixel(x, y, image::Rgba([255, 255, 255, *val]));
+            }
+        }
+
+        let low_scale = config.supersample as f32;
+        let info = FntFileInfo {
+            face: config.face.clone(),
+            size: config.size as i32,
+            unicode: true,
+            stretch_h: 100,
+            smooth: true,
+            aa: ss,
+            padding: [spread; 4],
+            spacing: [1, 1],
+            ..Default::default()
+        };
+        let common = FntFileCommon {
+            line_height: ((scaled.height() + scaled.line_gap()) / low_scale).ceil() as i32,
+            base: (ascent / low_scale).round() as i32,
+            scale_w,
+            scale_h,
+            pages: 1,
+            ..Default::default()
+        };
+        let pages = vec![FntFilePage { id: 0, file: config.page_file.clone() }];
+        let chars = glyphs.into_iter().map(|(row, _)| row).collect();
+
+        Ok(Self {
+            fnt: FntFile::new(info, common, pages, chars, kernings),
+            atlas,
+        })
+    }
+
+    /// writes the metrics to `fnt_path` and the atlas next to it
+    pub fn save(&self, fnt_path: &Path) -> HellResult<()> {
+        let page = self.fnt.pages.first().ok_or_else(|| HellErrorHelper::resource_msg_err("sdf font has no page"))?;
+        let atlas_path = fnt_path.parent().unwrap_or(Path::new("")).join(&page.file);
+
+        std::fs::write(fnt_path, self.fnt.to_text())?;
+        self.atlas.save(&atlas_path).map_err(|e| HellErrorHelper::resource_msg_err(format!("failed to save sdf atlas '{}': {e}", atlas_path.display())))
+    }
+}
+
+/// shelf packing with a gap of one pixel, the height of the atlas is rounded up to a power of two
+fn pack(glyphs: &mut [(FntFileCharRow, Option<Bitmap>)], width: i32) -> HellResult<(i32, i32)> {
+    const GAP: i32 = 1;
+
+    let mut order: Vec<usize> = (0..glyphs.len()).filter(|idx| glyphs[*idx].1.is_some()).collect();
+    order.sort_by_key(|idx| -glyphs[*idx].0.height);
+
+    let (mut x, mut y, mut shelf_height) = (GAP, GAP, 0);
+    for idx in order {
+        let row = &mut glyphs[idx].0;
+        if row.width + 2 * GAP > width {
+            return Err(HellErrorHelper::resource_msg_err(format!("sdf font: glyph {} doesn't fit into an atlas with a width of {width}", row.id)));
+        }
+
+        if x + row.width + GAP > width {
+            x = GAP;
+            y += shelf_height + GAP;
+            shelf_height = 0;
+        }
+
+        row.x = x;
+        row.y = y;
+        x += row.width + GAP;
+        shelf_height = shelf_height.max(row.height);
+    }
+
+    let height = (y + shelf_height + GAP).max(1) as u32;
+    Ok((width, height.next_power_of_two() as i32))
+}
+
+
+
+// ----------------------------------------------------------------------------
+// distance field
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+struct Bitmap {
+    width: usize,
+    data: Vec<u8>,
+}
+
+struct Coverage {
+    width: usize,
+    height: usize,
+    data: Vec<f32>,
+}
+
+impl Coverage {
+    fn new(width: usize, height: usize) -> Self {
+        Self { width, height, data: vec![0.0; width * height] }
+    }
+
+    fn set(&mut self, x: i32, y: i32, val: f32) {
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            self.data[y as usize * self.width + x as usize] = val;
+        }
+    }
+
+    /// signed distance in pixels for every pixel, positive outside of the glyph
+    fn signed_distances(&self) -> Vec<f32> {
+        let is_inside: Vec<bool> = self.data.iter().map(|c| *c >= 0.5).collect();
+        let to_inside = distance_transform(&is_inside, self.width, self.height);
+        let to_outside = distance_transform(&is_inside.iter().map(|i| !i).collect::<Vec<_>>(), self.width, self.height);
+
+        // the edge lies half a pixel between the centers of an inside and an outside pixel
+        to_inside.iter().zip(&to_outside)
+            .map(|(i, o)| if *i > 0.0 { i.sqrt() - 0.5 } else { -(o.sqrt() - 0.5) })
+            .collect()
+    }
+
+    /// samples the center of every `supersample` block and maps the distance to a byte
+    fn to_sdf(&self, supersample: usize, spread: f32) -> Bitmap {
+        let distances = self.signed_distances();
+        let width = self.width / supersample;
+        let height = self.height / supersample;
+        let spread = spread.max(1.0);
+
+        let mut data = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let hx = x * supersample + supersample / 2;
+                let hy = y * supersample + supersample / 2;
+                let dist = distances[hy * self.width + hx] / supersample as f32;
+                let val = (0.5 - dist / (2.0 * spread)).clamp(0.0, 1.0);
+                data.push((val * 255.0).round() as u8);
+            }
+        }
+
+        Bitmap { width, data }
+    }
+}
+
+/// squared euclidean distance of every pixel to the closest feature pixel (Felzenszwalb and Huttenlocher)
+fn distance_transform(is_feature: &[bool], width: usize, height: usize) -> Vec<f32> {
+    const INF: f32 = 1e20;
+
+    let mut grid: Vec<f32> = is_feature.iter().map(|f| if *f { 0.0 } else { INF }).collect();
+    let len = width.max(height);
+    let mut f = vec![0.0; len];
+    let mut d = vec![0.0; len];
+    let mut v = vec![0; len];
+    let mut z = vec![0.0; len + 1];
+
+    for x in 0..width {
+        for y in 0..height {
+            f[y] = grid[y * width + x];
+        }
+        distance_transform_1d(&f[..height], &mut d[..height], &mut v, &mut z);
+        for y in 0..height {
+            grid[y * width + x] = d[y];
+        }
+    }
+
+    for y in 0..height {
+        f[..width].copy_from_slice(&grid[y * width..(y + 1) * width]);
+        distance_transform_1d(&f[..width], &mut d[..width], &mut v, &mut z);
+        grid[y * width..(y + 1) * width].copy_from_slice(&d[..width]);
+    }
+
+    grid
+}
+
+/// lower envelope of the parabolas rooted at `f`
+fn distance_transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    if f.is_empty() {
+        return;
+    }
+
+    let intersection = |q: usize, p: usize| ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2.0 * q as f32 - 2.0 * p as f32);
+
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+
+    for q in 1..f.len() {
+        let mut s = intersection(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(q, v[k]);
+        }
+
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f32::INFINITY;
+    }
+
+    k = 0;
+    for (q, dist) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let diff = q as f32 - v[k] as f32;
+        *dist = diff * diff + f[v[k]];
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTF_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../assets/fonts/font_sdf_dejavu_sans.ttf");
+
+    #[test]
+    fn test_distance_field() {
+        // 4x4 square in the middle of a 12x12 image
+        let mut coverage = Coverage::new(12, 12);
+        for y in 4..8 {
+            for x in 4..8 {
+                coverage.set(x, y, 1.0);
+            }
+        }
+
+        let dist = coverage.signed_distances();
+        assert_eq!(dist[5 * 12 + 4], -0.5);
+        assert_eq!(dist[5 * 12 + 3], 0.5);
+        assert_eq!(dist[5 * 12], 3.5);
+        assert_eq!(dist[0], (32.0_f32).sqrt() - 0.5);
+
+        // the edge maps to the middle of the value range
+        let sdf = coverage.to_sdf(2, 2.0);
+        assert_eq!(sdf.width, 6);
+        assert_eq!(sdf.data[2 * 6 + 2], 175);
+        assert_eq!(sdf.data[2 * 6 + 1], 112);
+        assert!(sdf.data[0] < 16);
+    }
+
+    #[test]
+    fn test_pack() {
+        let row = |id, width, height| (FntFileCharRow { id, width, height, ..Default::default() }, Some(Bitmap { width: 0, data: Vec::new() }));
+        let mut glyphs = vec![row(1, 10, 5), row(2, 10, 20), row(3, 10, 10), (FntFileCharRow { id: 32, ..Default::default() }, None)];
+
+        assert_eq!(pack(&mut glyphs, 24).unwrap(), (24, 32));
+        let pos: Vec<(i32, i32)> = glyphs.iter().map(|(r, _)| (r.x, r.y)).collect();
+        assert_eq!(pos, vec![(1, 22), (1, 1), (12, 1), (0, 0)]);
+
+        assert!(pack(&mut glyphs, 8).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let data = std::fs::read(TTF_PATH).unwrap();
+
+        let config = SdfFontConfig { face: "DejaVu Sans".into(), size: 32, chars: "AV .".chars().collect(), ..Default::default() };
+        let font = SdfFont::from_data(data, &config).unwrap();
+        let fnt = &font.fnt;
+
+        assert_eq!(fnt.chars.len(), 4);
+        assert_eq!((fnt.common.scale_w, fnt.common.pages), (512, 1));
+        assert_eq!((font.atlas.width() as i32, font.atlas.height() as i32), (fnt.common.scale_w, fnt.common.scale_h));
+        assert!(fnt.common.base > 0 && fnt.common.base < fnt.common.line_height);
+
+        let space = fnt.char(' ' as u32).unwrap();
+        assert_eq!((space.width, space.height), (0, 0));
+        assert!(space.xadvance > 0);
+
+        // the middle of the '.' is inside, its corner outside of the glyph
+        let dot = fnt.char('.' as u32).unwrap();
+        assert!(dot.width > 2 * config.spread as i32);
+        let alpha = |x: i32, y: i32| font.atlas.get_pixel(x as u32, y as u32)[3];
+        assert!(alpha(dot.x + dot.width / 2, dot.y + dot.height / 2) > 128);
+        assert_eq!(alpha(dot.x, dot.y), 0);
+
+        assert!(fnt.kerning('A' as u32, 'V' as u32) < 0);
+        assert!(SdfFont::from_data(vec![1, 2, 3], &config).is_err());
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+use hell_core::error::{HellErrorHelper, HellResult};
+
+use super::{LocalizedString, PluralCategory, PluralRule, StringTable};
+
+
+
+// ----------------------------------------------------------------------------
+// args
+// ----------------------------------------------------------------------------
+
+/// value of a `{name}` placeholder
+#[derive(Debug, Clone, PartialEq)]
+pub enum LocaleArg {
+    Text(String),
+    Number(f64),
+}
+
+impl fmt::Display for LocaleArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocaleArg::Text(txt)  => f.write_str(txt),
+            LocaleArg::Number(n)  => write!(f, "{}", n),
+        }
+    }
+}
+
+impl From<&str> for LocaleArg {
+    fn from(val: &str) -> Self {
+        LocaleArg::Text(val.to_string())
+    }
+}
+
+impl From<String> for LocaleArg {
+    fn from(val: String) -> Self {
+        LocaleArg::Text(val)
+    }
+}
+
+macro_rules! impl_number_arg {
+    ($($t:ty),*) => {
+        $(impl From<$t> for LocaleArg {
+            fn from(val: $t) -> Self {
+                LocaleArg::Number(val as f64)
+            }
+        })*
+    };
+}
+
+impl_number_arg!(i32, i64, u32, u64, usize, f32, f64);
+
+
+
+// ----------------------------------------------------------------------------
+// missing keys
+// ----------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MissingKeys {
+    pub language: String,
+    /// keys that other languages have, sorted
+    pub keys: Vec<String>,
+    /// plural strings without a form the plural rules of the language can select - `other` is used instead
+    pub plural_forms: Vec<(String, PluralCategory)>,
+}
+
+impl MissingKeys {
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.plural_forms.is_empty()
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+// localization
+// ----------------------------------------------------------------------------
+
+/// string tables of all languages and the active one
+///
+/// keys that are missing in the active language are looked up in the fallback language and are shown as the key itself
+/// when that doesn't have them either, so they are easy to spot.
+#[derive(Debug, Clone)]
+pub struct Localization {
+    tables: Vec<StringTable>,
+    active: usize,
+    fallback: usize,
+    revision: u32,
+}
+
+impl Localization {
+    /// name of the arg that selects the plural form
+    pub const PLURAL_ARG: &'static str = "count";
+
+    /// the first language is active and the fallback
+    pub fn new(tables: Vec<StringTable>) -> HellResult<Self> {
+        if tables.is_empty() {
+            return Err(HellErrorHelper::resource_msg_err("localization without string tables"));
+        }
+
+        Ok(Self {
+            tables,
+            active: 0,
+            fallback: 0,
+            revision: 0,
+        })
+    }
+
+    /// loads every yaml file in `dir`, `language` becomes active and the fallback
+    pub fn from_dir(dir: impl AsRef<Path>, language: &str) -> HellResult<Self> {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "yaml" || ext == "yml"))
+            .collect();
+        paths.sort();
+
+        let tables = paths.iter()
+            .map(StringTable::from_file)
+            .collect::<HellResult<Vec<_>>>()?;
+
+        let mut result = Self::new(tables)?;
+        result.set_language(language)?;
+        result.set_fallback(language)?;
+        Ok(result)
+    }
+
+    /// replaces the table of the same language
+    pub fn add_table(&mut self, table: StringTable) {
+        match self.table_idx(&table.language) {
+            Some(idx) => {
+                self.tables[idx] = table;
+                if idx == self.active {
+                    self.revision = self.revision.wrapping_add(1);
+                }
+            }
+            None => self.tables.push(table),
+        }
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.tables.iter().map(|t| t.language.as_str())
+    }
+
+    pub fn language(&self) -> &str {
+        &self.tables[self.active].language
+    }
+
+    pub fn fallback(&self) -> &str {
+        &self.tables[self.fallback].language
+    }
+
+    /// changes with every language switch - texts that were localized with an older revision have to be localized again
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    pub fn set_language(&mut self, language: &str) -> HellResult<()> {
+        let idx = self.find_table(language)?;
+        if idx != self.active {
+            self.active = idx;
+            self.revision = self.revision.wrapping_add(1);
+        }
+
+        Ok(())
+    }
+
+    /// switches to the language after the active one, e.g. for a language button
+    pub fn next_language(&mut self) {
+        if self.tables.len() > 1 {
+            self.active = (self.active + 1) % self.tables.len();
+            self.revision = self.revision.wrapping_add(1);
+        }
+    }
+
+    pub fn set_fallback(&mut self, language: &str) -> HellResult<()> {
+        let idx = self.find_table(language)?;
+        if idx != self.fallback {
+            self.fallback = idx;
+            self.revision = self.revision.wrapping_add(1);
+        }
+
+        Ok(())
+    }
+
+    fn table_idx(&self, language: &str) -> Option<usize> {
+        self.tables.iter().position(|t| t.language.eq_ignore_ascii_case(language))
+    }
+
+    fn find_table(&self, language: &str) -> HellResult<usize> {
+        self.table_idx(language).ok_or_else(|| HellErrorHelper::resource_msg_err(format!("no string table for language '{}'", language)))
+    }
+}
+
+impl Default for Localization {
+    /// an empty english table, every key is shown as it is
+    fn default() -> Self {
+        Self {
+            tables: vec![StringTable::new("en")],
+            active: 0,
+            fallback: 0,
+            revision: 0,
+        }
+    }
+}
+
+// lookup
+// ------
+impl Localization {
+    pub fn contains(&self, key: &str) -> bool {
+        self.tables[self.active].contains(key) || self.tables[self.fallback].contains(key)
+    }
+
+    pub fn text(&self, key: &str) -> String {
+        self.format(key, &[])
+    }
+
+    /// replaces `{name}` with the arg of the same name, `{{` and `}}` are literal braces.
+    /// plural strings select their form with the `count` arg, placeholders without an arg are kept.
+    pub fn format(&self, key: &str, args: &[(&str, LocaleArg)]) -> String {
+        let (table, value) = match self.tables[self.active].get(key) {
+            Some(value) => (&self.tables[self.active], value),
+            None => match self.tables[self.fallback].get(key) {
+                Some(value) => (&self.tables[self.fallback], value),
+                None        => return key.to_string(),
+            },
+        };
+
+        let pattern = match value {
+            LocalizedString::Text(txt) => txt.as_str(),
+            LocalizedString::Plural(forms) => {
+                let count = args.iter().find_map(|(name, arg)| match arg {
+                    LocaleArg::Number(n) if *name == Self::PLURAL_ARG => Some(*n),
+                    _ => None,
+                });
+
+                let category = match count {
+                    Some(n) if n == 0.0 && forms.zero.is_some() => PluralCategory::Zero,
+                    Some(n) => PluralRule::for_language(&table.language).category(n),
+                    None    => PluralCategory::Other,
+                };
+                forms.get(category).unwrap_or(&forms.other)
+            }
+        };
+
+        substitute(pattern, args)
+    }
+
+    /// keys that are in any other table, for every language that misses some
+    pub fn missing_keys(&self) -> Vec<MissingKeys> {
+        let all_keys: BTreeSet<&str> = self.tables.iter().flat_map(|t| t.keys()).collect();
+
+        let mut result = Vec::new();
+        for table in &self.tables {
+            let rule = PluralRule::for_language(&table.language);
+            let mut missing = MissingKeys {
+                language: table.language.clone(),
+                ..Default::default()
+            };
+
+            for key in &all_keys {
+                match table.get(key) {
+                    None => missing.keys.push(key.to_string()),
+                    Some(LocalizedString::Plural(forms)) => {
+                        let absent = rule.categories().iter().filter(|c| forms.get(**c).is_none());
+                        missing.plural_forms.extend(absent.map(|c| (key.to_string(), *c)));
+                    }
+                    Some(LocalizedString::Text(_)) => {}
+                }
+            }
+
+            if !missing.is_empty() {
+                result.push(missing);
+            }
+        }
+
+        result
+    }
+}
+
+fn substitute(pattern: &str, args: &[(&str, LocaleArg)]) -> String {
+    let mut result = String::with_capacity(pattern.len());
+    let mut rest = pattern;
+
+    while let Some(idx) = rest.find(['{', '}']) {
+        result.push_str(&rest[..idx]);
+        let tail = &rest[idx..];
+
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            result.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+
+        let placeholder = tail.strip_prefix('{').and_then(|t| t.find('}').map(|end| &t[..end]));
+        match placeholder {
+            Some(name) => {
+                match args.iter().find(|(n, _)| *n == name.trim()) {
+                    Some((_, arg)) => result.push_str(&arg.to_string()),
+                    None           => result.push_str(&tail[..name.len() + 2]),
+                }
+                rest = &tail[name.len() + 2..];
+            }
+            None => {
+                result.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCALE_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../assets/locales");
+
+    fn localization() -> Localization {
+        let en = StringTable::from_yaml(r#"
+            language: en
+            strings:
+              menu.resume: resume
+              hud.score: "score: {score}"
+              hud.enemies:
+                zero: no enemies
+                one: "{count} enemy"
+                other: "{count} enemies"
+        "#).unwrap();
+        let ru = StringTable::from_yaml(r#"
+            language: ru
+            strings:
+              menu.resume: продолжить
+              hud.enemies:
+                one: "{count} враг"
+                few: "{count} врага"
+                other: "{count} врагов"
+        "#).unwrap();
+
+        Localization::new(vec![en, ru]).unwrap()
+    }
+
+    #[test]
+    fn test_format() {
+        let loc = localization();
+
+        assert_eq!(loc.text("menu.resume"), "resume");
+        assert_eq!(loc.format("hud.score", &[("score", 42.into())]), "score: 42");
+        assert_eq!(loc.format("hud.enemies", &[("count", 0.into())]), "no enemies");
+        assert_eq!(loc.format("hud.enemies", &[("count", 1.into())]), "1 enemy");
+        assert_eq!(loc.format("hud.enemies", &[("count", 3.into())]), "3 enemies");
+        // missing keys and args stay visible
+        assert_eq!(loc.text("menu.quit"), "menu.quit");
+        assert_eq!(loc.text("hud.score"), "score: {score}");
+        assert_eq!(substitute("{{a}} {b", &[("a", "x".into())]), "{a} {b");
+    }
+
+    #[test]
+    fn test_switch_language() {
+        let mut loc = localization();
+        let revision = loc.revision();
+
+        loc.set_language("RU").unwrap();
+        assert_eq!(loc.language(), "ru");
+        assert_ne!(loc.revision(), revision);
+        assert_eq!(loc.text("menu.resume"), "продолжить");
+        assert_eq!(loc.format("hud.enemies", &[("count", 3.into())]), "3 врага");
+        // 'many' is missing and falls back to 'other'
+        assert_eq!(loc.format("hud.enemies", &[("count", 5.into())]), "5 врагов");
+        // the score comes from the fallback language
+        assert_eq!(loc.format("hud.score", &[("score", 1.into())]), "score: 1");
+
+        assert!(loc.set_language("fr").is_err());
+        assert_eq!(loc.language(), "ru");
+    }
+
+    #[test]
+    fn test_missing_keys() {
+        let missing = localization().missing_keys();
+
+        assert_eq!(missing, vec![MissingKeys {
+            language: "ru".to_string(),
+            keys: vec!["hud.score".to_string()],
+            plural_forms: vec![("hud.enemies".to_string(), PluralCategory::Many)],
+        }]);
+    }
+
+    #[test]
+    fn test_bundled_locales() {
+        let loc = Localization::from_dir(LOCALE_DIR, "en").unwrap();
+
+        assert!(loc.languages().count() > 1);
+        assert_eq!(loc.missing_keys(), vec![]);
+    }
+}
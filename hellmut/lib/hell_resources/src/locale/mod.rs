@@ -0,0 +1,6 @@
+mod localization;
+mod plural;
+mod string_table;
+pub use localization::*;
+pub use plural::*;
+pub use string_table::*;
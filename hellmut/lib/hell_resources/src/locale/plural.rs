@@ -0,0 +1,133 @@
+use serde::Deserialize;
+
+
+
+// ----------------------------------------------------------------------------
+// plural category
+// ----------------------------------------------------------------------------
+
+/// cldr plural categories - every language uses a subset of them, `Other` is used by all of them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+/// group of languages that share their plural rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluralRule {
+    /// no plural forms, e.g. japanese or chinese
+    None,
+    /// one for 1, e.g. english or german
+    OneOther,
+    /// one for 0 and 1, e.g. french
+    ZeroOneOther,
+    /// one, few and many depending on the last digits, e.g. russian or ukrainian
+    EastSlavic,
+    /// one for 1, few and many depending on the last digits
+    Polish,
+    /// one for 1, few for 2 to 4, e.g. czech or slovak
+    WestSlavic,
+}
+
+impl PluralRule {
+    /// rule of the primary subtag of the language, e.g. "pt" for "pt-BR" - unknown languages use `OneOther`
+    pub fn for_language(language: &str) -> Self {
+        let primary = language.split(['-', '_']).next().unwrap_or_default().to_ascii_lowercase();
+
+        match primary.as_str() {
+            "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" => PluralRule::None,
+            "fr" | "hy" | "kab"                           => PluralRule::ZeroOneOther,
+            "ru" | "uk" | "be"                            => PluralRule::EastSlavic,
+            "pl"                                          => PluralRule::Polish,
+            "cs" | "sk"                                   => PluralRule::WestSlavic,
+            _                                             => PluralRule::OneOther,
+        }
+    }
+
+    /// categories the rule can select, the rest is never used by the language
+    pub fn categories(self) -> &'static [PluralCategory] {
+        use PluralCategory::*;
+
+        match self {
+            PluralRule::None         => &[Other],
+            PluralRule::OneOther     => &[One, Other],
+            PluralRule::ZeroOneOther => &[One, Other],
+            PluralRule::EastSlavic   => &[One, Few, Many, Other],
+            PluralRule::Polish       => &[One, Few, Many, Other],
+            PluralRule::WestSlavic   => &[One, Few, Other],
+        }
+    }
+
+    /// fractional numbers are always `Other`
+    pub fn category(self, n: f64) -> PluralCategory {
+        if n.fract() != 0.0 || !n.is_finite() {
+            return PluralCategory::Other;
+        }
+
+        let n = n.abs() as u64;
+        let mod10 = n % 10;
+        let mod100 = n % 100;
+        let is_few = (2..=4).contains(&mod10) && !(12..=14).contains(&mod100);
+
+        match self {
+            PluralRule::None => PluralCategory::Other,
+            PluralRule::OneOther => match n {
+                1 => PluralCategory::One,
+                _ => PluralCategory::Other,
+            },
+            PluralRule::ZeroOneOther => match n {
+                0 | 1 => PluralCategory::One,
+                _     => PluralCategory::Other,
+            },
+            PluralRule::EastSlavic => {
+                if mod10 == 1 && mod100 != 11 { PluralCategory::One }
+                else if is_few                { PluralCategory::Few }
+                else                          { PluralCategory::Many }
+            }
+            PluralRule::Polish => {
+                if n == 1       { PluralCategory::One }
+                else if is_few  { PluralCategory::Few }
+                else            { PluralCategory::Many }
+            }
+            PluralRule::WestSlavic => match n {
+                1      => PluralCategory::One,
+                2..=4  => PluralCategory::Few,
+                _      => PluralCategory::Other,
+            },
+        }
+    }
+}
+
+
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plural_rules() {
+        use PluralCategory::*;
+
+        let en = PluralRule::for_language("en-US");
+        assert_eq!([0.0, 1.0, 2.0, 1.5].map(|n| en.category(n)), [Other, One, Other, Other]);
+
+        let fr = PluralRule::for_language("fr");
+        assert_eq!([0.0, 1.0, 2.0].map(|n| fr.category(n)), [One, One, Other]);
+
+        let ru = PluralRule::for_language("ru");
+        assert_eq!([1.0, 21.0, 11.0, 3.0, 13.0, 5.0].map(|n| ru.category(n)), [One, One, Many, Few, Many, Many]);
+
+        let pl = PluralRule::for_language("pl");
+        assert_eq!([1.0, 21.0, 22.0].map(|n| pl.category(n)), [One, Many, Few]);
+
+        assert_eq!(PluralRule::for_language("ja").category(1.0), Other);
+    }
+}
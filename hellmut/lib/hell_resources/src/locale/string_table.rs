@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use hell_core::error::{HellErrorHelper, HellResult};
+use serde::Deserialize;
+
+use super::PluralCategory;
+
+
+
+// ----------------------------------------------------------------------------
+// strings
+// ----------------------------------------------------------------------------
+
+/// forms of a string that depends on a number, only `other` is required
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluralForms {
+    /// used for 0 in every language when it is set, e.g. "no enemies"
+    pub zero: Option<String>,
+    pub one: Option<String>,
+    pub two: Option<String>,
+    pub few: Option<String>,
+    pub many: Option<String>,
+    pub other: String,
+}
+
+impl PluralForms {
+    pub fn get(&self, category: PluralCategory) -> Option<&str> {
+        match category {
+            PluralCategory::Zero  => self.zero.as_deref(),
+            PluralCategory::One   => self.one.as_deref(),
+            PluralCategory::Two   => self.two.as_deref(),
+            PluralCategory::Few   => self.few.as_deref(),
+            PluralCategory::Many  => self.many.as_deref(),
+            PluralCategory::Other => Some(&self.other),
+        }
+    }
+}
+
+/// written as a plain string or as a map of plural forms in yaml files
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum LocalizedString {
+    Text(String),
+    Plural(PluralForms),
+}
+
+
+
+// ----------------------------------------------------------------------------
+// string table
+// ----------------------------------------------------------------------------
+
+/// all strings of a single language, e.g. loaded from `assets/locales/en.yaml`:
+///
+/// ```yaml
+/// language: en
+/// strings:
+///   menu.resume: resume
+///   hud.enemies:
+///     one: "{count} enemy"
+///     other: "{count} enemies"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StringTable {
+    /// e.g. "en" or "pt-BR"
+    pub language: String,
+    #[serde(default)]
+    pub strings: HashMap<String, LocalizedString>,
+}
+
+impl StringTable {
+    pub fn new(language: impl Into<String>) -> Self {
+        Self {
+            language: language.into(),
+            strings: HashMap::new(),
+        }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> HellResult<Self> {
+        let raw = std::fs::read_to_string(path)?;
+        Self::from_yaml(&raw)
+    }
+
+    pub fn from_yaml(raw: &str) -> HellResult<Self> {
+        let table: Self = serde_yaml::from_str(raw)?;
+        if table.language.trim().is_empty() {
+            return Err(HellErrorHelper::resource_msg_err("string table without a language"));
+        }
+
+        Ok(table)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&LocalizedString> {
+        self.strings.get(key)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.strings.contains_key(key)
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: LocalizedString) {
+        self.strings.insert(key.into(), value);
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.strings.keys().map(String::as_str)
+    }
+}
@@ -1,4 +1,4 @@
-use hell_input::{KeyCode, KeyState};
+use hell_input::{KeyCode, KeyState, MouseButton};
 
 pub(crate) fn keycode_to_hell(k: &winit::event::VirtualKeyCode) -> KeyCode {
     match k{
@@ -148,7 +148,7 @@ pub(crate) fn keycode_to_hell(k: &winit::event::VirtualKeyCode) -> KeyCode {
         winit::event::VirtualKeyCode::Sleep => KeyCode::Invalid,
         winit::event::VirtualKeyCode::Stop => KeyCode::Invalid,
         winit::event::VirtualKeyCode::Sysrq => KeyCode::Invalid,
-        winit::event::VirtualKeyCode::Tab => KeyCode::Invalid,
+        winit::event::VirtualKeyCode::Tab => KeyCode::Tab,
         winit::event::VirtualKeyCode::Underline => KeyCode::Invalid,
         winit::event::VirtualKeyCode::Unlabeled => KeyCode::Invalid,
         winit::event::VirtualKeyCode::VolumeDown => KeyCode::Invalid,
@@ -175,6 +175,15 @@ pub fn element_state_to_hell(state: winit::event::ElementState) -> KeyState {
     }
 }
 
+pub fn mouse_button_to_hell(button: winit::event::MouseButton) -> Option<MouseButton> {
+    match button {
+        winit::event::MouseButton::Left   => Some(MouseButton::Left),
+        winit::event::MouseButton::Right  => Some(MouseButton::Right),
+        winit::event::MouseButton::Middle => Some(MouseButton::Middle),
+        winit::event::MouseButton::Other(_) => None,
+    }
+}
+
 
 // pub(crate) fn update_key_state_winit(&mut self, e: winit::event::KeyboardInput) -> HellResult<()> {
 //     if let Some(code) = e.virtual_keycode {
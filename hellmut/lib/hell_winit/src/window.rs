@@ -2,12 +2,14 @@ use hell_app::HellApp;
 use hell_common::window::{HellWindow, HellSurfaceInfo, HellWindowExtent};
 
 use hell_core::error::{HellResult, OptToHellErr};
+use hell_core::logging::target;
+use tracing::debug;
 use winit::dpi::LogicalSize;
 use winit::error::OsError;
 use winit::event::{VirtualKeyCode, ElementState, KeyboardInput, WindowEvent, Event};
 use winit::event_loop::{EventLoop, ControlFlow};
 
-use crate::input::{keycode_to_hell, element_state_to_hell};
+use crate::input::{keycode_to_hell, element_state_to_hell, mouse_button_to_hell};
 use crate::utils::fps_limiter::FPSLimiter;
 
 
@@ -52,6 +54,7 @@ impl HellWindow for Window {
         HellWindowExtent {
             width: inner_size.width,
             height: inner_size.height,
+            scale_factor: self.window.scale_factor() as f32,
         }
     }
 }
@@ -63,6 +66,7 @@ impl Window {
         HellWindowExtent {
             width: inner_size.width,
             height: inner_size.height,
+            scale_factor: window.scale_factor() as f32,
         }
     }
 }
@@ -84,6 +88,10 @@ impl Window {
 
             match event {
                 Event::WindowEvent { event, .. } => {
+                    // e.g. the window was moved to a screen with a different dpi
+                    if let WindowEvent::ScaleFactorChanged { .. } = event {
+                        handle_resize = true;
+                    }
                     Self::handle_window_event(&event, control_flow, &mut app).expect("failed to handle window event");
                 },
                 Event::MainEventsCleared => {
@@ -108,7 +116,7 @@ impl Window {
             WindowEvent::CloseRequested => { *control_flow = ControlFlow::Exit },
 
             WindowEvent::KeyboardInput { input: KeyboardInput { virtual_keycode: Some(VirtualKeyCode::Escape), state: ElementState::Pressed, .. }, .. } => {
-                println!("> window-event: escape pressed");
+                debug!(target: target::APP, "window-event: escape pressed");
                 *control_flow = ControlFlow::Exit;
             }
 
@@ -118,6 +126,24 @@ impl Window {
                 app.input.update_key_state(keycode, state)?;
             }
 
+            WindowEvent::CursorMoved { position, .. } => {
+                app.input.update_cursor_pos(Some(glam::vec2(position.x as f32, position.y as f32)));
+            }
+
+            WindowEvent::CursorLeft { .. } => {
+                app.input.update_cursor_pos(None);
+            }
+
+            WindowEvent::MouseInput { state, button, .. } => {
+                if let Some(button) = mouse_button_to_hell(*button) {
+                    app.input.update_mouse_button_state(button, element_state_to_hell(*state));
+                }
+            }
+
+            WindowEvent::ReceivedCharacter(c) => {
+                app.input.push_typed_char(*c);
+            }
+
             _ => (),
         };
 
@@ -132,9 +158,9 @@ impl Window {
             if (window_extent.width * window_extent.height) > 0 {
                 app.handle_window_changed(window_extent)?;
                 *handle_resize = false;
-                println!("> resize was handled...");
+                debug!(target: target::APP, "resize was handled");
             } else {
-                println!("> can't handle resize - window-extent is zero");
+                debug!(target: target::APP, "can't handle resize - window-extent is zero");
             }
         } else {
             let delta_time = fps.delta_time();
@@ -3,11 +3,47 @@
 layout(set = 1, binding = 1) uniform sampler2D instance_tex_0;
 
 layout(location = 0) in vec2 in_tex_coord;
+layout(location = 1) in vec4 in_tint;
+layout(location = 2) flat in vec4 in_sdf_params;
+layout(location = 3) flat in vec4 in_outline_color;
+layout(location = 4) flat in vec4 in_shadow_color;
 layout(location = 0) out vec4 out_color;
 
 
 
+// coverage of a distance field sample, the edge is at 'edge'
+float sdf_coverage(float dist, float edge) {
+    // keeps the edge one pixel wide at every scale
+    float smoothing = max(fwidth(dist), 0.0001);
+    return smoothstep(edge - smoothing, edge + smoothing, dist);
+}
+
+vec4 sdf_color() {
+    float outline_width = in_sdf_params.y;
+    vec2 shadow_offset = in_sdf_params.zw / vec2(textureSize(instance_tex_0, 0));
+
+    float dist = texture(instance_tex_0, in_tex_coord).a;
+    float fill = sdf_coverage(dist, 0.5);
+    float outer = sdf_coverage(dist, 0.5 - outline_width);
+
+    vec4 glyph = mix(in_outline_color, in_tint, fill);
+    glyph.a *= outline_width > 0.0 ? outer : fill;
+
+    float shadow_dist = texture(instance_tex_0, in_tex_coord - shadow_offset).a;
+    vec4 shadow = in_shadow_color;
+    shadow.a *= sdf_coverage(shadow_dist, 0.5 - outline_width);
+
+    // glyph over shadow
+    float alpha = glyph.a + shadow.a * (1.0 - glyph.a);
+    vec3 color = mix(shadow.rgb, glyph.rgb, alpha > 0.0 ? glyph.a / alpha : 0.0);
+    return vec4(color, alpha);
+}
+
 void main() {
-    out_color = texture(instance_tex_0, in_tex_coord);
+    if (in_sdf_params.x > 0.5) {
+        out_color = sdf_color();
+    } else {
+        out_color = texture(instance_tex_0, in_tex_coord) * in_tint;
+    }
     // out_color = vec4(1.0, 0.0, 0.0, 1.0);
 }
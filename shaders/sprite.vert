@@ -10,6 +10,13 @@ layout(set = 0, binding = 0) uniform GlobalUbo {
 
 struct LocalUbo {
     mat4 model;
+    // offset (xy) and size (zw) of the sampled texture region
+    vec4 uv_rect;
+    vec4 tint;
+    // x: 1 for distance fields, y: outline width, zw: shadow offset in texture pixels
+    vec4 sdf_params;
+    vec4 outline_color;
+    vec4 shadow_color;
 };
 
 // std140 enforces cpp memory layout
@@ -27,6 +34,10 @@ layout(location = 0) in vec3 in_pos;
 layout(location = 1) in vec2 in_tex_coord;
 
 layout(location = 0) out vec2 out_tex_coord;
+layout(location = 1) out vec4 out_tint;
+layout(location = 2) flat out vec4 out_sdf_params;
+layout(location = 3) flat out vec4 out_outline_color;
+layout(location = 4) flat out vec4 out_shadow_color;
 
 
 
@@ -39,7 +50,11 @@ void main() {
 
     // mat4 model = global.objects[gl_BaseInstance].model;
 
-    mat4 model = local_storage.data[push_constants.local_idx].model;
-    gl_Position = global_ubo.view_proj * model *  vec4(in_pos, 1.0);
-    out_tex_coord = in_tex_coord;
+    LocalUbo local = local_storage.data[push_constants.local_idx];
+    gl_Position = global_ubo.view_proj * local.model *  vec4(in_pos, 1.0);
+    out_tex_coord = local.uv_rect.xy + in_tex_coord * local.uv_rect.zw;
+    out_tint = local.tint;
+    out_sdf_params = local.sdf_params;
+    out_outline_color = local.outline_color;
+    out_shadow_color = local.shadow_color;
 }
@@ -1,22 +1,35 @@
 #!/bin/env bash
 
-orig_dir=$(pwd)
+# compiles every shader stage in 'shaders/' to spir-v, next to its source (e.g. 'sprite.vert' -> 'sprite.vert.spv')
+# uses 'glslc' from the vulkan sdk or 'glslangValidator', whichever is installed
+
 sh_dir=$(dirname $0)
+shader_dir="$sh_dir/../shaders"
+
+# compiler
+# --------
+if command -v glslc > /dev/null; then
+    compile() { glslc "$1" -o "$2"; }
+elif command -v glslangValidator > /dev/null; then
+    compile() { glslangValidator -V "$1" -o "$2" > /dev/null; }
+else
+    echo "neither 'glslc' nor 'glslangValidator' was found, install the vulkan sdk"
+    exit 1
+fi
 
 # compile shaders
 # ---------------
-shader_sh="$sh_dir/../hellengine/engine/tools/compile_shaders.sh"
-
 input=(
-    "$sh_dir/shaders/sprite"
-    "$sh_dir/shaders/test"
+    "$shader_dir/sprite"
 )
 
 echo "start compiling shaders..."
 
 for in in "${input[@]}"; do
-    echo "---> compiling shader '$in'..."
-    eval "$shader_sh" "$in" || exit 1
+    for stage in vert frag; do
+        echo "---> compiling shader '$in.$stage'..."
+        compile "$in.$stage" "$in.$stage.spv" || exit 1
+    done
 done
 
 echo "done compiling shaders..."